@@ -0,0 +1,104 @@
+//! `#[derive(Prompt)]` for `input_macro`; use it through `input_macro`'s
+//! `derive` feature rather than depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr, Path};
+
+/// Derives `input_macro::Prompt` for a struct with named fields, asking for
+/// each field in declaration order.
+///
+/// Fields accept a `#[prompt(...)]` attribute with:
+/// - `text = "..."`: the prompt (defaults to `"<field name>: "`),
+/// - `default = "..."`: text parsed into the field when the user enters an empty line;
+///   text that does not parse fails the prompt with `InputError::Invalid`,
+/// - `validate = path`: a `fn(&T) -> bool` or `fn(&T) -> Result<(), String>` check.
+#[proc_macro_derive(Prompt, attributes(prompt))]
+pub fn derive_prompt(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "Prompt can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "Prompt can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut reads = Vec::new();
+    let mut names = Vec::new();
+    for field in fields {
+        let name = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+        let mut text = LitStr::new(&format!("{}: ", name), name.span());
+        let mut default: Option<LitStr> = None;
+        let mut validate: Option<Path> = None;
+
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("prompt")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("text") {
+                    text = meta.value()?.parse()?;
+                } else if meta.path.is_ident("default") {
+                    default = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("validate") {
+                    validate = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("expected `text`, `default`, or `validate`"));
+                }
+                Ok(())
+            })?;
+        }
+
+        // A default that does not parse fails the read, before anything is asked
+        let default = default.map(|lit| {
+            let message = format!("invalid `default` for field `{}`", name);
+            quote! {
+                .default(
+                    <#ty as ::std::str::FromStr>::from_str(#lit).map_err(|e| {
+                        ::input_macro::InputError::Invalid(::std::format!("{}: {}", #message, e))
+                    })?,
+                )
+            }
+        });
+        let validate = validate.map(|path| quote! { .validate(#path) });
+        reads.push(quote! {
+            let #name = ::input_macro::__prompt_field(
+                ::input_macro::Input::<#ty>::new()
+                    .prompt(#text)
+                    #default
+                    #validate
+                    .reader(&mut *reader),
+            )?;
+        });
+        names.push(name);
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::input_macro::Prompt for #ident #ty_generics #where_clause {
+            fn prompt_from(
+                reader: &mut dyn ::std::io::BufRead,
+            ) -> ::core::result::Result<Self, ::input_macro::InputError<::input_macro::BoxError>> {
+                #(#reads)*
+                ::core::result::Result::Ok(Self { #(#names),* })
+            }
+        }
+    })
+}
@@ -0,0 +1,142 @@
+use std::borrow::Cow;
+use std::fmt::Arguments;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::{parse_line, read_raw_line, strip_line_ending, InputError, Located};
+
+const ESC: char = '\u{1b}';
+const BEL: char = '\u{7}';
+
+/// Returns `text` without its ANSI escape sequences, borrowing it if it has
+/// none.
+///
+/// Removes CSI sequences such as colors and cursor movement (`ESC [ ... m`),
+/// OSC sequences such as hyperlinks and window titles (`ESC ] ... BEL`),
+/// other string sequences ending in `ESC \`, and two-character escapes. Text
+/// pasted from a colored terminal then parses like what was shown.
+///
+/// # Usage:
+/// ```
+/// use input_macro::strip_ansi;
+///
+/// assert_eq!(strip_ansi("\u{1b}[1;32m42\u{1b}[0m"), "42");
+/// assert_eq!(strip_ansi("plain"), "plain");
+/// ```
+pub fn strip_ansi(text: &str) -> Cow<'_, str> {
+    if !text.contains([ESC, '\u{9b}']) {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let kind = match c {
+            ESC => match chars.next() {
+                Some(kind) => kind,
+                None => break,
+            },
+            // The 8-bit form of `ESC [`
+            '\u{9b}' => '[',
+            c => {
+                out.push(c);
+                continue;
+            }
+        };
+        match kind {
+            '[' => {
+                // Parameters and intermediates, then one final byte
+                for c in chars.by_ref() {
+                    if ('\u{40}'..='\u{7e}').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            ']' | 'P' | 'X' | '^' | '_' => {
+                // A string ended by `ESC \`, or by BEL for OSC
+                while let Some(c) = chars.next() {
+                    if c == BEL && kind == ']' {
+                        break;
+                    }
+                    if c == ESC && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            '\u{20}'..='\u{2f}' => {
+                // Intermediates such as the `(` of `ESC ( B`, then one final byte
+                for c in chars.by_ref() {
+                    if !('\u{20}'..='\u{2f}').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Like [`read_input_from`](crate::read_input_from), but removes ANSI escape
+/// sequences from the line, as [`strip_ansi`] does, before parsing it.
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_input_strip_ansi_from;
+///
+/// let mut reader = std::io::Cursor::new("\u{1b}[33m8080\u{1b}[0m\n");
+/// let port: u16 = read_input_strip_ansi_from(&mut reader, None).unwrap();
+/// assert_eq!(port, 8080);
+/// ```
+pub fn read_input_strip_ansi_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let mut input = String::new();
+    let line = read_raw_line(reader, prompt, &mut input)?;
+    parse_line(strip_line_ending(&strip_ansi(line)))
+}
+
+/// A convenience wrapper around [`read_input_strip_ansi_from`] that reads from stdin.
+#[track_caller]
+pub fn read_input_strip_ansi<T>(
+    prompt: Option<Arguments<'_>>,
+) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::__with_stdin(|reader| read_input_strip_ansi_from(reader, prompt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Each kind of sequence is removed along with its parameters.
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(strip_ansi("\u{1b}[38;5;208mtext\u{1b}[m"), "text");
+        assert_eq!(strip_ansi("\u{9b}2Kline"), "line");
+        assert_eq!(
+            strip_ansi("\u{1b}]8;;https://x.io\u{7}link\u{1b}]8;;\u{1b}\\"),
+            "link"
+        );
+        assert_eq!(strip_ansi("\u{1b}(Ba\u{1b}7b\u{1b}"), "ab");
+        assert!(matches!(strip_ansi("a [b]"), Cow::Borrowed("a [b]")));
+    }
+
+    /// Colored input parses once its escapes are gone.
+    #[test]
+    fn test_read_input_strip_ansi() {
+        let mut reader = Cursor::new("\u{1b}[1m-3\u{1b}[0m\r\n");
+        let n: i32 = read_input_strip_ansi_from(&mut reader, None).unwrap();
+        assert_eq!(n, -3);
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt::Arguments;
+use std::future::Future;
+use std::str::FromStr;
+
+use futures_util::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::{strip_line_ending, InputError};
+
+/// The counterpart of [`read_input_to`](crate::read_input_to) for
+/// `futures_io` readers and writers, as used by async-std, smol, and other
+/// runtimes that do not build on tokio.
+///
+/// The prompt is drawn up front, with the prefix, suffix, and theme that
+/// [`read_input_to`](crate::read_input_to) gives it, and recorded by a
+/// [`Transcript`](crate::Transcript) then; it is written to `writer` and
+/// flushed asynchronously when the future is first polled, since the
+/// `futures_io` traits have no stdout of their own. Lines are capped by this
+/// thread's [`limits`](crate::limits) when the future is created. Errors
+/// mirror the sync API: `Io`, `Parse`, `Eof`, and `TooLong`.
+///
+/// # Usage:
+/// ```no_run
+/// # async fn run(
+/// #     mut reader: impl futures_util::io::AsyncBufRead + Unpin,
+/// #     mut stdout: impl futures_util::io::AsyncWrite + Unpin,
+/// # ) {
+/// use input_macro::read_input_to_futures;
+///
+/// let port: u16 = read_input_to_futures(&mut reader, &mut stdout, Some(format_args!("Port: ")))
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+pub fn read_input_to_futures<'a, R, W, T>(
+    reader: &'a mut R,
+    writer: &'a mut W,
+    prompt: Option<Arguments<'_>>,
+) -> impl Future<Output = Result<T, InputError<T::Err>>> + 'a
+where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin,
+    T: FromStr + 'a,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let prompt = prompt.map(crate::draw_prompt);
+    let max_len = crate::limits().max_line_len;
+    async move {
+        if let Some(prompt) = prompt {
+            writer
+                .write_all(prompt.as_bytes())
+                .await
+                .map_err(InputError::Io)?;
+            writer.flush().await.map_err(InputError::Io)?;
+        }
+
+        let input = read_line_limited(reader, max_len).await?;
+        if input.is_empty() {
+            return Err(InputError::Eof);
+        }
+
+        crate::parse_line(strip_line_ending(&input))
+    }
+}
+
+/// Reads one line, at most `max_len` bytes plus the `\n`, as the blocking
+/// readers do; a longer line is discarded up to its end.
+async fn read_line_limited<R, E>(reader: &mut R, max_len: usize) -> Result<String, InputError<E>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut bytes = Vec::new();
+    let cap = (max_len as u64).saturating_add(1);
+    (&mut *reader)
+        .take(cap)
+        .read_until(b'\n', &mut bytes)
+        .await
+        .map_err(InputError::Io)?;
+    if bytes.len() as u64 == cap && bytes.last() != Some(&b'\n') {
+        loop {
+            let buf = reader.fill_buf().await.map_err(InputError::Io)?;
+            let (used, done) = match buf.iter().position(|&b| b == b'\n') {
+                Some(i) => (i + 1, true),
+                None => (buf.len(), buf.is_empty()),
+            };
+            reader.consume_unpin(used);
+            if done {
+                return Err(InputError::TooLong(max_len));
+            }
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| InputError::Io(crate::limits::invalid_utf8()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    /// Polls a future that never waits on a runtime resource.
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut fut = pin!(fut);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+                return out;
+            }
+        }
+    }
+
+    /// Lines are parsed in order, then EOF is reported.
+    #[test]
+    fn test_read_async_lines_then_eof() {
+        let mut reader: &[u8] = b"7\nx\n";
+        let first: u8 =
+            block_on(read_input_to_futures(&mut reader, &mut Vec::new(), None)).unwrap();
+        assert_eq!(first, 7);
+        let second = block_on(read_input_to_futures::<_, _, u8>(
+            &mut reader,
+            &mut Vec::new(),
+            None,
+        ));
+        assert!(matches!(second, Err(InputError::Parse { .. })));
+        let third = block_on(read_input_to_futures::<_, _, u8>(
+            &mut reader,
+            &mut Vec::new(),
+            None,
+        ));
+        assert!(matches!(third, Err(InputError::Eof)));
+    }
+
+    /// The prompt goes to the writer, affixed, before the line is read.
+    #[test]
+    fn test_read_async_prompt() {
+        let mut reader: &[u8] = b"42\n";
+        let mut prompts = Vec::new();
+        let prompt = Some(format_args!("n: "));
+        let n: u8 = block_on(read_input_to_futures(&mut reader, &mut prompts, prompt)).unwrap();
+        assert_eq!((n, prompts.as_slice()), (42, &b"n: "[..]));
+
+        // With the prefix and suffix of the blocking readers
+        crate::set_prompt_suffix("> ");
+        let mut prompts = Vec::new();
+        let prompt = Some(format_args!("n"));
+        let n: u8 = block_on(read_input_to_futures(
+            &mut &b"7\n"[..],
+            &mut prompts,
+            prompt,
+        ))
+        .unwrap();
+        crate::set_prompt_suffix("");
+        assert_eq!((n, prompts.as_slice()), (7, &b"n> "[..]));
+    }
+
+    /// A line over the limit is skipped whole and reported.
+    #[test]
+    fn test_read_async_too_long() {
+        let mut reader: &[u8] = b"123456789\n7\n";
+        let _limits = crate::limits::use_limits(Some(crate::Limits { max_line_len: 4 }));
+        let long = block_on(read_input_to_futures::<_, _, u64>(
+            &mut reader,
+            &mut Vec::new(),
+            None,
+        ));
+        assert!(matches!(long, Err(InputError::TooLong(4))));
+        let next: u8 = block_on(read_input_to_futures(&mut reader, &mut Vec::new(), None)).unwrap();
+        assert_eq!(next, 7);
+    }
+}
@@ -0,0 +1,279 @@
+use std::fmt::Arguments;
+use std::future::Future;
+use std::io;
+use std::panic::Location;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, BufReader, Stdin};
+use tokio::sync::Mutex;
+
+use crate::{strip_line_ending, InputError, Located};
+
+/// The async counterpart of [`read_input_from`](crate::read_input_from), for
+/// tokio readers.
+///
+/// The prompt is written before the returned future is created, as the
+/// blocking readers write it: to the [prompt output](crate::set_prompt_output)
+/// with its prefix, suffix, and theme, and recorded by a
+/// [`Transcript`](crate::Transcript). So the future is `Send` whenever the
+/// reader is; likewise, lines are capped by this thread's
+/// [`limits`](crate::limits) at that point. Errors mirror the sync API: `Io`,
+/// `Parse`, `Eof`, and `TooLong`.
+pub fn read_input_from_async<'a, R, T>(
+    reader: &'a mut R,
+    prompt: Option<Arguments<'_>>,
+) -> impl Future<Output = Result<T, InputError<T::Err>>> + 'a
+where
+    R: AsyncBufRead + Unpin,
+    T: FromStr + 'a,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    read_line_async(
+        reader,
+        prompt.map_or(Ok(()), crate::write_prompt),
+        crate::limits().max_line_len,
+    )
+}
+
+/// A convenience wrapper around [`read_input_from_async`] that reads from a
+/// shared, buffered tokio stdin.
+///
+/// The buffer is kept between calls, so lines read ahead from a pipe are not
+/// lost. Like the blocking stdin readers, it fails with a [`Located`] error.
+#[track_caller]
+pub fn read_input_async<T>(
+    prompt: Option<Arguments<'_>>,
+) -> impl Future<Output = Result<T, Located<InputError<T::Err>>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let prompted = prompt.map_or(Ok(()), crate::write_prompt);
+    let max_len = crate::limits().max_line_len;
+    let location = Location::caller();
+    async move {
+        let mut stdin = shared_stdin().lock().await;
+        read_line_async(&mut *stdin, prompted, max_len)
+            .await
+            .map_err(|error| Located { error, location })
+    }
+}
+
+fn shared_stdin() -> &'static Mutex<BufReader<Stdin>> {
+    static STDIN: OnceLock<Mutex<BufReader<Stdin>>> = OnceLock::new();
+    STDIN.get_or_init(|| Mutex::new(BufReader::new(tokio::io::stdin())))
+}
+
+/// Reads and parses one line, once the prompt has been written with the
+/// result `prompted`.
+async fn read_line_async<R, T>(
+    reader: &mut R,
+    prompted: io::Result<()>,
+    max_len: usize,
+) -> Result<T, InputError<T::Err>>
+where
+    R: AsyncBufRead + Unpin,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    prompted.map_err(InputError::Io)?;
+    let input = read_line_limited(reader, max_len).await?;
+    if input.is_empty() {
+        return Err(InputError::Eof);
+    }
+
+    crate::parse_line(strip_line_ending(&input))
+}
+
+/// Reads one line, at most `max_len` bytes plus the `\n`, as the blocking
+/// readers do; a longer line is discarded up to its end.
+async fn read_line_limited<R, E>(reader: &mut R, max_len: usize) -> Result<String, InputError<E>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut bytes = Vec::new();
+    let cap = (max_len as u64).saturating_add(1);
+    (&mut *reader)
+        .take(cap)
+        .read_until(b'\n', &mut bytes)
+        .await
+        .map_err(InputError::Io)?;
+    if bytes.len() as u64 == cap && bytes.last() != Some(&b'\n') {
+        loop {
+            let buf = reader.fill_buf().await.map_err(InputError::Io)?;
+            let (used, done) = match buf.iter().position(|&b| b == b'\n') {
+                Some(i) => (i + 1, true),
+                None => (buf.len(), buf.is_empty()),
+            };
+            reader.consume(used);
+            if done {
+                return Err(InputError::TooLong(max_len));
+            }
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| InputError::Io(crate::limits::invalid_utf8()))
+}
+
+/// Maps `Err(InputError::Eof)` to `Ok(None)`; used by [`input_async!`].
+#[doc(hidden)]
+pub async fn __eof_as_none_async<T, E, F>(read: F) -> Result<Option<T>, Located<InputError<E>>>
+where
+    F: Future<Output = Result<T, Located<InputError<E>>>>,
+{
+    match read.await {
+        Ok(val) => Ok(Some(val)),
+        Err(Located {
+            error: InputError::Eof,
+            ..
+        }) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// The async counterpart of [`input!`](crate::input), built on tokio.
+///
+/// Expands to a future; `.await` it to get `Ok(Some(value))`, or `Ok(None)` on EOF.
+///
+/// # Usage:
+/// ```no_run
+/// # use input_macro::input_async;
+/// # async fn run() {
+/// let name: Option<String> = input_async!("Enter your name: ").await.unwrap();
+/// let age = input_async!(u8, "Enter {}'s age: ", "Alice").await.unwrap();
+/// # }
+/// ```
+#[macro_export]
+macro_rules! input_async {
+    (@read $t:ty, $prompt:expr) => {
+        $crate::__eof_as_none_async($crate::read_input_async::<$t>($prompt))
+    };
+    () => {
+        $crate::input_async!(@read _, None)
+    };
+    ($fmt:literal $($arg:tt)*) => {
+        $crate::input_async!(@read _, Some(format_args!($fmt $($arg)*)))
+    };
+    ($t:ty) => {
+        $crate::input_async!(@read $t, None)
+    };
+    ($t:ty, $($arg:tt)+) => {
+        $crate::input_async!(@read $t, Some(format_args!($($arg)+)))
+    };
+}
+
+/// The async counterpart of [`input_no_eof!`](crate::input_no_eof): EOF is
+/// returned as `Err(InputError::Eof)`.
+///
+/// # Usage:
+/// ```no_run
+/// # use input_macro::input_no_eof_async;
+/// # async fn run() {
+/// let age: i32 = input_no_eof_async!("Enter your age: ").await.unwrap();
+/// # }
+/// ```
+#[macro_export]
+macro_rules! input_no_eof_async {
+    (@read $t:ty, $prompt:expr) => {
+        $crate::read_input_async::<$t>($prompt)
+    };
+    () => {
+        $crate::input_no_eof_async!(@read _, None)
+    };
+    ($fmt:literal $($arg:tt)*) => {
+        $crate::input_no_eof_async!(@read _, Some(format_args!($fmt $($arg)*)))
+    };
+    ($t:ty) => {
+        $crate::input_no_eof_async!(@read $t, None)
+    };
+    ($t:ty, $($arg:tt)+) => {
+        $crate::input_no_eof_async!(@read $t, Some(format_args!($($arg)+)))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    /// Polls a future that never waits on a runtime resource.
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut fut = pin!(fut);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+                return out;
+            }
+        }
+    }
+
+    /// Lines are parsed in order, then EOF is reported.
+    #[test]
+    fn test_read_async_lines_then_eof() {
+        let mut reader: &[u8] = b"42\r\nhello\n";
+        let first: i32 = block_on(read_input_from_async(&mut reader, None)).unwrap();
+        assert_eq!(first, 42);
+        let second: String = block_on(read_input_from_async(&mut reader, None)).unwrap();
+        assert_eq!(second, "hello");
+        let third = block_on(read_input_from_async::<_, i32>(&mut reader, None));
+        assert!(matches!(third, Err(InputError::Eof)));
+    }
+
+    /// A line that does not parse is reported as `Parse`.
+    #[test]
+    fn test_read_async_parse_error() {
+        let mut reader: &[u8] = b"nope\n";
+        let res = block_on(read_input_from_async::<_, u8>(&mut reader, None));
+        assert!(matches!(res, Err(InputError::Parse { .. })));
+    }
+
+    /// A line over the limit is skipped whole and reported.
+    #[test]
+    fn test_read_async_too_long() {
+        let mut reader: &[u8] = b"123456789\n7\n";
+        let _limits = crate::limits::use_limits(Some(crate::Limits { max_line_len: 4 }));
+        let long = block_on(read_input_from_async::<_, u64>(&mut reader, None));
+        assert!(matches!(long, Err(InputError::TooLong(4))));
+        let next: u8 = block_on(read_input_from_async(&mut reader, None)).unwrap();
+        assert_eq!(next, 7);
+    }
+
+    /// The prompt goes to the prompt output, affixed, as for the blocking readers.
+    #[test]
+    fn test_read_async_prompt() {
+        #[derive(Clone, Default)]
+        struct Shared(std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+        impl std::io::Write for Shared {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.borrow_mut().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let output = Shared::default();
+        crate::set_prompt_output(Box::new(output.clone()));
+        crate::set_prompt_prefix("app> ");
+        let mut reader: &[u8] = b"3\n";
+        let read = read_input_from_async::<_, u8>(&mut reader, Some(format_args!("n: ")));
+        crate::set_prompt_prefix("");
+        crate::reset_prompt_output();
+        assert_eq!(output.0.borrow().as_slice(), b"app> n: ");
+        assert_eq!(block_on(read).unwrap(), 3);
+    }
+
+    /// The returned future can be sent to another thread.
+    #[test]
+    fn test_future_is_send() {
+        fn assert_send<F: Send>(_: F) {}
+        let mut reader: &[u8] = b"1\n";
+        assert_send(read_input_from_async::<_, i32>(
+            &mut reader,
+            Some(format_args!("n: ")),
+        ));
+        assert_send(read_input_async::<i32>(Some(format_args!("n: "))));
+    }
+}
@@ -0,0 +1,239 @@
+use std::convert::Infallible;
+use std::fmt::Arguments;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+use crate::limits::read_line_limited;
+use crate::{strip_line_ending, write_prompt, CancellationToken, InputError, Located};
+
+/// How often a cancellable read checks its token.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+/// A line source that reads on a background thread, so waiting for a line can
+/// time out instead of blocking forever.
+///
+/// The thread reads one line ahead at most. Lines that arrive after a timeout
+/// are kept and returned by the next read, so no input is lost.
+#[derive(Debug)]
+pub struct BackgroundReader {
+    lines: Receiver<Result<String, InputError<Infallible>>>,
+}
+
+impl BackgroundReader {
+    /// Moves `reader` to a new background thread that forwards its lines.
+    ///
+    /// The thread exits at EOF, after an I/O error, or when it next reads a
+    /// line after the `BackgroundReader` has been dropped. Lines are capped by
+    /// the [limits](crate::set_limits) of the thread that calls this.
+    pub fn spawn<R>(mut reader: R) -> Self
+    where
+        R: BufRead + Send + 'static,
+    {
+        let (tx, lines) = mpsc::sync_channel(0);
+        let max_len = crate::limits().max_line_len;
+        thread::spawn(move || loop {
+            let mut buf = String::new();
+            match read_line_limited(&mut reader, &mut buf, max_len) {
+                Ok(0) => break,
+                Ok(_) => {
+                    if tx.send(Ok(buf)).is_err() {
+                        break;
+                    }
+                }
+                // The rest of the line was skipped, so reading can go on
+                Err(err @ InputError::TooLong(_)) => {
+                    if tx.send(Err(err)).is_err() {
+                        break;
+                    }
+                }
+                Err(err) => {
+                    let _ = tx.send(Err(err));
+                    break;
+                }
+            }
+        });
+        BackgroundReader { lines }
+    }
+
+    /// Prints the optional prompt, then waits up to `timeout` for a line and
+    /// parses it into `T`.
+    ///
+    /// Returns `Err(InputError::Timeout)` if no line arrived in time, and
+    /// `Err(InputError::Eof)` once the source is exhausted.
+    pub fn read_timeout<T>(
+        &mut self,
+        prompt: Option<Arguments<'_>>,
+        timeout: Duration,
+    ) -> Result<T, InputError<T::Err>>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display + std::fmt::Debug,
+    {
+        if let Some(prompt_args) = prompt {
+            write_prompt(prompt_args).map_err(InputError::Io)?;
+        }
+        let line = match self.lines.recv_timeout(timeout) {
+            Ok(line) => line.map_err(|err| err.map_parse(|never| match never {}))?,
+            Err(RecvTimeoutError::Timeout) => return Err(InputError::Timeout),
+            Err(RecvTimeoutError::Disconnected) => return Err(InputError::Eof),
+        };
+        crate::parse_line(strip_line_ending(&line))
+    }
+
+    /// Prints the optional prompt, then waits for a line until `token` is
+    /// cancelled, and parses it into `T`.
+    ///
+    /// Returns `Err(InputError::Cancelled)` if the token fires first; a line
+    /// that arrives afterwards is kept for the next read.
+    pub fn read_cancellable<T>(
+        &mut self,
+        prompt: Option<Arguments<'_>>,
+        token: &CancellationToken,
+    ) -> Result<T, InputError<T::Err>>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display + std::fmt::Debug,
+    {
+        self.read_until_stopped(prompt, || {
+            token.is_cancelled().then_some(InputError::Cancelled)
+        })
+    }
+
+    /// Prints the optional prompt, then waits for a line, checking `stop`
+    /// between short waits and returning the error it yields, if any.
+    pub(crate) fn read_until_stopped<T>(
+        &mut self,
+        prompt: Option<Arguments<'_>>,
+        mut stop: impl FnMut() -> Option<InputError<T::Err>>,
+    ) -> Result<T, InputError<T::Err>>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display + std::fmt::Debug,
+    {
+        if let Some(prompt_args) = prompt {
+            write_prompt(prompt_args).map_err(InputError::Io)?;
+        }
+        loop {
+            if let Some(err) = stop() {
+                return Err(err);
+            }
+            match self.read_timeout(None, CANCEL_POLL_INTERVAL) {
+                Err(InputError::Timeout) => continue,
+                result => return result,
+            }
+        }
+    }
+}
+
+/// The process-wide background reader over stdin, spawned on first use.
+pub(crate) fn stdin_reader() -> &'static Mutex<BackgroundReader> {
+    static STDIN: OnceLock<Mutex<BackgroundReader>> = OnceLock::new();
+    STDIN.get_or_init(|| Mutex::new(BackgroundReader::spawn(io::BufReader::new(io::stdin()))))
+}
+
+/// Reads one line from stdin, giving up after `timeout` with
+/// `Err(InputError::Timeout)`.
+///
+/// The first call hands stdin to a background thread for the rest of the
+/// process, so mixing this with the blocking readers on stdin is not supported.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::{read_input_timeout, InputError, Located};
+/// use std::time::Duration;
+///
+/// let port: u16 = match read_input_timeout(Some(format_args!("Port: ")), Duration::from_secs(10)) {
+///     Err(Located { error: InputError::Timeout, .. }) => 8080,
+///     other => other.unwrap(),
+/// };
+/// ```
+#[track_caller]
+pub fn read_input_timeout<T>(
+    prompt: Option<Arguments<'_>>,
+    timeout: Duration,
+) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let mut reader = stdin_reader().lock().unwrap_or_else(|e| e.into_inner());
+    crate::source::located(reader.read_timeout(prompt, timeout))
+}
+
+/// Reads one line from stdin, returning `Err(InputError::Cancelled)` if `token`
+/// is cancelled first.
+///
+/// Shares the background stdin reader of [`read_input_timeout`], with the same
+/// caveat about mixing it with blocking reads.
+#[track_caller]
+pub fn read_input_cancellable<T>(
+    prompt: Option<Arguments<'_>>,
+    token: &CancellationToken,
+) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let mut reader = stdin_reader().lock().unwrap_or_else(|e| e.into_inner());
+    crate::source::located(reader.read_cancellable(prompt, token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufReader, Cursor, Write};
+
+    /// Lines are parsed as they arrive, then EOF is reported.
+    #[test]
+    fn test_read_timeout_lines_then_eof() {
+        let mut reader = BackgroundReader::spawn(Cursor::new("1\n2\n"));
+        let timeout = Duration::from_secs(5);
+        assert_eq!(reader.read_timeout::<i32>(None, timeout).unwrap(), 1);
+        assert_eq!(reader.read_timeout::<i32>(None, timeout).unwrap(), 2);
+        assert!(matches!(
+            reader.read_timeout::<i32>(None, timeout),
+            Err(InputError::Eof)
+        ));
+    }
+
+    /// A read times out while the source is silent, and the late line is kept.
+    #[test]
+    fn test_read_timeout_expires_then_recovers() {
+        let (pipe_reader, mut pipe_writer) = io::pipe().unwrap();
+        let mut reader = BackgroundReader::spawn(BufReader::new(pipe_reader));
+        let res = reader.read_timeout::<i32>(None, Duration::from_millis(20));
+        assert!(matches!(res, Err(InputError::Timeout)));
+
+        pipe_writer.write_all(b"42\n").unwrap();
+        let res = reader.read_timeout::<i32>(None, Duration::from_secs(5));
+        assert_eq!(res.unwrap(), 42);
+    }
+
+    /// Cancelling from another thread aborts a read on a silent source.
+    #[test]
+    fn test_read_cancellable_aborts() {
+        let (pipe_reader, _pipe_writer) = io::pipe().unwrap();
+        let mut reader = BackgroundReader::spawn(BufReader::new(pipe_reader));
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(30));
+            canceller.cancel();
+        });
+        let res = reader.read_cancellable::<i32>(None, &token);
+        assert!(matches!(res, Err(InputError::Cancelled)));
+        handle.join().unwrap();
+    }
+
+    /// Lines that are already available are read normally.
+    #[test]
+    fn test_read_cancellable_reads_line() {
+        let mut reader = BackgroundReader::spawn(Cursor::new("9\n"));
+        let token = CancellationToken::new();
+        assert_eq!(reader.read_cancellable::<i32>(None, &token).unwrap(), 9);
+    }
+}
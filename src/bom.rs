@@ -0,0 +1,109 @@
+use std::io::{self, BufRead, Read};
+
+const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// Consumes a UTF-8 byte order mark at the current position of `reader`, if
+/// there is one.
+fn skip_bom<R: BufRead + ?Sized>(reader: &mut R) -> io::Result<()> {
+    if reader.fill_buf()?.starts_with(UTF8_BOM) {
+        reader.consume(UTF8_BOM.len());
+    }
+    Ok(())
+}
+
+/// A reader wrapper that drops the UTF-8 byte order mark that files saved by
+/// some Windows editors start with, which would otherwise end up in the first
+/// value read.
+///
+/// Stdin and sources installed with [`set_input_source`](crate::set_input_source)
+/// are wrapped automatically; wrap other readers given to the `*_from`
+/// functions yourself.
+///
+/// # Usage:
+/// ```
+/// use input_macro::{read_input_from, SkipBom};
+///
+/// let mut reader = SkipBom::new("\u{feff}42\n".as_bytes());
+/// assert_eq!(read_input_from::<_, i32>(&mut reader, None).unwrap(), 42);
+/// ```
+#[derive(Debug)]
+pub struct SkipBom<R> {
+    inner: R,
+    checked: bool,
+}
+
+impl<R> SkipBom<R> {
+    /// Wraps `inner`, which should be positioned at the start of its input.
+    pub fn new(inner: R) -> Self {
+        SkipBom {
+            inner,
+            checked: false,
+        }
+    }
+
+    /// Wraps `inner`, skipping the check if an earlier wrapper already made it.
+    pub(crate) fn resume(inner: R, checked: bool) -> Self {
+        SkipBom { inner, checked }
+    }
+
+    /// Returns whether the start of the input has been checked for a BOM.
+    pub(crate) fn checked(&self) -> bool {
+        self.checked
+    }
+
+    /// Returns a reference to the wrapped reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: BufRead> Read for SkipBom<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        crate::buffered::read_from_buf(self, buf)
+    }
+}
+
+impl<R: BufRead> BufRead for SkipBom<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if !self.checked {
+            skip_bom(&mut self.inner)?;
+            self.checked = true;
+        }
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{input, read_input_from, set_input_source};
+    use std::io::Cursor;
+
+    /// Only a BOM at the very start is dropped.
+    #[test]
+    fn test_skip_bom() {
+        let mut reader = SkipBom::new(Cursor::new("\u{feff}1\n\u{feff}2\n"));
+        assert_eq!(read_input_from::<_, i32>(&mut reader, None).unwrap(), 1);
+        assert!(read_input_from::<_, i32>(&mut reader, None).is_err());
+
+        let mut reader = SkipBom::new(Cursor::new("3\n"));
+        assert_eq!(read_input_from::<_, i32>(&mut reader, None).unwrap(), 3);
+    }
+
+    /// Installed input sources have their BOM dropped too.
+    #[test]
+    fn test_input_source_skips_bom() {
+        set_input_source(Box::new(Cursor::new("\u{feff}42\n")));
+        let n: Option<i32> = input!().unwrap();
+        assert_eq!(n, Some(42));
+    }
+}
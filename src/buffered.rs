@@ -0,0 +1,52 @@
+use std::io::{self, BufRead};
+
+/// Implements `Read::read` for a reader whose `BufRead` side does the work,
+/// copying out as much of its buffer as fits in `buf`.
+pub(crate) fn read_from_buf<R>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize>
+where
+    R: BufRead + ?Sized,
+{
+    let available = reader.fill_buf()?;
+    let n = available.len().min(buf.len());
+    buf[..n].copy_from_slice(&available[..n]);
+    reader.consume(n);
+    Ok(n)
+}
+
+/// Shows `f` the `amt` bytes about to be consumed from `reader`, then
+/// consumes them.
+///
+/// Those bytes are still buffered, so this does not read any further.
+pub(crate) fn consume_with<R>(reader: &mut R, amt: usize, f: impl FnOnce(&[u8]))
+where
+    R: BufRead + ?Sized,
+{
+    if amt > 0 {
+        if let Ok(buf) = reader.fill_buf() {
+            f(&buf[..amt.min(buf.len())]);
+        }
+    }
+    reader.consume(amt);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufReader, Read};
+
+    /// Reads copy from the buffer, and consumed bytes are seen exactly once.
+    #[test]
+    fn test_read_and_consume() {
+        let mut reader = BufReader::with_capacity(4, "abcdef".as_bytes());
+        let mut buf = [0; 3];
+        assert_eq!(read_from_buf(&mut reader, &mut buf).unwrap(), 3);
+        assert_eq!(&buf, b"abc");
+        let mut seen = Vec::new();
+        consume_with(&mut reader, 1, |bytes| seen.extend_from_slice(bytes));
+        consume_with(&mut reader, 0, |_| panic!("nothing was consumed"));
+        assert_eq!(seen, b"d");
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "ef");
+    }
+}
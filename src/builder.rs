@@ -0,0 +1,663 @@
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::skip::{is_blank, is_comment, read_line_skipping};
+use crate::{write_error, write_hinted_prompt, EofPolicy, InputError, InputSource, Located, Trim};
+
+/// The outcome of a validator passed to [`Input::validate`]: either a `bool`
+/// or a `Result` carrying the rejection message.
+pub trait Validation {
+    /// Converts the outcome into `Ok(())` or the rejection message.
+    fn into_result(self) -> Result<(), String>;
+}
+
+impl Validation for bool {
+    fn into_result(self) -> Result<(), String> {
+        if self {
+            Ok(())
+        } else {
+            Err("value rejected".to_string())
+        }
+    }
+}
+
+impl Validation for Result<(), String> {
+    fn into_result(self) -> Result<(), String> {
+        self
+    }
+}
+
+impl Validation for Result<(), &str> {
+    fn into_result(self) -> Result<(), String> {
+        self.map_err(str::to_string)
+    }
+}
+
+type Validator<'a, T> = Box<dyn FnMut(&T) -> Result<(), String> + 'a>;
+
+/// A builder that composes a prompt, default, validation, retries, and reader
+/// into a single read.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::Input;
+///
+/// let port = Input::<u16>::new()
+///     .prompt("Port: ")
+///     .default(8080)
+///     .validate(|p| *p >= 1024)
+///     .max_attempts(3)
+///     .read()
+///     .unwrap();
+/// ```
+pub struct Input<'a, T> {
+    prompt: Option<String>,
+    default: Option<T>,
+    default_hint: Option<String>,
+    validators: Vec<Validator<'a, T>>,
+    max_attempts: Option<usize>,
+    eof: EofPolicy<T>,
+    strip_ansi: bool,
+    trim: Trim<'a>,
+    skip_blank: bool,
+    comment_prefixes: &'a [&'a str],
+    #[cfg(feature = "unicode")]
+    normalize: Option<crate::Normalization>,
+    #[cfg(feature = "rustyline")]
+    history: Option<std::path::PathBuf>,
+    #[cfg(feature = "rustyline")]
+    completer: Option<std::sync::Arc<dyn crate::Completer + Send + Sync>>,
+    #[cfg(feature = "color")]
+    styles: crate::color::Styles,
+    theme: Option<std::sync::Arc<dyn crate::Theme + Send + Sync>>,
+    reader: Option<&'a mut dyn BufRead>,
+    hide_prompts: bool,
+}
+
+impl<T> Default for Input<'_, T> {
+    fn default() -> Self {
+        Input {
+            prompt: None,
+            default: None,
+            default_hint: None,
+            validators: Vec::new(),
+            max_attempts: None,
+            eof: EofPolicy::Error,
+            strip_ansi: false,
+            trim: Trim::LineEndings,
+            skip_blank: false,
+            comment_prefixes: &[],
+            #[cfg(feature = "unicode")]
+            normalize: None,
+            #[cfg(feature = "rustyline")]
+            history: None,
+            #[cfg(feature = "rustyline")]
+            completer: None,
+            #[cfg(feature = "color")]
+            styles: crate::color::Styles::default(),
+            theme: None,
+            reader: None,
+            hide_prompts: false,
+        }
+    }
+}
+
+impl<'a, T> Input<'a, T> {
+    /// Creates a builder that reads one line from stdin with no prompt.
+    pub fn new() -> Self {
+        <Self as Default>::default()
+    }
+
+    /// Sets the prompt printed before each attempt.
+    pub fn prompt(mut self, prompt: impl std::fmt::Display) -> Self {
+        self.prompt = Some(prompt.to_string());
+        self
+    }
+
+    /// Returns `value` when the user enters an empty (or whitespace-only) line.
+    ///
+    /// `"[default: <value>] "` is shown after the prompt.
+    pub fn default(mut self, value: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        self.default_hint = Some(value.to_string());
+        self.default = Some(value);
+        self
+    }
+
+    /// Adds a check run on every parsed value, in the order added.
+    ///
+    /// The closure returns a `bool` or a `Result<(), String>`; a rejection
+    /// becomes `InputError::Invalid`.
+    pub fn validate<F, V>(mut self, mut validator: F) -> Self
+    where
+        F: FnMut(&T) -> V + 'a,
+        V: Validation,
+    {
+        self.validators
+            .push(Box::new(move |value| validator(value).into_result()));
+        self
+    }
+
+    /// Re-prompts after parse or validation failures, up to `attempts` in total.
+    ///
+    /// Each failure is printed before the next attempt. Once all attempts
+    /// fail, the read returns `Err(InputError::AttemptsExhausted)`. Without
+    /// this option the first failure is returned as-is.
+    pub fn max_attempts(mut self, attempts: usize) -> Self {
+        self.max_attempts = Some(attempts.max(1));
+        self
+    }
+
+    /// Sets what the read returns if the input ends; by default it fails with
+    /// `Err(InputError::Eof)`.
+    ///
+    /// [`EofPolicy::AsNone`] only takes effect through [`Input::read_opt`];
+    /// [`Input::read`] still reports EOF as an error under it.
+    pub fn on_eof(mut self, policy: EofPolicy<T>) -> Self {
+        self.eof = policy;
+        self
+    }
+
+    /// Removes ANSI escape sequences, such as colors pasted from a terminal,
+    /// from each line before it is trimmed; see [`strip_ansi`](crate::strip_ansi).
+    pub fn strip_ansi(mut self, strip: bool) -> Self {
+        self.strip_ansi = strip;
+        self
+    }
+
+    /// Sets how each line is trimmed before parsing; by default only its line
+    /// ending is stripped.
+    pub fn trim(mut self, trim: Trim<'a>) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Silently skips empty and whitespace-only lines, reading until a line
+    /// has content.
+    ///
+    /// An empty line then never selects the [`default`](Input::default).
+    pub fn skip_blank(mut self, skip: bool) -> Self {
+        self.skip_blank = skip;
+        self
+    }
+
+    /// Silently skips lines whose first non-whitespace characters are one of
+    /// `prefixes`, such as [`COMMENT_PREFIXES`](crate::COMMENT_PREFIXES).
+    pub fn skip_comments(mut self, prefixes: &'a [&'a str]) -> Self {
+        self.comment_prefixes = prefixes;
+        self
+    }
+
+    /// Normalizes each line to `form` after trimming, before the default
+    /// check, parsing, and validation.
+    #[cfg(feature = "unicode")]
+    pub fn normalize(mut self, form: crate::Normalization) -> Self {
+        self.normalize = Some(form);
+        self
+    }
+
+    /// Keeps the line history in the file at `path`, so Up and Down recall
+    /// answers from earlier sessions as well as this one.
+    ///
+    /// Applies when stdin reads go through the line editor; a leading `~`
+    /// stands for the home directory.
+    ///
+    /// # Usage:
+    /// ```no_run
+    /// use input_macro::Input;
+    ///
+    /// let query: String = Input::new()
+    ///     .prompt("query> ")
+    ///     .history("~/.mytool_history")
+    ///     .read()
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "rustyline")]
+    pub fn history(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.history = Some(path.into());
+        self
+    }
+
+    /// Offers Tab completions from `completer` while the line is edited.
+    ///
+    /// Applies when stdin reads go through the line editor; see
+    /// [`Completer`](crate::Completer).
+    #[cfg(feature = "rustyline")]
+    pub fn completer(mut self, completer: impl crate::Completer + Send + Sync + 'static) -> Self {
+        self.completer = Some(std::sync::Arc::new(completer));
+        self
+    }
+
+    /// Sets the style of the prompt text; plain by default.
+    ///
+    /// Styles are only drawn when colors are enabled; see [`ColorChoice`](crate::ColorChoice).
+    ///
+    /// # Usage:
+    /// ```no_run
+    /// use input_macro::{Color, Input, Style};
+    ///
+    /// let name: String = Input::new()
+    ///     .prompt("Name: ")
+    ///     .prompt_style(Style::new().bold())
+    ///     .default("anonymous".to_string())
+    ///     .hint_style(Style::new().fg(Color::Cyan))
+    ///     .read()
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "color")]
+    pub fn prompt_style(mut self, style: crate::Style) -> Self {
+        self.styles.prompt = style;
+        self
+    }
+
+    /// Sets the style of the `[default: ...]` hint; dim by default.
+    #[cfg(feature = "color")]
+    pub fn hint_style(mut self, style: crate::Style) -> Self {
+        self.styles.hint = style;
+        self
+    }
+
+    /// Sets the style of the message shown before a retry; red by default.
+    #[cfg(feature = "color")]
+    pub fn error_style(mut self, style: crate::Style) -> Self {
+        self.styles.error = style;
+        self
+    }
+
+    /// Draws the prompt, default hint, and retry messages with `theme`
+    /// instead of the one set with [`set_theme`](crate::set_theme).
+    pub fn theme(mut self, theme: impl crate::Theme + Send + Sync + 'static) -> Self {
+        self.theme = Some(std::sync::Arc::new(theme));
+        self
+    }
+
+    /// Reads from `reader` instead of stdin.
+    pub fn reader(mut self, reader: &'a mut dyn BufRead) -> Self {
+        self.reader = Some(reader);
+        self.hide_prompts = false;
+        self
+    }
+
+    /// Reads from `source` instead of stdin, printing the prompt and retry
+    /// messages only if the source [is interactive](InputSource::is_interactive).
+    pub fn source(mut self, source: &'a mut dyn InputSource) -> Self {
+        self.hide_prompts = !source.is_interactive();
+        self.reader = Some(source);
+        self
+    }
+
+    /// Runs the read.
+    #[track_caller]
+    pub fn read(self) -> Result<T, Located<InputError<T::Err>>>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display + std::fmt::Debug,
+    {
+        crate::source::located(self.read_opt()?.ok_or(InputError::Eof))
+    }
+
+    /// Runs the read, returning `Ok(None)` at EOF under [`EofPolicy::AsNone`].
+    ///
+    /// # Usage:
+    /// ```
+    /// use input_macro::{EofPolicy, Input};
+    ///
+    /// let mut reader = std::io::Cursor::new("");
+    /// let name = Input::<String>::new()
+    ///     .on_eof(EofPolicy::AsNone)
+    ///     .reader(&mut reader)
+    ///     .read_opt()
+    ///     .unwrap();
+    /// assert_eq!(name, None);
+    /// ```
+    #[track_caller]
+    pub fn read_opt(mut self) -> Result<Option<T>, Located<InputError<T::Err>>>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display + std::fmt::Debug,
+    {
+        match self.reader.take() {
+            Some(reader) => {
+                let _hidden = self.hide_prompts.then(crate::source::hide_prompts);
+                crate::source::located(self.read_opt_using(reader))
+            }
+            None => {
+                #[cfg(feature = "rustyline")]
+                let _history = crate::editor::use_history(self.history.as_deref());
+                #[cfg(feature = "rustyline")]
+                let _completer = crate::editor::use_completer(self.completer.take());
+                crate::__with_stdin(|reader| self.read_opt_using(reader))
+            }
+        }
+    }
+
+    /// Runs the read on `reader`, ignoring any reader set with [`Input::reader`].
+    pub(crate) fn read_using(self, reader: &mut dyn BufRead) -> Result<T, InputError<T::Err>>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display + std::fmt::Debug,
+    {
+        self.read_opt_using(reader)?.ok_or(InputError::Eof)
+    }
+
+    fn read_opt_using(self, reader: &mut dyn BufRead) -> Result<Option<T>, InputError<T::Err>>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display + std::fmt::Debug,
+    {
+        self.read_opt_observed(reader, &mut |_, _| {})
+    }
+
+    /// Like `read_opt_using`, but calls `failed` with each attempt that fails
+    /// to parse or validate, and whether another attempt follows it.
+    pub(crate) fn read_opt_observed(
+        mut self,
+        reader: &mut dyn BufRead,
+        failed: &mut dyn FnMut(&InputError<T::Err>, bool),
+    ) -> Result<Option<T>, InputError<T::Err>>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display + std::fmt::Debug,
+    {
+        let _theme = crate::theme::use_theme(self.theme.take());
+        let attempts = self.max_attempts.unwrap_or(1);
+        let mut attempt = 1;
+        loop {
+            match self.read_once(reader) {
+                Err(InputError::Eof) => {
+                    return match std::mem::take(&mut self.eof) {
+                        EofPolicy::Error => Err(InputError::Eof),
+                        EofPolicy::AsNone => Ok(None),
+                        EofPolicy::AsDefault(value) => Ok(Some(value)),
+                        EofPolicy::AsEmptyString => self.accept("").map(Some),
+                    };
+                }
+                Err(err @ (InputError::Parse { .. } | InputError::Invalid(_))) => {
+                    failed(&err, attempt < attempts);
+                    if attempt == attempts {
+                        let err = match self.max_attempts {
+                            Some(n) => InputError::AttemptsExhausted(n),
+                            None => err,
+                        };
+                        #[cfg(feature = "tracing")]
+                        crate::trace::error(&err);
+                        return Err(err);
+                    }
+                    #[cfg(feature = "color")]
+                    let err = crate::color::paint(self.styles.error, err);
+                    write_error(&err).map_err(InputError::Io)?;
+                    attempt += 1;
+                }
+                result => return result.map(Some),
+            }
+        }
+    }
+
+    fn read_once(&mut self, reader: &mut dyn BufRead) -> Result<T, InputError<T::Err>>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display + std::fmt::Debug,
+    {
+        let mut input = String::new();
+        let (skip_blank, comment_prefixes) = (self.skip_blank, self.comment_prefixes);
+        let skipped =
+            |line: &str| (skip_blank && is_blank(line)) || is_comment(line, comment_prefixes);
+        let prompt = PromptText {
+            prompt: self.prompt.as_deref(),
+            hint: self.default_hint.as_deref(),
+            #[cfg(feature = "color")]
+            styles: &self.styles,
+        };
+        prompt.write().map_err(InputError::Io)?;
+        let line = read_line_skipping(reader, None, &mut input, skipped)?;
+        let stripped;
+        let line = if self.strip_ansi {
+            stripped = crate::strip_ansi(line);
+            &stripped
+        } else {
+            line
+        };
+        let line = self.trim.apply(line);
+        #[cfg(feature = "unicode")]
+        if let Some(form) = self.normalize {
+            return self.accept(&form.apply(line));
+        }
+        self.accept(line)
+    }
+
+    /// Applies the default, parsing, and validators to one line.
+    fn accept(&mut self, line: &str) -> Result<T, InputError<T::Err>>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display + std::fmt::Debug,
+    {
+        if line.trim().is_empty() {
+            if let Some(default) = self.default.take() {
+                return Ok(default);
+            }
+        }
+        let value = crate::parse_line::<T>(line)?;
+        for validator in &mut self.validators {
+            validator(&value).map_err(InputError::Invalid)?;
+        }
+        Ok(value)
+    }
+}
+
+/// The prompt [`Input`] writes: its prompt text, then any default hint.
+struct PromptText<'a> {
+    prompt: Option<&'a str>,
+    hint: Option<&'a str>,
+    #[cfg(feature = "color")]
+    styles: &'a crate::color::Styles,
+}
+
+impl PromptText<'_> {
+    /// Prints the prompt, unless there is neither prompt text nor a hint.
+    fn write(&self) -> std::io::Result<()> {
+        if self.prompt.is_none() && self.hint.is_none() {
+            return Ok(());
+        }
+        let hint = self.hint.map(|hint| crate::theme::default_hint(&hint));
+        let hint = hint.as_deref().map(|hint| {
+            // Styled without its trailing space, which the cursor follows
+            let (hint, space) = hint.split_at(hint.trim_end().len());
+            #[cfg(feature = "color")]
+            let hint = crate::color::paint(self.styles.hint, hint);
+            format!("{}{}", hint, space)
+        });
+        let hint = hint.as_ref().map(|hint| hint as &dyn std::fmt::Display);
+        match self.prompt {
+            Some(prompt) => {
+                #[cfg(feature = "color")]
+                let prompt = crate::color::paint(self.styles.prompt, prompt);
+                write_hinted_prompt(Some(format_args!("{}", prompt)), hint)
+            }
+            None => write_hinted_prompt(None, hint),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// An empty line takes the default.
+    #[test]
+    fn test_default() {
+        let mut reader = Cursor::new("\n");
+        let port = Input::<u16>::new()
+            .prompt("Port: ")
+            .default(8080)
+            .reader(&mut reader)
+            .read();
+        assert_eq!(port.unwrap(), 8080);
+    }
+
+    /// Parse and validation failures are retried up to the limit.
+    #[test]
+    fn test_validate_with_retries() {
+        let mut reader = Cursor::new("abc\n80\n8443\n");
+        let port = Input::<u16>::new()
+            .validate(|p| *p >= 1024)
+            .max_attempts(3)
+            .reader(&mut reader)
+            .read();
+        assert_eq!(port.unwrap(), 8443);
+    }
+
+    /// Running out of attempts reports `AttemptsExhausted`.
+    #[test]
+    fn test_attempts_exhausted() {
+        let mut reader = Cursor::new("1\n2\n3\n");
+        let res = Input::<u16>::new()
+            .validate(|p| if *p > 10 { Ok(()) } else { Err("too small") })
+            .max_attempts(2)
+            .reader(&mut reader)
+            .read();
+        assert!(matches!(
+            res,
+            Err(crate::Located {
+                error: InputError::AttemptsExhausted(2),
+                ..
+            })
+        ));
+    }
+
+    /// Without `max_attempts`, the first failure is returned unchanged.
+    #[test]
+    fn test_single_attempt_error() {
+        let mut reader = Cursor::new("5\n");
+        let res = Input::<u16>::new()
+            .validate(|p| if *p > 10 { Ok(()) } else { Err("too small") })
+            .reader(&mut reader)
+            .read();
+        assert!(
+            matches!(res, Err(crate::Located { error: InputError::Invalid(ref msg), .. }) if msg == "too small")
+        );
+    }
+
+    /// The EOF policy decides the result once the input ends.
+    #[test]
+    fn test_on_eof() {
+        let read = |policy| {
+            Input::<u16>::new()
+                .default(8080)
+                .on_eof(policy)
+                .reader(&mut Cursor::new(""))
+                .read_opt()
+        };
+        assert!(matches!(
+            read(EofPolicy::Error),
+            Err(Located {
+                error: InputError::Eof,
+                ..
+            })
+        ));
+        assert_eq!(read(EofPolicy::AsNone).unwrap(), None);
+        assert_eq!(read(EofPolicy::AsDefault(80)).unwrap(), Some(80));
+        assert_eq!(read(EofPolicy::AsEmptyString).unwrap(), Some(8080));
+
+        let res = Input::<u16>::new()
+            .on_eof(EofPolicy::AsNone)
+            .reader(&mut Cursor::new(""))
+            .read();
+        assert!(matches!(
+            res,
+            Err(crate::Located {
+                error: InputError::Eof,
+                ..
+            })
+        ));
+    }
+
+    /// Lines are trimmed with the chosen policy before the default check and parsing.
+    #[test]
+    fn test_trim() {
+        let mut reader = Cursor::new("  Ada  \n");
+        let name = Input::<String>::new()
+            .trim(Trim::Whitespace)
+            .reader(&mut reader)
+            .read();
+        assert_eq!(name.unwrap(), "Ada");
+    }
+
+    /// Blank lines are skipped instead of taking the default.
+    #[test]
+    fn test_skip_blank() {
+        let mut reader = Cursor::new("\n  \n5\n\n");
+        let read = |reader: &mut Cursor<&str>| {
+            Input::<u16>::new()
+                .default(8080)
+                .skip_blank(true)
+                .reader(reader)
+                .read()
+        };
+        assert_eq!(read(&mut reader).unwrap(), 5);
+        assert!(matches!(
+            read(&mut reader),
+            Err(crate::Located {
+                error: InputError::Eof,
+                ..
+            })
+        ));
+    }
+
+    /// Comment lines are skipped, so annotated input parses.
+    #[test]
+    fn test_skip_comments() {
+        let mut reader = Cursor::new("# listen port\n\n8080\n");
+        let port = Input::<u16>::new()
+            .skip_blank(true)
+            .skip_comments(crate::COMMENT_PREFIXES)
+            .reader(&mut reader)
+            .read();
+        assert_eq!(port.unwrap(), 8080);
+    }
+
+    /// Escapes are removed before trimming, so whitespace inside them is trimmed too.
+    #[test]
+    fn test_strip_ansi() {
+        let mut reader = Cursor::new(" \u{1b}[32m 7 \u{1b}[0m\n");
+        let n = Input::<u8>::new()
+            .strip_ansi(true)
+            .trim(Trim::Whitespace)
+            .reader(&mut reader)
+            .read();
+        assert_eq!(n.unwrap(), 7);
+    }
+
+    /// A non-interactive source gets no prompts; an interactive one does.
+    #[test]
+    fn test_source_prompts() {
+        let (n, prompts) = crate::testing::ScriptedReader::new().record(|| {
+            let mut file = Cursor::new("x\n3\n");
+            let read = Input::<u8>::new()
+                .prompt("n: ")
+                .max_attempts(2)
+                .source(&mut file)
+                .read();
+            let mut person = crate::testing::ScriptedReader::new().respond("4");
+            let asked = Input::<u8>::new().prompt("m: ").source(&mut person).read();
+            (read.unwrap(), asked.unwrap())
+        });
+        assert_eq!(n, (3, 4));
+        assert_eq!(prompts.concat(), "m: ");
+    }
+
+    /// Validators see the normalized line.
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_normalize() {
+        let mut reader = Cursor::new("cafe\u{301}\n");
+        let drink = Input::<String>::new()
+            .normalize(crate::Normalization::Nfc)
+            .validate(|s| s == "caf\u{e9}")
+            .reader(&mut reader)
+            .read();
+        assert_eq!(drink.unwrap(), "caf\u{e9}");
+    }
+}
@@ -0,0 +1,368 @@
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+use crate::{
+    parse_tokens, read_input_from, read_trimmed_line, strip_line_ending, InputError, LineError,
+    Located, TokenError, TrackedReader,
+};
+
+/// A line that failed to parse during a bulk read.
+#[derive(Debug)]
+pub struct LineFailure<E> {
+    /// One-based line number within the input.
+    pub line: usize,
+    /// The line's text, without its line ending; empty for a line over the
+    /// [`limits`](crate::limits).
+    pub text: String,
+    /// Why the line failed: `Parse` with the parse error, or `TooLong` for a
+    /// line over the limits, which is skipped.
+    pub error: InputError<E>,
+}
+
+/// The outcome of [`collect_all_from`]: every parsed value plus the lines that failed.
+#[derive(Debug)]
+pub struct Collected<T, E> {
+    /// Successfully parsed values, in input order.
+    pub values: Vec<T>,
+    /// Lines that failed to parse or were too long, in input order.
+    pub failures: Vec<LineFailure<E>>,
+}
+
+impl<T, E> Collected<T, E> {
+    /// Returns `true` if every line parsed.
+    pub fn is_clean(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Reads `reader` to exhaustion, parsing each line into `T`.
+///
+/// Lines that fail to parse, or are longer than the
+/// [`limits`](crate::limits) allow, do not stop the read; they are recorded in
+/// [`Collected::failures`] with their line number and text. Only an I/O
+/// error aborts.
+pub fn collect_all_from<R, T>(reader: &mut R) -> io::Result<Collected<T, T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+{
+    collect_all_with(reader, str::parse)
+}
+
+/// Like [`collect_all_from`], but parses each line with `parse`.
+pub(crate) fn collect_all_with<R, T, E>(
+    reader: &mut R,
+    parse: impl Fn(&str) -> Result<T, E>,
+) -> io::Result<Collected<T, E>>
+where
+    R: BufRead + ?Sized,
+{
+    let mut collected = Collected {
+        values: Vec::new(),
+        failures: Vec::new(),
+    };
+    let mut buf = String::new();
+    let mut line = 0;
+    loop {
+        buf.clear();
+        let read = crate::limits::read_line_or_skip(reader, &mut buf)?;
+        if read == Some(0) {
+            return Ok(collected);
+        }
+        line += 1;
+        if read.is_none() {
+            collected.failures.push(LineFailure {
+                line,
+                text: String::new(),
+                error: InputError::TooLong(crate::limits().max_line_len),
+            });
+            continue;
+        }
+        let text = strip_line_ending(&buf);
+        match parse(text) {
+            Ok(value) => collected.values.push(value),
+            Err(error) => collected.failures.push(LineFailure {
+                line,
+                text: text.to_string(),
+                error: InputError::parse(error, text),
+            }),
+        }
+    }
+}
+
+/// A convenience wrapper around [`collect_all_from`] that reads all of stdin.
+#[track_caller]
+pub fn collect_all<T: FromStr>() -> Result<Collected<T, T::Err>, Located<io::Error>> {
+    crate::__with_stdin(|reader| collect_all_from(reader))
+}
+
+/// Reads exactly `n` lines, parsing each into `T`, as in inputs whose first
+/// line gives the count of the lines that follow.
+///
+/// The first line that fails stops the read and is reported with its
+/// one-based number among the `n`: a parse error, or `InputError::Eof` if the
+/// input holds fewer lines.
+///
+/// # Usage:
+/// ```
+/// use input_macro::{read_input_from, read_n_lines_from};
+///
+/// let mut reader = std::io::Cursor::new("3\n10\n20\n");
+/// let n: usize = read_input_from(&mut reader, None).unwrap();
+/// let err = read_n_lines_from::<_, i32>(&mut reader, n).unwrap_err();
+/// assert_eq!(err.to_string(), "line 3: EOF encountered");
+/// ```
+pub fn read_n_lines_from<R, T>(reader: &mut R, n: usize) -> Result<Vec<T>, LineError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let mut reader = TrackedReader::new(reader);
+    let mut values = Vec::with_capacity(n.min(1024));
+    for line in 1..=n {
+        match read_input_from(&mut reader, None) {
+            Ok(value) => values.push(value),
+            Err(error) => {
+                return Err(LineError {
+                    line,
+                    offset: reader.offset(),
+                    error,
+                })
+            }
+        }
+    }
+    Ok(values)
+}
+
+/// A convenience wrapper around [`read_n_lines_from`] that reads from stdin.
+#[track_caller]
+pub fn read_n_lines<T>(n: usize) -> Result<Vec<T>, Located<LineError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::__with_stdin(|reader| read_n_lines_from(reader, n))
+}
+
+/// Reads `rows` lines of `cols` whitespace-separated values each, as a grid
+/// of rows.
+///
+/// The first bad row stops the read and is reported with its one-based
+/// number among the `rows`. Its error is `InputError::Parse` holding a
+/// [`TokenError`] that names the zero-based column: `Invalid` for a cell that
+/// failed to parse, `Missing` for a short row, and `Unexpected` for a long one.
+/// Too few lines is `InputError::Eof`.
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_matrix_from;
+///
+/// let mut reader = std::io::Cursor::new("1 2 3\n4 5 6\n");
+/// let grid: Vec<Vec<u8>> = read_matrix_from(&mut reader, 2, 3).unwrap();
+/// assert_eq!(grid, [[1, 2, 3], [4, 5, 6]]);
+///
+/// let mut reader = std::io::Cursor::new("1 2\n3 x\n");
+/// let err = read_matrix_from::<_, u8>(&mut reader, 2, 2).unwrap_err();
+/// assert!(err.to_string().starts_with("line 2: Parse error: token 1 (\"x\")"));
+/// ```
+pub fn read_matrix_from<R, T>(
+    reader: &mut R,
+    rows: usize,
+    cols: usize,
+) -> Result<Vec<Vec<T>>, MatrixError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let mut grid = Vec::with_capacity(rows.min(1024));
+    read_rows(reader, rows, cols, |row| grid.push(row))?;
+    Ok(grid)
+}
+
+/// A failure on one token of one row of a matrix, as returned by
+/// [`read_matrix_from`] and [`read_matrix_flat_from`].
+pub type MatrixError<E> = LineError<TokenError<E>>;
+
+/// A convenience wrapper around [`read_matrix_from`] that reads from stdin.
+#[track_caller]
+pub fn read_matrix<T>(rows: usize, cols: usize) -> Result<Vec<Vec<T>>, Located<MatrixError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::__with_stdin(|reader| read_matrix_from(reader, rows, cols))
+}
+
+/// Like [`read_matrix_from`], but returns the cells in one `Vec`, row after
+/// row, so the cell at `(row, col)` is at index `row * cols + col`.
+pub fn read_matrix_flat_from<R, T>(
+    reader: &mut R,
+    rows: usize,
+    cols: usize,
+) -> Result<Vec<T>, MatrixError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let mut cells = Vec::with_capacity(rows.saturating_mul(cols).min(1 << 16));
+    read_rows(reader, rows, cols, |row| cells.extend(row))?;
+    Ok(cells)
+}
+
+/// A convenience wrapper around [`read_matrix_flat_from`] that reads from stdin.
+#[track_caller]
+pub fn read_matrix_flat<T>(rows: usize, cols: usize) -> Result<Vec<T>, Located<MatrixError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::__with_stdin(|reader| read_matrix_flat_from(reader, rows, cols))
+}
+
+/// Reads `rows` rows of exactly `cols` values, passing each to `push`.
+fn read_rows<R, T>(
+    reader: &mut R,
+    rows: usize,
+    cols: usize,
+    mut push: impl FnMut(Vec<T>),
+) -> Result<(), MatrixError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+{
+    let mut reader = TrackedReader::new(reader);
+    for row in 1..=rows {
+        let mut input = String::new();
+        let result = read_trimmed_line(&mut reader, None, &mut input).and_then(|line| {
+            // Check the width first, so an extra token is reported as such
+            // even if it would not parse
+            let cells = match line.split_whitespace().nth(cols) {
+                Some(token) => Err(TokenError::Unexpected {
+                    index: cols,
+                    token: token.to_string(),
+                }),
+                None => parse_tokens(line, None, false).and_then(|cells: Vec<T>| {
+                    if cells.len() < cols {
+                        Err(TokenError::Missing { index: cells.len() })
+                    } else {
+                        Ok(cells)
+                    }
+                }),
+            };
+            cells.map_err(|source| InputError::parse(source, line))
+        });
+        match result {
+            Ok(cells) => push(cells),
+            Err(error) => {
+                return Err(LineError {
+                    line: row,
+                    offset: reader.offset(),
+                    error,
+                })
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Good lines are collected and bad lines are reported with their numbers.
+    #[test]
+    fn test_collect_all_reports_failures() {
+        let mut reader = Cursor::new("1\n2\nthree\n4\r\n\n");
+        let collected: Collected<i32, _> = collect_all_from(&mut reader).unwrap();
+        assert_eq!(collected.values, vec![1, 2, 4]);
+        let failed: Vec<(usize, &str)> = collected
+            .failures
+            .iter()
+            .map(|f| (f.line, f.text.as_str()))
+            .collect();
+        assert_eq!(failed, vec![(3, "three"), (5, "")]);
+        assert!(!collected.is_clean());
+    }
+
+    /// An over-long line is reported as a failure, and the lines after it
+    /// are still collected.
+    #[test]
+    fn test_collect_all_too_long() {
+        let _limits = crate::limits::use_limits(Some(crate::Limits { max_line_len: 4 }));
+        let mut reader = Cursor::new("1\n123456789\nx\n4\n");
+        let collected: Collected<i32, _> = collect_all_from(&mut reader).unwrap();
+        assert_eq!(collected.values, [1, 4]);
+        let failure = &collected.failures[0];
+        assert_eq!((failure.line, failure.text.as_str()), (2, ""));
+        assert!(matches!(failure.error, InputError::TooLong(4)));
+        assert!(matches!(
+            collected.failures[1].error,
+            InputError::Parse { ref input, .. } if input == "x"
+        ));
+    }
+
+    /// Exactly `n` lines are consumed, and a bad one is reported by number.
+    #[test]
+    fn test_read_n_lines() {
+        let mut reader = Cursor::new("1\n2\n3\nx\n5\n");
+        assert_eq!(read_n_lines_from::<_, i32>(&mut reader, 2).unwrap(), [1, 2]);
+        let err = read_n_lines_from::<_, i32>(&mut reader, 3).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(matches!(err.error, crate::InputError::Parse { ref input, .. } if input == "x"));
+        assert!(read_n_lines_from::<_, i32>(&mut reader, 0)
+            .unwrap()
+            .is_empty());
+    }
+
+    /// Rows of the wrong width are reported with the column at fault.
+    #[test]
+    fn test_read_matrix_dimensions() {
+        let mut reader = Cursor::new("1 2\n3\n1 2 3\n1 2 x\n");
+        let mut read = || read_matrix_from::<_, u8>(&mut reader, 1, 2).map_err(|e| e.error);
+        assert_eq!(read().unwrap(), [[1, 2]]);
+        assert!(matches!(
+            read(),
+            Err(InputError::Parse {
+                source: TokenError::Missing { index: 1 },
+                ..
+            })
+        ));
+        assert!(matches!(
+            read(),
+            Err(InputError::Parse {
+                source: TokenError::Unexpected { index: 2, .. },
+                ..
+            })
+        ));
+        // An extra token is reported as extra, not as unparsable
+        assert!(matches!(
+            read(),
+            Err(InputError::Parse {
+                source: TokenError::Unexpected { index: 2, ref token },
+                ..
+            }) if token == "x"
+        ));
+        assert!(matches!(read(), Err(InputError::Eof)));
+    }
+
+    /// The flat form lays rows end to end.
+    #[test]
+    fn test_read_matrix_flat() {
+        let mut reader = Cursor::new("1 2 3\n4 5 6\n");
+        let cells: Vec<i32> = read_matrix_flat_from(&mut reader, 2, 3).unwrap();
+        assert_eq!(cells, [1, 2, 3, 4, 5, 6]);
+    }
+
+    /// Empty input yields an empty, clean result.
+    #[test]
+    fn test_collect_all_empty() {
+        let mut reader = Cursor::new("");
+        let collected: Collected<i32, _> = collect_all_from(&mut reader).unwrap();
+        assert!(collected.values.is_empty());
+        assert!(collected.is_clean());
+    }
+}
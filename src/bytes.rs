@@ -0,0 +1,133 @@
+use std::convert::Infallible;
+use std::ffi::OsString;
+use std::fmt::Arguments;
+use std::io::BufRead;
+
+use crate::limits::read_until_limited;
+use crate::{limits, write_prompt, InputError, Located};
+
+/// Prints the optional prompt, then reads one line as raw bytes, without its
+/// trailing `\n` or `\r\n`.
+///
+/// The bytes need not be UTF-8. EOF, I/O errors, and [`Limits`](crate::Limits)
+/// are handled as for [`read_input_from`](crate::read_input_from).
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_bytes_line_from;
+///
+/// let mut reader = std::io::Cursor::new(&b"\x00\xff\r\n"[..]);
+/// assert_eq!(read_bytes_line_from(&mut reader, None).unwrap(), b"\x00\xff");
+/// ```
+pub fn read_bytes_line_from<R>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<Vec<u8>, InputError<Infallible>>
+where
+    R: BufRead + ?Sized,
+{
+    if let Some(prompt_args) = prompt {
+        write_prompt(prompt_args).map_err(InputError::Io)?;
+    }
+    let mut bytes = Vec::new();
+    if read_until_limited(reader, &mut bytes, b"\n", limits().max_line_len)? == 0 {
+        return Err(InputError::Eof);
+    }
+    if bytes.last() == Some(&b'\n') {
+        bytes.pop();
+        if bytes.last() == Some(&b'\r') {
+            bytes.pop();
+        }
+    }
+    Ok(bytes)
+}
+
+/// A convenience wrapper around [`read_bytes_line_from`] that reads from stdin.
+#[track_caller]
+pub fn read_bytes_line(
+    prompt: Option<Arguments<'_>>,
+) -> Result<Vec<u8>, Located<InputError<Infallible>>> {
+    crate::__with_stdin(|reader| read_bytes_line_from(reader, prompt))
+}
+
+/// Prints the optional prompt, then reads one line as an [`OsString`],
+/// without its line ending.
+///
+/// On Unix the bytes are taken as they are, so file names that are not
+/// valid UTF-8 survive a round trip. Elsewhere an `OsString` cannot hold
+/// arbitrary bytes, so the line must be UTF-8, as with
+/// [`read_input_from`](crate::read_input_from).
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::read_os_line;
+/// use std::path::PathBuf;
+///
+/// let path = PathBuf::from(read_os_line(Some(format_args!("File: "))).unwrap());
+/// ```
+pub fn read_os_line_from<R>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<OsString, InputError<Infallible>>
+where
+    R: BufRead + ?Sized,
+{
+    let bytes = read_bytes_line_from(reader, prompt)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+        Ok(OsString::from_vec(bytes))
+    }
+    #[cfg(not(unix))]
+    {
+        String::from_utf8(bytes).map(OsString::from).map_err(|_| {
+            InputError::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            ))
+        })
+    }
+}
+
+/// A convenience wrapper around [`read_os_line_from`] that reads from stdin.
+#[track_caller]
+pub fn read_os_line(
+    prompt: Option<Arguments<'_>>,
+) -> Result<OsString, Located<InputError<Infallible>>> {
+    crate::__with_stdin(|reader| read_os_line_from(reader, prompt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Only the line ending is stripped, and a final unterminated line is kept.
+    #[test]
+    fn test_read_bytes_line() {
+        let mut reader = Cursor::new(&b" a\r\r\n\n\xc3\x28"[..]);
+        let mut read = || read_bytes_line_from(&mut reader, None);
+        assert_eq!(read().unwrap(), b" a\r");
+        assert_eq!(read().unwrap(), b"");
+        assert_eq!(read().unwrap(), b"\xc3\x28");
+        assert!(matches!(read(), Err(InputError::Eof)));
+    }
+
+    /// UTF-8 lines read back as the same text.
+    #[test]
+    fn test_read_os_line() {
+        let mut reader = Cursor::new(&b"notes.txt\n"[..]);
+        assert_eq!(read_os_line_from(&mut reader, None).unwrap(), "notes.txt");
+    }
+
+    /// On Unix, non-UTF-8 file names keep their exact bytes.
+    #[cfg(unix)]
+    #[test]
+    fn test_read_os_line_non_utf8() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let mut reader = Cursor::new(&b"caf\xe9.txt\n"[..]);
+        let name = read_os_line_from(&mut reader, None).unwrap();
+        assert_eq!(name.as_bytes(), b"caf\xe9.txt");
+    }
+}
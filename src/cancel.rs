@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+use std::future::{poll_fn, Future};
+use std::panic::Location;
+use std::pin::pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
+
+use crate::{InputError, Located};
+
+/// A cloneable handle for aborting pending reads.
+///
+/// Cancelling the token makes reads waiting on it return
+/// `Err(InputError::Cancelled)`: blocking reads through
+/// [`BackgroundReader::read_cancellable`](crate::BackgroundReader::read_cancellable),
+/// and async reads wrapped in [`cancellable`].
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    next_id: AtomicU64,
+    /// The waker of each pending [`cancellable`] read, by registration id.
+    wakers: Mutex<HashMap<u64, Waker>>,
+}
+
+impl CancellationToken {
+    /// Creates a token that has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the token, waking every async read waiting on it.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        let wakers = std::mem::take(&mut *self.wakers());
+        for waker in wakers.into_values() {
+            waker.wake();
+        }
+    }
+
+    /// Returns `true` once [`cancel`](Self::cancel) has been called on any clone.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn wakers(&self) -> std::sync::MutexGuard<'_, HashMap<u64, Waker>> {
+        self.inner.wakers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A read's slot for its waker in a token, freed when the read is dropped.
+struct Registration<'a> {
+    token: &'a CancellationToken,
+    id: u64,
+}
+
+impl<'a> Registration<'a> {
+    fn new(token: &'a CancellationToken) -> Self {
+        let id = token.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        Self { token, id }
+    }
+
+    /// Stores `waker` in this read's slot, replacing the one from an earlier poll.
+    fn register(&self, waker: &Waker) {
+        let mut wakers = self.token.wakers();
+        match wakers.get_mut(&self.id) {
+            Some(old) if old.will_wake(waker) => {}
+            Some(old) => old.clone_from(waker),
+            None => {
+                wakers.insert(self.id, waker.clone());
+            }
+        }
+    }
+}
+
+impl Drop for Registration<'_> {
+    fn drop(&mut self) {
+        self.token.wakers().remove(&self.id);
+    }
+}
+
+/// An error that reads wrapped in [`cancellable`] can fail with, once their
+/// token is cancelled.
+pub trait CancelError {
+    /// Returns the error for a read cancelled by the call at `location`.
+    fn cancelled(location: &'static Location<'static>) -> Self;
+}
+
+impl<E> CancelError for InputError<E> {
+    fn cancelled(_: &'static Location<'static>) -> Self {
+        InputError::Cancelled
+    }
+}
+
+impl<E> CancelError for Located<InputError<E>> {
+    fn cancelled(location: &'static Location<'static>) -> Self {
+        Located {
+            error: InputError::Cancelled,
+            location,
+        }
+    }
+}
+
+/// Runs the async `read`, returning `Err(InputError::Cancelled)` as soon as
+/// `token` is cancelled.
+///
+/// Works with any runtime; the `read` future is dropped on cancellation. A
+/// read that fails with a [`Located`] error, such as
+/// [`read_input_async`](crate::read_input_async), is cancelled with the
+/// location of this call.
+///
+/// # Usage:
+/// ```no_run
+/// # #[cfg(feature = "tokio")]
+/// # async fn run() {
+/// use input_macro::{cancellable, read_input_async, CancellationToken};
+///
+/// let token = CancellationToken::new();
+/// let name: Result<String, _> = cancellable(&token, read_input_async(None)).await;
+/// # }
+/// ```
+#[track_caller]
+pub fn cancellable<'a, T, E, F>(
+    token: &'a CancellationToken,
+    read: F,
+) -> impl Future<Output = Result<T, E>> + 'a
+where
+    E: CancelError,
+    F: Future<Output = Result<T, E>> + 'a,
+{
+    let location = Location::caller();
+    async move {
+        let registration = Registration::new(token);
+        let mut read = pin!(read);
+        poll_fn(|cx| {
+            if token.is_cancelled() {
+                return Poll::Ready(Err(E::cancelled(location)));
+            }
+            registration.register(cx.waker());
+            // Re-check so a cancel racing with registration is not missed
+            if token.is_cancelled() {
+                return Poll::Ready(Err(E::cancelled(location)));
+            }
+            read.as_mut().poll(cx)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::task::{Context, Wake};
+
+    /// Cancelling one clone is visible through every other clone.
+    #[test]
+    fn test_token_clones_share_state() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+        token.cancel();
+        assert!(clone.is_cancelled());
+    }
+
+    /// A pending read resolves to `Cancelled` once the token fires.
+    #[test]
+    fn test_cancellable_aborts_pending_read() {
+        let token = CancellationToken::new();
+        let read = std::future::pending::<Result<i32, InputError<()>>>();
+        let mut fut = pin!(cancellable(&token, read));
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert!(fut.as_mut().poll(&mut cx).is_pending());
+        token.cancel();
+        assert!(matches!(
+            fut.as_mut().poll(&mut cx),
+            Poll::Ready(Err(InputError::Cancelled))
+        ));
+    }
+
+    /// A read that completes first is returned unchanged.
+    #[test]
+    fn test_cancellable_passes_through_result() {
+        let token = CancellationToken::new();
+        let read = std::future::ready(Ok::<_, InputError<()>>(5));
+        let mut fut = pin!(cancellable(&token, read));
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(matches!(fut.as_mut().poll(&mut cx), Poll::Ready(Ok(5))));
+    }
+
+    /// Located reads are cancelled with the location of the `cancellable` call.
+    #[test]
+    fn test_cancellable_located() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let read = std::future::pending::<Result<i32, Located<InputError<()>>>>();
+        let line = line!() + 1;
+        let mut fut = pin!(cancellable(&token, read));
+        let mut cx = Context::from_waker(Waker::noop());
+        match fut.as_mut().poll(&mut cx) {
+            Poll::Ready(Err(err)) => {
+                assert!(matches!(err.error, InputError::Cancelled));
+                assert_eq!(err.location().line(), line);
+                assert_eq!(err.location().file(), file!());
+            }
+            _ => panic!("the read was not cancelled"),
+        }
+    }
+
+    /// Each read keeps one waker however often it is polled, and frees it
+    /// when dropped.
+    #[test]
+    fn test_cancellable_reuses_waker_slot() {
+        struct Noop;
+        impl Wake for Noop {
+            fn wake(self: Arc<Self>) {}
+        }
+
+        let token = CancellationToken::new();
+        let read = std::future::pending::<Result<i32, InputError<()>>>();
+        let mut fut = Box::pin(cancellable(&token, read));
+        for _ in 0..3 {
+            let waker = Waker::from(Arc::new(Noop));
+            assert!(fut
+                .as_mut()
+                .poll(&mut Context::from_waker(&waker))
+                .is_pending());
+        }
+        assert_eq!(token.wakers().len(), 1);
+        drop(fut);
+        assert!(token.wakers().is_empty());
+    }
+}
@@ -0,0 +1,157 @@
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::io::{self, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, ExitStatus, Stdio};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::{BackgroundReader, InputError};
+
+/// How long reads wait for the child unless [`ChildSession::set_timeout`] says otherwise.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A child process driven line by line, in the manner of `expect`: lines are
+/// sent to its stdin, and its stdout is read and parsed like any other input.
+///
+/// Every read waits at most the session's timeout, failing with
+/// `InputError::Timeout`, and the child closing its stdout is
+/// `InputError::Eof`. Output is read in whole lines, so a prompt the child
+/// prints without a line ending is only seen once the line is completed.
+/// The child is killed if the session is dropped before it exits.
+///
+/// # Usage:
+/// ```no_run
+/// use std::process::Command;
+/// use input_macro::ChildSession;
+///
+/// let mut bc = ChildSession::spawn(Command::new("bc").arg("-q")).unwrap();
+/// bc.send_line("6 * 7").unwrap();
+/// let answer: u32 = bc.read().unwrap();
+/// assert_eq!(answer, 42);
+/// bc.send_line("quit").unwrap();
+/// assert!(bc.wait().unwrap().success());
+/// ```
+#[derive(Debug)]
+pub struct ChildSession {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    stdout: BackgroundReader,
+    timeout: Duration,
+}
+
+impl ChildSession {
+    /// Starts `command` with its stdin and stdout piped to the session.
+    ///
+    /// Its stderr is inherited unless `command` already configures it.
+    pub fn spawn(command: &mut Command) -> io::Result<Self> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take();
+        let stdout = child.stdout.take().expect("stdout is piped");
+        Ok(ChildSession {
+            child,
+            stdin,
+            stdout: BackgroundReader::spawn(BufReader::new(stdout)),
+            timeout: DEFAULT_TIMEOUT,
+        })
+    }
+
+    /// Sets how long each read waits for the child; the default is 10 seconds.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Writes `line` and a newline to the child's stdin, and flushes it.
+    ///
+    /// Fails with `BrokenPipe` once the child has exited or its stdin was
+    /// [closed](ChildSession::close_stdin).
+    pub fn send_line(&mut self, line: impl Display) -> io::Result<()> {
+        let stdin = self
+            .stdin
+            .as_mut()
+            .ok_or_else(|| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        writeln!(stdin, "{}", line)?;
+        stdin.flush()
+    }
+
+    /// Reads the child's next line of output and parses it into `T`, as
+    /// [`read_input_from`](crate::read_input_from) does.
+    pub fn read<T>(&mut self) -> Result<T, InputError<T::Err>>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display + std::fmt::Debug,
+    {
+        self.stdout.read_timeout(None, self.timeout)
+    }
+
+    /// Reads lines of output until one contains `text`, and returns that
+    /// line; the lines before it are discarded.
+    ///
+    /// The timeout covers the whole search, not each line.
+    pub fn expect(&mut self, text: &str) -> Result<String, InputError<Infallible>> {
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(InputError::Timeout);
+            }
+            let line: String = self.stdout.read_timeout(None, remaining)?;
+            if line.contains(text) {
+                return Ok(line);
+            }
+        }
+    }
+
+    /// Closes the child's stdin, which many tools take as the end of their input.
+    pub fn close_stdin(&mut self) {
+        self.stdin = None;
+    }
+
+    /// Closes the child's stdin and waits for it to exit.
+    pub fn wait(mut self) -> io::Result<ExitStatus> {
+        self.close_stdin();
+        self.child.wait()
+    }
+}
+
+impl Drop for ChildSession {
+    fn drop(&mut self) {
+        if let Ok(None) = self.child.try_wait() {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// Sent lines come back through the child and parse as values.
+    #[test]
+    fn test_round_trip() {
+        let mut cat = ChildSession::spawn(&mut Command::new("cat")).unwrap();
+        cat.send_line(42).unwrap();
+        assert_eq!(cat.read::<u32>().unwrap(), 42);
+        cat.send_line("x").unwrap();
+        assert!(matches!(cat.read::<u32>(), Err(InputError::Parse { .. })));
+        assert!(cat.wait().unwrap().success());
+    }
+
+    /// `expect` skips to the matching line, and silence or exit end the wait.
+    #[test]
+    fn test_expect_timeout_and_eof() {
+        let script = "echo starting; echo 'ready on 8080'; read line; echo \"got $line\"";
+        let mut child = ChildSession::spawn(Command::new("sh").args(["-c", script])).unwrap();
+        assert_eq!(child.expect("ready").unwrap(), "ready on 8080");
+        child.set_timeout(Duration::from_millis(50));
+        assert!(matches!(child.read::<String>(), Err(InputError::Timeout)));
+        child.send_line("go").unwrap();
+        child.set_timeout(DEFAULT_TIMEOUT);
+        assert_eq!(child.read::<String>().unwrap(), "got go");
+        assert!(matches!(child.read::<String>(), Err(InputError::Eof)));
+        assert!(matches!(child.expect("more"), Err(InputError::Eof)));
+    }
+}
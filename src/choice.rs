@@ -0,0 +1,628 @@
+use std::convert::Infallible;
+use std::fmt::Arguments;
+use std::io::BufRead;
+
+use crate::{
+    read_trimmed_line, write_error, write_menu_item, write_own_prompt, write_prompt,
+    write_prompt_line, InputError, Located,
+};
+
+/// A type with a fixed set of named values, such as a fieldless enum, that can
+/// be picked by name in [`read_choice_from`].
+///
+/// Implement it by hand or with [`impl_choices!`](crate::impl_choices).
+pub trait Choices: Sized {
+    /// The names of the valid choices, in display order.
+    fn names() -> &'static [&'static str];
+
+    /// Returns the value for `name`, which is always one of [`names`](Self::names).
+    fn from_name(name: &str) -> Option<Self>;
+}
+
+/// Any enum deriving strum's `EnumString` and `VariantNames` is a [`Choices`].
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::read_choice;
+/// use strum::{EnumString, VariantNames};
+///
+/// #[derive(EnumString, VariantNames)]
+/// #[strum(serialize_all = "lowercase")]
+/// enum Env { Development, Staging, Production }
+///
+/// let env: Env = read_choice(Some(format_args!("Environment "))).unwrap();
+/// ```
+#[cfg(feature = "strum")]
+impl<T> Choices for T
+where
+    T: strum::VariantNames + std::str::FromStr,
+{
+    fn names() -> &'static [&'static str] {
+        T::VARIANTS
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        name.parse().ok()
+    }
+}
+
+/// Implements [`Choices`] for an enum from a list of `Variant => "name"` pairs.
+///
+/// # Usage:
+/// ```
+/// # use input_macro::{impl_choices, Choices};
+/// #[derive(Debug, PartialEq)]
+/// enum Level { Low, Medium, High }
+///
+/// impl_choices!(Level { Low => "low", Medium => "medium", High => "high" });
+///
+/// assert_eq!(Level::names(), &["low", "medium", "high"]);
+/// assert_eq!(Level::from_name("high"), Some(Level::High));
+/// ```
+#[macro_export]
+macro_rules! impl_choices {
+    ($t:ty { $($variant:ident => $name:literal),+ $(,)? }) => {
+        impl $crate::Choices for $t {
+            fn names() -> &'static [&'static str] {
+                &[$($name),+]
+            }
+
+            fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $($name => Some(Self::$variant),)+
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+/// How an answer is matched against the names of choices.
+///
+/// Every mode ignores case and surrounding whitespace, and an exact match
+/// always wins. An answer matching several names re-prompts with them listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Matching {
+    /// The answer must be a whole name.
+    #[default]
+    Exact,
+    /// The answer may also be the start of a name, e.g. `"prod"` for
+    /// `"production"`.
+    Prefix,
+    /// Like `Prefix`, and failing that, the answer's characters may appear
+    /// in order within a name, e.g. `"prdn"` for `"production"`.
+    Fuzzy,
+}
+
+impl Matching {
+    /// Returns the indices of the `names` that `answer` picks under this mode,
+    /// from the strictest kind of match found.
+    fn find<S: AsRef<str>>(self, names: &[S], answer: &str) -> Vec<usize> {
+        let answer = fold(answer);
+        if answer.is_empty() {
+            return Vec::new();
+        }
+        let names: Vec<String> = names.iter().map(|name| fold(name.as_ref())).collect();
+        let find = |matches: &dyn Fn(&str) -> bool| -> Vec<usize> {
+            (0..names.len()).filter(|&i| matches(&names[i])).collect()
+        };
+        let exact = find(&|name| name == answer);
+        if !exact.is_empty() || self == Matching::Exact {
+            return exact;
+        }
+        let prefix = find(&|name| name.starts_with(&answer));
+        if !prefix.is_empty() || self == Matching::Prefix {
+            return prefix;
+        }
+        find(&|name| is_subsequence(&answer, name))
+    }
+}
+
+/// Prepares a name or answer for comparison: trimmed, ASCII-lowercased, and
+/// with the `unicode` feature, in NFC so composed and decomposed spellings
+/// match.
+fn fold(text: &str) -> String {
+    let text = text.trim();
+    #[cfg(feature = "unicode")]
+    let text = &*crate::Normalization::Nfc.apply(text);
+    text.to_ascii_lowercase()
+}
+
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut haystack = haystack.chars();
+    needle.chars().all(|c| haystack.any(|h| h == c))
+}
+
+/// Prints why `answer` picked nothing, or listing the `names` it matched.
+fn write_no_match<S: std::fmt::Display>(
+    answer: &str,
+    matched: &[S],
+    all: &[S],
+) -> std::io::Result<()> {
+    if matched.is_empty() {
+        return write_error(&format_args!("Please choose one of: {}.", join(all)));
+    }
+    write_error(&format_args!(
+        "{:?} could be any of: {}. Please be more specific.",
+        answer.trim(),
+        join(matched)
+    ))
+}
+
+fn join<S: std::fmt::Display>(items: &[S]) -> String {
+    items
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Asks for one of `T`'s [`Choices`], re-prompting until the answer matches.
+///
+/// The valid names are listed after the prompt, e.g. `"Level [low/medium/high] "`.
+/// Matching ignores case and surrounding whitespace.
+///
+/// # Usage:
+/// ```no_run
+/// # use input_macro::{impl_choices, read_choice};
+/// # enum Level { Low, Medium, High }
+/// # impl_choices!(Level { Low => "low", Medium => "medium", High => "high" });
+/// let level: Level = read_choice(Some(format_args!("Level "))).unwrap();
+/// ```
+pub fn read_choice_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<T, InputError<Infallible>>
+where
+    R: BufRead + ?Sized,
+    T: Choices,
+{
+    read_choice_matching_from(reader, prompt, Matching::Exact)
+}
+
+/// A convenience wrapper around [`read_choice_from`] that reads from stdin.
+#[track_caller]
+pub fn read_choice<T: Choices>(
+    prompt: Option<Arguments<'_>>,
+) -> Result<T, Located<InputError<Infallible>>> {
+    crate::__with_stdin(|reader| read_choice_from(reader, prompt))
+}
+
+/// Like [`read_choice_from`], but matches answers against the names as
+/// `matching` says, so e.g. `"prod"` can pick `"production"`.
+///
+/// # Usage:
+/// ```
+/// # use input_macro::{impl_choices, read_choice_matching_from, Matching};
+/// # #[derive(Debug, PartialEq)]
+/// # enum Env { Development, Devops, Production }
+/// # impl_choices!(Env { Development => "development", Devops => "devops", Production => "production" });
+/// // "dev" is ambiguous, so the next line is read too
+/// let mut reader = std::io::Cursor::new("dev\ndvlp\n");
+/// let env: Env = read_choice_matching_from(&mut reader, None, Matching::Fuzzy).unwrap();
+/// assert_eq!(env, Env::Development);
+/// ```
+pub fn read_choice_matching_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    matching: Matching,
+) -> Result<T, InputError<Infallible>>
+where
+    R: BufRead + ?Sized,
+    T: Choices,
+{
+    let names = T::names();
+    loop {
+        if let Some(prompt_args) = prompt {
+            write_prompt(prompt_args).map_err(InputError::Io)?;
+        }
+        write_own_prompt(format_args!("[{}] ", names.join("/"))).map_err(InputError::Io)?;
+        let mut input = String::new();
+        let line = read_trimmed_line(reader, None, &mut input)?;
+        let found = matching.find(names, line);
+        if let [index] = found[..] {
+            if let Some(value) = T::from_name(names[index]) {
+                return Ok(value);
+            }
+        }
+        let matched: Vec<&str> = found.iter().map(|&i| names[i]).collect();
+        write_no_match(line, &matched, names).map_err(InputError::Io)?;
+    }
+}
+
+/// A convenience wrapper around [`read_choice_matching_from`] that reads from stdin.
+#[track_caller]
+pub fn read_choice_matching<T: Choices>(
+    prompt: Option<Arguments<'_>>,
+    matching: Matching,
+) -> Result<T, Located<InputError<Infallible>>> {
+    crate::__with_stdin(|reader| read_choice_matching_from(reader, prompt, matching))
+}
+
+/// Shows `options` as a numbered menu and returns the zero-based index of the
+/// one picked, re-prompting until a valid number is entered.
+///
+/// The prompt, if any, is printed on its own line above the menu. An empty
+/// `options` slice is rejected with `Err(InputError::Invalid)`.
+pub fn select_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    options: &[T],
+) -> Result<usize, InputError<Infallible>>
+where
+    R: BufRead + ?Sized,
+    T: std::fmt::Display,
+{
+    if options.is_empty() {
+        return Err(InputError::Invalid("no options to choose from".to_string()));
+    }
+    if let Some(prompt_args) = prompt {
+        write_prompt_line(prompt_args).map_err(InputError::Io)?;
+    }
+    for (i, option) in options.iter().enumerate() {
+        write_menu_item(i + 1, option).map_err(InputError::Io)?;
+    }
+    loop {
+        write_own_prompt(format_args!("Choice [1-{}]: ", options.len())).map_err(InputError::Io)?;
+        let mut input = String::new();
+        let line = read_trimmed_line(reader, None, &mut input)?;
+        match line.trim().parse::<usize>() {
+            Ok(n) if (1..=options.len()).contains(&n) => return Ok(n - 1),
+            _ => write_error(&format_args!(
+                "Please enter a number between 1 and {}.",
+                options.len()
+            ))
+            .map_err(InputError::Io)?,
+        }
+    }
+}
+
+/// Lets the user pick one of `options` on stdin, returning its zero-based index.
+///
+/// With the `crossterm` feature, and when both stdin and stdout are terminals,
+/// this is an arrow-key selector (Enter picks, Esc cancels with
+/// `Err(InputError::Cancelled)`, and Ctrl-C returns
+/// `Err(InputError::Interrupted)`). Otherwise it falls back to the numbered
+/// menu of [`select_from`].
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::select;
+///
+/// let envs = ["development", "staging", "production"];
+/// let picked = select(Some(format_args!("Deploy to:")), &envs).unwrap();
+/// println!("Deploying to {}", envs[picked]);
+/// ```
+#[track_caller]
+pub fn select<T: std::fmt::Display>(
+    prompt: Option<Arguments<'_>>,
+    options: &[T],
+) -> Result<usize, Located<InputError<Infallible>>> {
+    #[cfg(feature = "crossterm")]
+    if crate::is_interactive() && !crate::has_input_source() && !options.is_empty() {
+        return crate::source::located(crate::term::select_interactive(prompt, options));
+    }
+    crate::__with_stdin(|reader| select_from(reader, prompt, options))
+}
+
+/// Like [`select_from`], but also accepts an option's text, matched as
+/// `matching` says, in place of its number.
+///
+/// # Usage:
+/// ```
+/// use input_macro::{select_matching_from, Matching};
+///
+/// let envs = ["development", "staging", "production"];
+/// let mut reader = std::io::Cursor::new("pro\n");
+/// let picked = select_matching_from(&mut reader, None, &envs, Matching::Prefix).unwrap();
+/// assert_eq!(envs[picked], "production");
+/// ```
+pub fn select_matching_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    options: &[T],
+    matching: Matching,
+) -> Result<usize, InputError<Infallible>>
+where
+    R: BufRead + ?Sized,
+    T: std::fmt::Display,
+{
+    if options.is_empty() {
+        return Err(InputError::Invalid("no options to choose from".to_string()));
+    }
+    if let Some(prompt_args) = prompt {
+        write_prompt_line(prompt_args).map_err(InputError::Io)?;
+    }
+    for (i, option) in options.iter().enumerate() {
+        write_menu_item(i + 1, option).map_err(InputError::Io)?;
+    }
+    let names: Vec<String> = options.iter().map(ToString::to_string).collect();
+    loop {
+        write_own_prompt(format_args!("Choice [1-{}]: ", options.len())).map_err(InputError::Io)?;
+        let mut input = String::new();
+        let line = read_trimmed_line(reader, None, &mut input)?;
+        if let Ok(n) = line.trim().parse::<usize>() {
+            if (1..=options.len()).contains(&n) {
+                return Ok(n - 1);
+            }
+        }
+        let found = matching.find(&names, line);
+        if let [index] = found[..] {
+            return Ok(index);
+        }
+        let matched: Vec<&str> = found.iter().map(|&i| names[i].as_str()).collect();
+        let all: Vec<&str> = names.iter().map(String::as_str).collect();
+        write_no_match(line, &matched, &all).map_err(InputError::Io)?;
+    }
+}
+
+/// A convenience wrapper around [`select_matching_from`] that reads from stdin.
+#[track_caller]
+pub fn select_matching<T: std::fmt::Display>(
+    prompt: Option<Arguments<'_>>,
+    options: &[T],
+    matching: Matching,
+) -> Result<usize, Located<InputError<Infallible>>> {
+    crate::__with_stdin(|reader| select_matching_from(reader, prompt, options, matching))
+}
+
+/// Shows `options` as a numbered menu and returns the zero-based indices of
+/// the ones picked, in ascending order.
+///
+/// Picks are entered as comma- or space-separated numbers, e.g. `1, 3`; an
+/// empty line picks nothing. Any invalid number re-prompts.
+pub fn multi_select_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    options: &[T],
+) -> Result<Vec<usize>, InputError<Infallible>>
+where
+    R: BufRead + ?Sized,
+    T: std::fmt::Display,
+{
+    if let Some(prompt_args) = prompt {
+        write_prompt_line(prompt_args).map_err(InputError::Io)?;
+    }
+    for (i, option) in options.iter().enumerate() {
+        write_menu_item(i + 1, option).map_err(InputError::Io)?;
+    }
+    loop {
+        write_own_prompt(format_args!(
+            "Choices (comma-separated) [1-{}]: ",
+            options.len()
+        ))
+        .map_err(InputError::Io)?;
+        let mut input = String::new();
+        let line = read_trimmed_line(reader, None, &mut input)?;
+        let picks: Option<Vec<usize>> = line
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|token| !token.is_empty())
+            .map(|token| match token.parse::<usize>() {
+                Ok(n) if (1..=options.len()).contains(&n) => Some(n - 1),
+                _ => None,
+            })
+            .collect();
+        match picks {
+            Some(mut picks) => {
+                picks.sort_unstable();
+                picks.dedup();
+                return Ok(picks);
+            }
+            None => write_error(&format_args!(
+                "Please enter numbers between 1 and {}, separated by commas.",
+                options.len()
+            ))
+            .map_err(InputError::Io)?,
+        }
+    }
+}
+
+/// Lets the user pick any number of `options` on stdin, returning their
+/// zero-based indices in ascending order.
+///
+/// With the `crossterm` feature, and when both stdin and stdout are terminals,
+/// this is an arrow-key checklist (Space toggles, Enter confirms, Esc cancels
+/// with `Err(InputError::Cancelled)`, and Ctrl-C returns
+/// `Err(InputError::Interrupted)`). Otherwise it falls back to the numbered
+/// menu of [`multi_select_from`].
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::multi_select;
+///
+/// let components = ["core", "docs", "examples"];
+/// for i in multi_select(Some(format_args!("Install which components?")), &components).unwrap() {
+///     println!("Installing {}", components[i]);
+/// }
+/// ```
+#[track_caller]
+pub fn multi_select<T: std::fmt::Display>(
+    prompt: Option<Arguments<'_>>,
+    options: &[T],
+) -> Result<Vec<usize>, Located<InputError<Infallible>>> {
+    #[cfg(feature = "crossterm")]
+    if crate::is_interactive() && !crate::has_input_source() && !options.is_empty() {
+        return crate::source::located(crate::term::multi_select_interactive(prompt, options));
+    }
+    crate::__with_stdin(|reader| multi_select_from(reader, prompt, options))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[derive(Debug, PartialEq)]
+    enum Level {
+        Low,
+        Medium,
+        High,
+    }
+
+    impl_choices!(Level { Low => "low", Medium => "medium", High => "high" });
+
+    /// Names match case-insensitively.
+    #[test]
+    fn test_read_choice() {
+        let mut reader = Cursor::new(" HIGH \nlow\n");
+        assert_eq!(
+            read_choice_from::<_, Level>(&mut reader, None).unwrap(),
+            Level::High
+        );
+        assert_eq!(
+            read_choice_from::<_, Level>(&mut reader, None).unwrap(),
+            Level::Low
+        );
+    }
+
+    /// Unknown answers re-prompt until one matches, and EOF ends the loop.
+    #[test]
+    fn test_read_choice_reprompts() {
+        let mut reader = Cursor::new("extreme\n\nmedium\n");
+        assert_eq!(
+            read_choice_from::<_, Level>(&mut reader, None).unwrap(),
+            Level::Medium
+        );
+        assert!(matches!(
+            read_choice_from::<_, Level>(&mut reader, None),
+            Err(InputError::Eof)
+        ));
+    }
+
+    /// The numbered menu returns a zero-based index and re-prompts on bad input.
+    #[test]
+    fn test_select_from() {
+        let options = ["dev", "prod"];
+        let mut reader = Cursor::new("0\nthree\n2\n");
+        assert_eq!(select_from(&mut reader, None, &options).unwrap(), 1);
+        let empty: [&str; 0] = [];
+        assert!(matches!(
+            select_from(&mut reader, None, &empty),
+            Err(InputError::Invalid(_))
+        ));
+    }
+
+    /// Only the caller's prompt gets the prefix and suffix, once, not the menu or hint.
+    #[test]
+    fn test_prompt_affixes() {
+        crate::set_prompt_prefix("myctl> ");
+        crate::set_prompt_suffix(": ");
+        let (picked, select_prompts) = crate::testing::ScriptedReader::new()
+            .respond("9")
+            .respond("2")
+            .record(|| select(Some(format_args!("Deploy to")), &["dev", "prod"]));
+        let (level, choice_prompts) = crate::testing::ScriptedReader::new()
+            .respond("high")
+            .record(|| read_choice::<Level>(Some(format_args!("Level"))));
+        crate::set_prompt_prefix("");
+        crate::set_prompt_suffix("");
+        assert_eq!(picked.unwrap(), 1);
+        assert_eq!(
+            select_prompts.concat(),
+            "myctl> Deploy to: \n  1) dev\n  2) prod\nChoice [1-2]: \
+             Please enter a number between 1 and 2.\nChoice [1-2]: "
+        );
+        assert_eq!(level.unwrap(), Level::High);
+        assert_eq!(choice_prompts.concat(), "myctl> Level: [low/medium/high] ");
+    }
+
+    /// Comma- or space-separated numbers are collected, sorted, and deduplicated.
+    #[test]
+    fn test_multi_select_from() {
+        let options = ["core", "docs", "examples"];
+        let mut reader = Cursor::new("3, 1 3\n1,4\n\n");
+        assert_eq!(
+            multi_select_from(&mut reader, None, &options).unwrap(),
+            vec![0, 2]
+        );
+        // "4" is out of range, so the next line (empty) is read instead
+        assert!(multi_select_from(&mut reader, None, &options)
+            .unwrap()
+            .is_empty());
+    }
+
+    /// Prefixes and subsequences pick a name only when they pick just one.
+    #[test]
+    fn test_matching_find() {
+        let names = ["development", "devops", "production"];
+        assert_eq!(Matching::Exact.find(&names, "prod"), Vec::<usize>::new());
+        assert_eq!(Matching::Prefix.find(&names, " PROD "), [2]);
+        assert_eq!(Matching::Prefix.find(&names, "dev"), [0, 1]);
+        assert_eq!(Matching::Prefix.find(&names, "prdn"), Vec::<usize>::new());
+        assert_eq!(Matching::Fuzzy.find(&names, "prdn"), [2]);
+        assert_eq!(Matching::Fuzzy.find(&names, "dvs"), [1]);
+        assert_eq!(Matching::Fuzzy.find(&names, ""), Vec::<usize>::new());
+        // An exact name is never ambiguous with longer names it prefixes
+        assert_eq!(Matching::Prefix.find(&["dev", "devops"], "dev"), [0]);
+    }
+
+    /// Ambiguous answers re-prompt listing the candidates.
+    #[test]
+    fn test_read_choice_matching_ambiguous() {
+        #[derive(Debug, PartialEq)]
+        enum Env {
+            Development,
+            Devops,
+        }
+        impl_choices!(Env { Development => "development", Devops => "devops" });
+
+        let (env, prompts) = crate::testing::ScriptedReader::new()
+            .respond("dev")
+            .respond("devo")
+            .record(|| read_choice_matching::<Env>(None, Matching::Prefix));
+        assert_eq!(env.unwrap(), Env::Devops);
+        assert_eq!(
+            prompts[1],
+            "\"dev\" could be any of: development, devops. Please be more specific.\n\
+             [development/devops] "
+        );
+    }
+
+    /// Option text works alongside numbers in the menu.
+    #[test]
+    fn test_select_matching_from() {
+        let options = ["dev", "prod"];
+        let mut reader = Cursor::new("1\nPR\n");
+        let mut select = || select_matching_from(&mut reader, None, &options, Matching::Prefix);
+        assert_eq!(select().unwrap(), 0);
+        assert_eq!(select().unwrap(), 1);
+    }
+
+    /// With the `unicode` feature, decomposed answers match composed names.
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_choice_normalized() {
+        #[derive(Debug, PartialEq)]
+        enum Drink {
+            Cafe,
+            The,
+        }
+        impl_choices!(Drink { Cafe => "caf\u{e9}", The => "th\u{e9}" });
+
+        let mut reader = Cursor::new("the\u{301}\n");
+        assert_eq!(
+            read_choice_from::<_, Drink>(&mut reader, None).unwrap(),
+            Drink::The
+        );
+        assert_eq!(Matching::Exact.find(Drink::names(), "Caf\u{e9}"), [0]);
+    }
+
+    /// With the `strum` feature, `VariantNames + FromStr` types are choices.
+    #[cfg(feature = "strum")]
+    #[test]
+    fn test_strum_choices() {
+        #[derive(Debug, PartialEq, strum::EnumString, strum::VariantNames)]
+        #[strum(serialize_all = "lowercase")]
+        enum Env {
+            Development,
+            Production,
+        }
+
+        let mut reader = Cursor::new("staging\nProduction\n");
+        assert_eq!(Env::names(), &["development", "production"]);
+        assert_eq!(
+            read_choice_from::<_, Env>(&mut reader, None).unwrap(),
+            Env::Production
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use std::ffi::{OsStr, OsString};
+
+use clap::error::ErrorKind;
+use clap::{Arg, ArgMatches, Command};
+
+use crate::{read_with, write_error, InputError, Located};
+
+/// Parses the process's arguments with `command`, first asking on stdin for
+/// any required argument that is missing, and exits on error as
+/// [`Command::get_matches`] does.
+///
+/// See [`try_get_matches_interactive_from`].
+///
+/// # Usage:
+/// ```no_run
+/// use clap::{Arg, Command};
+///
+/// // `deploy` alone asks for both values; `deploy --env prod web` asks for none
+/// let matches = input_macro::get_matches_interactive(
+///     Command::new("deploy")
+///         .arg(Arg::new("env").long("env").required(true).help("Target environment"))
+///         .arg(Arg::new("service").required(true).help("Service to deploy")),
+/// );
+/// let env: &String = matches.get_one("env").unwrap();
+/// ```
+#[track_caller]
+pub fn get_matches_interactive(command: Command) -> ArgMatches {
+    try_get_matches_interactive_from(command, std::env::args_os()).unwrap_or_else(|err| err.exit())
+}
+
+/// Parses `args`, the program name first, with `command`, first asking on
+/// stdin for each required argument that `args` lacks, so one binary runs
+/// both from scripts and interactively.
+///
+/// Each prompt is the argument's help text, or its name if it has none, and
+/// an answer is checked with the argument's own value parser, asking again
+/// until it is accepted. The answers are then added to `args` and the whole
+/// command line is parsed as usual, so defaults, conflicts, and every other
+/// rule still apply. Only the top-level command's arguments are considered.
+/// EOF or an I/O error while asking is returned as an `ErrorKind::Io` error.
+#[track_caller]
+pub fn try_get_matches_interactive_from<I, T>(
+    command: Command,
+    args: I,
+) -> Result<ArgMatches, clap::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let mut args: Vec<OsString> = args.into_iter().map(Into::into).collect();
+    let mut command = command;
+    command.build();
+    let relaxed = command
+        .clone()
+        .arg_required_else_help(false)
+        .mut_args(|arg| arg.required(false))
+        .try_get_matches_from(&args)?;
+
+    let mut options = Vec::new();
+    let mut positionals = Vec::new();
+    for arg in command.get_arguments() {
+        let missing = arg.is_required_set()
+            && arg.get_action().takes_values()
+            && relaxed.value_source(arg.get_id().as_str()).is_none();
+        if !missing {
+            continue;
+        }
+        let value = ask(arg)?;
+        // Attached with `=`, so an answer such as `-5` is not taken for a flag
+        let flag = match (arg.get_long(), arg.get_short()) {
+            _ if arg.is_positional() => None,
+            (Some(long), _) => Some(format!("--{}=", long)),
+            (None, Some(short)) => Some(format!("-{}=", short)),
+            (None, None) => None,
+        };
+        match flag {
+            Some(flag) => {
+                let mut option = OsString::from(flag);
+                option.push(value);
+                options.push(option);
+            }
+            None => positionals.push(value),
+        }
+    }
+    // Options go first, before any `--`; positionals go last, after one
+    let at = args.len().min(1);
+    args.splice(at..at, options);
+    if !positionals.is_empty() && !args.iter().any(|arg| arg == "--") {
+        args.push("--".into());
+    }
+    args.extend(positionals);
+    command.try_get_matches_from(args)
+}
+
+/// Runs `value` through `arg`'s value parser alone, on a command holding
+/// just that argument, so rules tying it to other arguments are left to the
+/// final parse.
+fn validate(arg: &Arg, value: &str) -> Result<(), clap::Error> {
+    let mut only = Arg::new(arg.get_id().clone())
+        .required(true)
+        .allow_hyphen_values(true)
+        .value_parser(arg.get_value_parser().clone());
+    if let Some(name) = arg.get_value_names().and_then(<[_]>::first) {
+        only = only.value_name(name.clone());
+    }
+    Command::new("")
+        .no_binary_name(true)
+        .arg(only)
+        .try_get_matches_from([OsStr::new(value)])
+        .map(drop)
+}
+
+/// Asks for `arg` until its value parser accepts the answer.
+#[track_caller]
+fn ask(arg: &Arg) -> Result<OsString, clap::Error> {
+    let label = match arg.get_help() {
+        Some(help) => help.to_string(),
+        None => arg.get_id().to_string(),
+    };
+    loop {
+        let answer = read_with(Some(format_args!("{}: ", label)), |line: &str| {
+            validate(arg, line).map(|_| OsString::from(line))
+        });
+        match answer.map_err(Located::into_inner) {
+            Ok(value) => return Ok(value),
+            Err(InputError::Parse { source, .. }) => {
+                // Just the first line; the rest points at `--help`
+                let message = source.render().to_string();
+                let message = message.lines().next().unwrap_or_default();
+                write_error(&message).map_err(|err| clap::Error::raw(ErrorKind::Io, err))?;
+            }
+            Err(err) => {
+                return Err(clap::Error::raw(
+                    ErrorKind::Io,
+                    format!("could not read {}: {}\n", arg.get_id(), err),
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::ScriptedReader;
+    use clap::value_parser;
+
+    fn command() -> Command {
+        Command::new("serve")
+            .arg(
+                Arg::new("port")
+                    .long("port")
+                    .required(true)
+                    .value_parser(value_parser!(u16))
+                    .help("Port to listen on"),
+            )
+            .arg(Arg::new("root").required(true))
+            .arg(
+                Arg::new("verbose")
+                    .short('v')
+                    .action(clap::ArgAction::SetTrue),
+            )
+    }
+
+    /// Missing required arguments are asked for, with invalid answers retried.
+    #[test]
+    fn test_fills_missing() {
+        let (matches, prompts) = ScriptedReader::new()
+            .expect("Port to listen on: ", "huge")
+            .respond("8080")
+            .expect("root: ", "-srv")
+            .record(|| try_get_matches_interactive_from(command(), ["serve", "-v"]).unwrap());
+        assert_eq!(matches.get_one::<u16>("port"), Some(&8080));
+        assert_eq!(matches.get_one::<String>("root").unwrap(), "-srv");
+        assert!(matches.get_flag("verbose"));
+        assert!(
+            prompts[1].starts_with("error: invalid value 'huge' for '<port>'"),
+            "{:?}",
+            prompts
+        );
+    }
+
+    /// Arguments already given are not asked for, and EOF is an error.
+    #[test]
+    fn test_nothing_missing_and_eof() {
+        let matches = ScriptedReader::new().run(|| {
+            try_get_matches_interactive_from(command(), ["serve", "--port", "1", "/"]).unwrap()
+        });
+        assert_eq!(matches.get_one::<u16>("port"), Some(&1));
+        let err = ScriptedReader::new()
+            .run(|| try_get_matches_interactive_from(command(), ["serve", "/"]).unwrap_err());
+        assert_eq!(err.kind(), ErrorKind::Io);
+    }
+}
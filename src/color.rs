@@ -0,0 +1,247 @@
+use std::ffi::OsStr;
+use std::fmt::{self, Display};
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// One of the eight basic terminal colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+/// How prompt text is drawn: a foreground color and text attributes.
+///
+/// The default style is plain text, which writes no escape codes at all.
+///
+/// # Usage:
+/// ```
+/// use input_macro::{Color, Style};
+///
+/// let warning = Style::new().fg(Color::Yellow).bold();
+/// assert_ne!(warning, Style::default());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Style {
+    fg: Option<Color>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+}
+
+impl Style {
+    /// Creates a plain style.
+    pub const fn new() -> Self {
+        Style {
+            fg: None,
+            bold: false,
+            dim: false,
+            italic: false,
+            underline: false,
+        }
+    }
+
+    /// Sets the foreground color.
+    pub const fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+
+    /// Makes the text bold.
+    pub const fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Makes the text dim.
+    pub const fn dim(mut self) -> Self {
+        self.dim = true;
+        self
+    }
+
+    /// Makes the text italic.
+    pub const fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    /// Underlines the text.
+    pub const fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    /// Writes the escape code that starts the style, if it is not plain.
+    fn write_start(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let attributes = [
+            (self.bold, "1"),
+            (self.dim, "2"),
+            (self.italic, "3"),
+            (self.underline, "4"),
+        ];
+        let mut codes: Vec<String> = attributes
+            .iter()
+            .filter(|(set, _)| *set)
+            .map(|(_, code)| code.to_string())
+            .collect();
+        codes.extend(self.fg.map(|color| (30 + color as u8).to_string()));
+        if codes.is_empty() {
+            return Ok(());
+        }
+        write!(f, "\x1b[{}m", codes.join(";"))
+    }
+}
+
+/// The styles [`Input`](crate::Input) draws its prompt text in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Styles {
+    pub(crate) prompt: Style,
+    pub(crate) hint: Style,
+    pub(crate) error: Style,
+}
+
+impl Default for Styles {
+    fn default() -> Self {
+        Styles {
+            prompt: Style::new(),
+            hint: Style::new().dim(),
+            error: Style::new().fg(Color::Red),
+        }
+    }
+}
+
+/// Whether prompts are colored, set with [`set_color_choice`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    /// Only when the prompt stream is a terminal, no [prompt
+    /// output](crate::set_prompt_output) is set, and the `NO_COLOR`
+    /// environment variable is unset or empty.
+    #[default]
+    Auto,
+    /// Always, even into files and pipes.
+    Always,
+    /// Never.
+    Never,
+}
+
+static CHOICE: AtomicU8 = AtomicU8::new(ColorChoice::Auto as u8);
+
+/// Sets whether prompts are colored for the whole process; the default is
+/// [`ColorChoice::Auto`].
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::{set_color_choice, ColorChoice};
+///
+/// if std::env::args().any(|arg| arg == "--no-color") {
+///     set_color_choice(ColorChoice::Never);
+/// }
+/// ```
+pub fn set_color_choice(choice: ColorChoice) {
+    CHOICE.store(choice as u8, Ordering::Relaxed);
+}
+
+/// Returns the choice set with [`set_color_choice`].
+pub fn color_choice() -> ColorChoice {
+    match CHOICE.load(Ordering::Relaxed) {
+        1 => ColorChoice::Always,
+        2 => ColorChoice::Never,
+        _ => ColorChoice::Auto,
+    }
+}
+
+/// Returns whether prompt text written now should be colored.
+fn enabled() -> bool {
+    match color_choice() {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => auto_colors(
+            std::env::var_os("NO_COLOR").as_deref(),
+            !crate::source::has_prompt_output()
+                && if crate::source::prompts_to_stderr() {
+                    io::stderr().is_terminal()
+                } else {
+                    io::stdout().is_terminal()
+                },
+        ),
+    }
+}
+
+/// Returns whether [`ColorChoice::Auto`] colors prompts, given the value of
+/// `NO_COLOR` and whether the prompts reach a terminal.
+fn auto_colors(no_color: Option<&OsStr>, to_terminal: bool) -> bool {
+    no_color.is_none_or(|value| value.is_empty()) && to_terminal
+}
+
+/// `text` drawn in a style, if colors are [enabled](enabled) when it is
+/// created.
+pub(crate) struct Painted<T> {
+    style: Option<Style>,
+    text: T,
+}
+
+/// Draws `text` in `style`.
+pub(crate) fn paint<T: Display>(style: Style, text: T) -> Painted<T> {
+    let style = (style != Style::new() && enabled()).then_some(style);
+    Painted { style, text }
+}
+
+impl<T: Display> Display for Painted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.style {
+            Some(style) => {
+                style.write_start(f)?;
+                write!(f, "{}\x1b[0m", self.text)
+            }
+            None => write!(f, "{}", self.text),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn painted(style: Style, text: &str) -> String {
+        let painted = Painted {
+            style: Some(style),
+            text,
+        };
+        painted.to_string()
+    }
+
+    /// Styles render as SGR escape codes around the text.
+    #[test]
+    fn test_painted() {
+        assert_eq!(
+            painted(Style::new().fg(Color::Red), "no"),
+            "\x1b[31mno\x1b[0m"
+        );
+        let style = Style::new().bold().underline().fg(Color::Cyan);
+        assert_eq!(painted(style, "x"), "\x1b[1;4;36mx\x1b[0m");
+    }
+
+    /// `Auto` colors a terminal unless `NO_COLOR` is set to something.
+    #[test]
+    fn test_auto_no_color() {
+        assert!(auto_colors(None, true));
+        assert!(auto_colors(Some(OsStr::new("")), true));
+        assert!(!auto_colors(Some(OsStr::new("1")), true));
+        assert!(!auto_colors(None, false));
+    }
+
+    /// Prompts redirected to a prompt output get no escape codes.
+    #[test]
+    fn test_plain_without_terminal() {
+        crate::set_prompt_output(Box::new(io::sink()));
+        assert_eq!(paint(Style::new().bold(), "b").to_string(), "b");
+        crate::reset_prompt_output();
+    }
+}
@@ -0,0 +1,85 @@
+/// Offers Tab completions during line-edited reads, set with
+/// [`Input::completer`](crate::Input::completer).
+///
+/// Completions replace the word before the cursor, which runs back from `pos`
+/// to the previous whitespace. Closures taking the line and cursor position
+/// are completers, and so is a list of words, which completes any of them
+/// that start with the word typed so far.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::{Completer, Input};
+///
+/// struct Hosts(Vec<String>);
+///
+/// impl Completer for Hosts {
+///     fn complete(&self, line: &str, pos: usize) -> Vec<String> {
+///         let typed = &line[..pos];
+///         self.0.iter().filter(|h| h.starts_with(typed)).cloned().collect()
+///     }
+/// }
+///
+/// let hosts = Hosts(vec!["alpha.local".into(), "beta.local".into()]);
+/// let host: String = Input::new().prompt("Host: ").completer(hosts).read().unwrap();
+/// let cmd: String = Input::new()
+///     .prompt("> ")
+///     .completer(&["add", "delete", "quit"][..])
+///     .read()
+///     .unwrap();
+/// ```
+pub trait Completer {
+    /// Returns the candidates for the word ending at byte offset `pos` of
+    /// `line`, each replacing that whole word.
+    fn complete(&self, line: &str, pos: usize) -> Vec<String>;
+}
+
+impl<F> Completer for F
+where
+    F: Fn(&str, usize) -> Vec<String>,
+{
+    fn complete(&self, line: &str, pos: usize) -> Vec<String> {
+        self(line, pos)
+    }
+}
+
+impl Completer for &[&str] {
+    fn complete(&self, line: &str, pos: usize) -> Vec<String> {
+        let word = &line[word_start(line, pos)..pos];
+        self.iter()
+            .filter(|candidate| candidate.starts_with(word))
+            .map(|candidate| candidate.to_string())
+            .collect()
+    }
+}
+
+/// Returns the byte offset where the word ending at `pos` starts.
+pub(crate) fn word_start(line: &str, pos: usize) -> usize {
+    line[..pos]
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_whitespace())
+        .map_or(0, |(i, c)| i + c.len_utf8())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Words start after the last whitespace before the cursor.
+    #[test]
+    fn test_word_start() {
+        assert_eq!(word_start("del", 3), 0);
+        assert_eq!(word_start("git che", 7), 4);
+        assert_eq!(word_start("a\u{3000}b", 4), 4);
+        assert_eq!(word_start("git ", 4), 4);
+    }
+
+    /// A word list completes the word at the cursor by prefix.
+    #[test]
+    fn test_word_list() {
+        let words: &[&str] = &["add", "addr", "delete"];
+        assert_eq!(words.complete("ad", 2), ["add", "addr"]);
+        assert_eq!(words.complete("help del", 8), ["delete"]);
+        assert!(words.complete("x", 1).is_empty());
+    }
+}
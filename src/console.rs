@@ -0,0 +1,125 @@
+use std::fs::File;
+use std::io::{self, BufRead, Read};
+use std::os::windows::io::AsRawHandle;
+
+use windows_sys::Win32::System::Console::{GetConsoleMode, ReadConsoleW};
+
+/// How many UTF-16 units are requested from the console per read.
+const CHUNK: usize = 1024;
+
+/// A reader over a Windows console that reads with `ReadConsoleW` and
+/// transcodes to UTF-8, so non-ASCII input arrives intact whatever the
+/// console's code page.
+///
+/// Reading a console handle as a file, as `CONIN$` is by
+/// [`read_from_tty`](crate::read_from_tty) without this feature, returns text
+/// in the active code page instead. Std's `Stdin` already reads consoles
+/// this way. Unpaired surrogates are replaced with `U+FFFD`, and Ctrl-Z at
+/// the start of a line ends the input.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::{read_input_from, WideConsoleReader};
+///
+/// let mut console = WideConsoleReader::open().unwrap();
+/// let name: String = read_input_from(&mut console, None).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct WideConsoleReader {
+    console: File,
+    buf: Vec<u8>,
+    pos: usize,
+    pending_surrogate: Option<u16>,
+    eof: bool,
+}
+
+impl WideConsoleReader {
+    /// Opens the console input buffer, `CONIN$`.
+    pub fn open() -> io::Result<Self> {
+        let console = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open("CONIN$")?;
+        Self::new(console)
+    }
+
+    /// Wraps a handle to a console input buffer, failing if it is not one.
+    pub fn new(console: File) -> io::Result<Self> {
+        let mut mode = 0;
+        // SAFETY: the handle is owned by `console` and `mode` is a valid out pointer
+        if unsafe { GetConsoleMode(console.as_raw_handle(), &mut mode) } == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(WideConsoleReader {
+            console,
+            buf: Vec::new(),
+            pos: 0,
+            pending_surrogate: None,
+            eof: false,
+        })
+    }
+
+    /// Reads the next chunk of input from the console into `buf`.
+    fn refill(&mut self) -> io::Result<()> {
+        let mut units = [0u16; CHUNK];
+        let start = match self.pending_surrogate.take() {
+            Some(high) => {
+                units[0] = high;
+                1
+            }
+            None => 0,
+        };
+        let mut read = 0u32;
+        // SAFETY: the handle is owned by `self.console`, and the buffer holds
+        // `CHUNK - start` units past `start`
+        let ok = unsafe {
+            ReadConsoleW(
+                self.console.as_raw_handle(),
+                units[start..].as_mut_ptr().cast(),
+                (CHUNK - start) as u32,
+                &mut read,
+                std::ptr::null(),
+            )
+        };
+        if ok == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut end = start + read as usize;
+        if read > 0 && (0xD800..0xDC00).contains(&units[end - 1]) {
+            // Keep a high surrogate for the next read, where its pair arrives
+            end -= 1;
+            self.pending_surrogate = Some(units[end]);
+        }
+        let text: String = char::decode_utf16(units[..end].iter().copied())
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect();
+        if read == 0 || text.starts_with('\u{1a}') {
+            self.eof = true;
+        }
+        self.buf = text.into_bytes();
+        self.pos = 0;
+        Ok(())
+    }
+}
+
+impl Read for WideConsoleReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        crate::buffered::read_from_buf(self, buf)
+    }
+}
+
+impl BufRead for WideConsoleReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while self.pos >= self.buf.len() && !self.eof {
+            self.refill()?;
+        }
+        if self.eof {
+            return Ok(&[]);
+        }
+        Ok(&self.buf[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.buf.len());
+    }
+}
@@ -0,0 +1,175 @@
+#[cfg(any(feature = "flate2", feature = "zstd"))]
+use std::io::BufReader;
+use std::io::{self, BufRead, Chain, Cursor, Read};
+
+#[cfg(feature = "flate2")]
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// The inner reader, after the bytes taken to detect the format are put back.
+type Source<R> = Chain<Cursor<Vec<u8>>, R>;
+
+/// A reader that decompresses gzip or zstd input and passes anything else
+/// through unchanged, so `tool file.gz` and `zcat file.gz | tool` read the same.
+///
+/// The format is detected from the first bytes of the input: gzip needs the
+/// `flate2` feature and zstd the `zstd` feature, and without either every
+/// input is passed through. Concatenated gzip members and zstd frames are
+/// decompressed one after another, as `zcat` does.
+///
+/// [`InputReader::open`](crate::InputReader::open) and
+/// [`read_lines_from_path`](crate::read_lines_from_path) wrap their files in
+/// one already.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::{set_input_source, Decompress};
+///
+/// let file = std::fs::File::open("values.txt.gz").unwrap();
+/// let reader = Decompress::new(std::io::BufReader::new(file)).unwrap();
+/// set_input_source(Box::new(reader));
+/// ```
+pub struct Decompress<R> {
+    inner: Inner<R>,
+}
+
+enum Inner<R> {
+    Plain(Source<R>),
+    #[cfg(feature = "flate2")]
+    Gzip(BufReader<flate2::bufread::MultiGzDecoder<Source<R>>>),
+    #[cfg(feature = "zstd")]
+    Zstd(BufReader<zstd::stream::read::Decoder<'static, Source<R>>>),
+}
+
+impl<R: BufRead> Decompress<R> {
+    /// Wraps `inner`, reading its first few bytes to detect the format.
+    ///
+    /// Fails if reading them fails, or if the zstd decoder cannot be set up.
+    pub fn new(mut inner: R) -> io::Result<Self> {
+        let mut head = Vec::with_capacity(ZSTD_MAGIC.len());
+        (&mut inner)
+            .take(ZSTD_MAGIC.len() as u64)
+            .read_to_end(&mut head)?;
+        let source = Cursor::new(head).chain(inner);
+        #[cfg(feature = "flate2")]
+        if source.get_ref().0.get_ref().starts_with(GZIP_MAGIC) {
+            let decoder = flate2::bufread::MultiGzDecoder::new(source);
+            return Ok(Decompress {
+                inner: Inner::Gzip(BufReader::new(decoder)),
+            });
+        }
+        #[cfg(feature = "zstd")]
+        if source.get_ref().0.get_ref().starts_with(ZSTD_MAGIC) {
+            let decoder = zstd::stream::read::Decoder::with_buffer(source)?;
+            return Ok(Decompress {
+                inner: Inner::Zstd(BufReader::new(decoder)),
+            });
+        }
+        Ok(Decompress {
+            inner: Inner::Plain(source),
+        })
+    }
+
+    /// Returns the name of the detected compression format, or `None` for
+    /// input that is passed through.
+    pub fn format(&self) -> Option<&'static str> {
+        match self.inner {
+            Inner::Plain(_) => None,
+            #[cfg(feature = "flate2")]
+            Inner::Gzip(_) => Some("gzip"),
+            #[cfg(feature = "zstd")]
+            Inner::Zstd(_) => Some("zstd"),
+        }
+    }
+}
+
+impl<R: BufRead> std::fmt::Debug for Decompress<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Decompress")
+            .field("format", &self.format().unwrap_or("plain"))
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: BufRead> Read for Decompress<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match &mut self.inner {
+            Inner::Plain(reader) => reader.read(buf),
+            #[cfg(feature = "flate2")]
+            Inner::Gzip(reader) => reader.read(buf),
+            #[cfg(feature = "zstd")]
+            Inner::Zstd(reader) => reader.read(buf),
+        }
+    }
+}
+
+impl<R: BufRead> BufRead for Decompress<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        match &mut self.inner {
+            Inner::Plain(reader) => reader.fill_buf(),
+            #[cfg(feature = "flate2")]
+            Inner::Gzip(reader) => reader.fill_buf(),
+            #[cfg(feature = "zstd")]
+            Inner::Zstd(reader) => reader.fill_buf(),
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        match &mut self.inner {
+            Inner::Plain(reader) => reader.consume(amt),
+            #[cfg(feature = "flate2")]
+            Inner::Gzip(reader) => reader.consume(amt),
+            #[cfg(feature = "zstd")]
+            Inner::Zstd(reader) => reader.consume(amt),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_input_from;
+
+    fn read_all<R: BufRead>(mut reader: Decompress<R>) -> Vec<u32> {
+        std::iter::from_fn(|| read_input_from(&mut reader, None).ok()).collect()
+    }
+
+    /// Uncompressed input passes through, including input shorter than any magic number.
+    #[test]
+    fn test_plain() {
+        let reader = Decompress::new("1\n2\n".as_bytes()).unwrap();
+        assert_eq!(reader.format(), None);
+        assert_eq!(read_all(reader), [1, 2]);
+        let reader = Decompress::new(std::io::BufReader::with_capacity(1, "7".as_bytes())).unwrap();
+        assert_eq!(read_all(reader), [7]);
+    }
+
+    /// Concatenated gzip members read as one stream, as with `zcat`.
+    #[cfg(feature = "flate2")]
+    #[test]
+    fn test_gzip() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let mut data = Vec::new();
+        for part in ["1\n2\n", "3\n"] {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+            encoder.write_all(part.as_bytes()).unwrap();
+            data.extend(encoder.finish().unwrap());
+        }
+        let reader =
+            Decompress::new(std::io::BufReader::with_capacity(1, data.as_slice())).unwrap();
+        assert_eq!(reader.format(), Some("gzip"));
+        assert_eq!(read_all(reader), [1, 2, 3]);
+    }
+
+    /// Zstd input is detected and decompressed.
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd() {
+        let data = zstd::stream::encode_all("4\n5\n".as_bytes(), 0).unwrap();
+        let reader = Decompress::new(data.as_slice()).unwrap();
+        assert_eq!(reader.format(), Some("zstd"));
+        assert_eq!(read_all(reader), [4, 5]);
+    }
+}
@@ -0,0 +1,205 @@
+use std::any::Any;
+use std::error::Error;
+use std::fmt::Display;
+
+use miette::{Diagnostic, LabeledSpan, SourceCode};
+
+use crate::{InputError, Located};
+
+/// Reports read failures through [`miette`].
+///
+/// `Parse` errors carry the offending line as their source code, with a label
+/// under the text that failed to parse and, for the standard library's number,
+/// `bool`, and `char` parse errors, a hint at what was expected.
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_input_from;
+/// use miette::Diagnostic;
+///
+/// let err = read_input_from::<_, u32>(&mut "  forty-two\n".as_bytes(), None).unwrap_err();
+/// assert_eq!(err.help().unwrap().to_string(), "expected a whole number like 42");
+/// let label = err.labels().unwrap().next().unwrap();
+/// assert_eq!((label.offset(), label.len()), (2, 9));
+/// ```
+impl<E: Error + 'static> Diagnostic for InputError<E> {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let code = match self {
+            InputError::Io(_) => "input_macro::io",
+            InputError::Parse { .. } => "input_macro::parse",
+            InputError::Eof => "input_macro::eof",
+            InputError::AttemptsExhausted(_) => "input_macro::attempts_exhausted",
+            InputError::Invalid(_) => "input_macro::invalid",
+            InputError::Timeout => "input_macro::timeout",
+            InputError::Cancelled => "input_macro::cancelled",
+            InputError::Interrupted => "input_macro::interrupted",
+            InputError::TooLong(_) => "input_macro::too_long",
+        };
+        Some(Box::new(code))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            InputError::Parse { source, .. } => expected(source).map(|h| Box::new(h) as _),
+            _ => None,
+        }
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        match self {
+            InputError::Parse { input, .. } => Some(input),
+            _ => None,
+        }
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        match self {
+            InputError::Parse { input, .. } => {
+                let start = input.len() - input.trim_start().len();
+                let len = input.trim().len();
+                let label = LabeledSpan::new_with_span(Some("here".to_string()), (start, len));
+                Some(Box::new(std::iter::once(label)))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Reports a stdin read failure as its underlying error does, with the
+/// location of the failed read added to the help text.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::input;
+/// use miette::Diagnostic;
+///
+/// let err = input!(u32, "Count: ").unwrap_err();
+/// // "expected a whole number like 42 (read at src/main.rs:4:11)"
+/// println!("{}", err.help().unwrap());
+/// ```
+impl<E: Diagnostic> Diagnostic for Located<E> {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.error.code()
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        self.error.severity()
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let help = match self.error.help() {
+            Some(help) => format!("{} (read at {})", help, self.location),
+            None => format!("read at {}", self.location),
+        };
+        Some(Box::new(help))
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.error.url()
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        self.error.source_code()
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        self.error.labels()
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        self.error.related()
+    }
+
+    fn diagnostic_source(&self) -> Option<&dyn Diagnostic> {
+        self.error.diagnostic_source()
+    }
+}
+
+/// Describes the input expected by the standard library's parse error types.
+fn expected(source: &dyn Any) -> Option<&'static str> {
+    use std::num::{IntErrorKind, ParseFloatError, ParseIntError};
+
+    if let Some(e) = source.downcast_ref::<ParseIntError>() {
+        return Some(match e.kind() {
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+                "the number is out of range for this type"
+            }
+            _ => "expected a whole number like 42",
+        });
+    }
+    if source.is::<ParseFloatError>() {
+        return Some("expected a number like 3.14");
+    }
+    if source.is::<std::str::ParseBoolError>() {
+        return Some("expected `true` or `false`");
+    }
+    if source.is::<std::char::ParseCharError>() {
+        return Some("expected a single character");
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_input_from;
+    use std::io::Cursor;
+
+    /// Parse errors get a code, a span over the trimmed input, and a hint.
+    #[test]
+    fn test_parse_diagnostic() {
+        let mut reader = Cursor::new("3.5 \n300\nyes\n");
+        let err = read_input_from::<_, i32>(&mut reader, None).unwrap_err();
+        assert_eq!(err.code().unwrap().to_string(), "input_macro::parse");
+        assert_eq!(
+            err.help().unwrap().to_string(),
+            "expected a whole number like 42"
+        );
+        let labels: Vec<_> = err.labels().unwrap().collect();
+        assert_eq!((labels[0].offset(), labels[0].len()), (0, 3));
+        assert!(err.source_code().is_some());
+
+        let err = read_input_from::<_, u8>(&mut reader, None).unwrap_err();
+        assert_eq!(
+            err.help().unwrap().to_string(),
+            "the number is out of range for this type"
+        );
+        let err = read_input_from::<_, bool>(&mut reader, None).unwrap_err();
+        assert_eq!(
+            err.help().unwrap().to_string(),
+            "expected `true` or `false`"
+        );
+    }
+
+    /// Other errors have a code but no source code or labels.
+    #[test]
+    fn test_eof_diagnostic() {
+        let err = read_input_from::<_, i32>(&mut Cursor::new(""), None).unwrap_err();
+        assert_eq!(err.code().unwrap().to_string(), "input_macro::eof");
+        assert!(err.help().is_none());
+        assert!(err.source_code().is_none() && err.labels().is_none());
+    }
+
+    /// Located errors keep the underlying diagnostic and add their location
+    /// to the help.
+    #[test]
+    fn test_located_diagnostic() {
+        let err = read_input_from::<_, i32>(&mut Cursor::new("x\n"), None);
+        let err = crate::source::located(err).unwrap_err();
+        let location = err.location();
+        assert_eq!(err.code().unwrap().to_string(), "input_macro::parse");
+        assert_eq!(
+            err.help().unwrap().to_string(),
+            format!("expected a whole number like 42 (read at {})", location)
+        );
+        assert_eq!(err.labels().unwrap().count(), 1);
+        assert!(err.source_code().is_some());
+
+        let err = read_input_from::<_, i32>(&mut Cursor::new(""), None);
+        let err = crate::source::located(err).unwrap_err();
+        assert_eq!(
+            err.help().unwrap().to_string(),
+            format!("read at {}", err.location())
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use std::convert::Infallible;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{InputError, Located};
+
+/// Opens `initial_text` in the user's editor and returns the text saved
+/// there once it exits, the way `git commit` asks for a message.
+///
+/// The editor is taken from `$VISUAL`, then `$EDITOR`, falling back to `vi`
+/// (`notepad` on Windows), and may include arguments, e.g. `code --wait`. The
+/// text goes through a temporary file, which is removed afterwards. An
+/// editor that cannot be started or exits unsuccessfully is an
+/// `InputError::Io`.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::read_via_editor;
+///
+/// let notes = read_via_editor("# Describe the release\n").unwrap();
+/// ```
+#[track_caller]
+pub fn read_via_editor(initial_text: &str) -> Result<String, Located<InputError<Infallible>>> {
+    let var = |name| {
+        std::env::var(name)
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+    };
+    let editor = var("VISUAL")
+        .or_else(|| var("EDITOR"))
+        .unwrap_or_else(|| default_editor().to_string());
+    crate::source::located(edit_with(&editor, initial_text).map_err(InputError::Io))
+}
+
+fn default_editor() -> &'static str {
+    if cfg!(windows) {
+        "notepad"
+    } else {
+        "vi"
+    }
+}
+
+/// Runs `editor` on a temporary file holding `initial_text`.
+fn edit_with(editor: &str, initial_text: &str) -> io::Result<String> {
+    let file = TempFile::create(initial_text)?;
+    let status = editor_command(editor, &file.0).status().map_err(|err| {
+        io::Error::new(
+            err.kind(),
+            format!("could not start editor `{}`: {}", editor, err),
+        )
+    })?;
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "editor `{}` exited with {}",
+            editor, status
+        )));
+    }
+    fs::read_to_string(&file.0)
+}
+
+/// Builds the command that opens `path` in `editor`, which may carry arguments.
+fn editor_command(editor: &str, path: &Path) -> Command {
+    if cfg!(windows) {
+        let mut words = editor.split_whitespace();
+        let mut command = Command::new(words.next().unwrap_or(default_editor()));
+        command.args(words).arg(path);
+        command
+    } else {
+        // As git does, so quoting in the variable is honored
+        let mut command = Command::new("sh");
+        command
+            .arg("-c")
+            .arg(format!("{} \"$@\"", editor))
+            .arg(editor)
+            .arg(path);
+        command
+    }
+}
+
+/// A file in the temp directory, removed when dropped; tests use it too.
+pub(crate) struct TempFile(pub(crate) PathBuf);
+
+impl TempFile {
+    pub(crate) fn create(contents: impl AsRef<[u8]>) -> io::Result<Self> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let name = format!(
+            "input-macro-{}-{}.txt",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = std::env::temp_dir().join(name);
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .and_then(|mut file| io::Write::write_all(&mut file, contents.as_ref()))?;
+        Ok(TempFile(path))
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// The editor sees the initial text, and what it saves is read back.
+    #[test]
+    fn test_edit_with() {
+        let text = edit_with("tr a-z A-Z < \"$1\" > \"$1.up\"; mv \"$1.up\"", "notes\n").unwrap();
+        assert_eq!(text, "NOTES\n");
+    }
+
+    /// A failing editor is an error.
+    #[test]
+    fn test_edit_with_failure() {
+        let err = edit_with("false", "x").unwrap_err();
+        assert!(err.to_string().contains("editor `false` exited"), "{}", err);
+    }
+}
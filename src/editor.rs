@@ -0,0 +1,346 @@
+//! Line editing for interactive stdin reads, behind the `rustyline` feature.
+
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::convert::Infallible;
+use std::io::{self, BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::FileHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+
+use crate::complete::word_start;
+use crate::{Completer, InputError};
+
+thread_local! {
+    static EDITOR: RefCell<Option<EditorReader>> = const { RefCell::new(None) };
+    static CAPTURING: Cell<bool> = const { Cell::new(false) };
+    static PENDING_PROMPT: RefCell<String> = const { RefCell::new(String::new()) };
+    static HISTORY: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+    static COMPLETER: RefCell<Option<Arc<dyn Completer + Send + Sync>>> = const { RefCell::new(None) };
+}
+
+/// Returns whether this thread's stdin reads should go through the line
+/// editor, creating it on first use.
+///
+/// That is the case when a person is at the terminal and prompts go to
+/// stdout, where the editor draws them.
+pub(crate) fn is_active() -> bool {
+    if !crate::is_interactive() || crate::source::prompts_to_stderr() {
+        return false;
+    }
+    EDITOR.with(|slot| {
+        let mut slot = slot.borrow_mut();
+        if slot.is_none() {
+            *slot = Editor::new().ok().map(|mut editor: LineEditor| {
+                editor.set_helper(Some(EditorHelper));
+                EditorReader::new(editor)
+            });
+        }
+        slot.is_some()
+    })
+}
+
+/// Runs `f` on the line editor, which [`is_active`] must have created.
+///
+/// Prompts written during the call are collected and handed to the editor
+/// with the next line it reads, so it can redraw them while editing.
+pub(crate) fn with_editor<T, E>(f: impl FnOnce(&mut dyn BufRead) -> Result<T, E>) -> Result<T, E> {
+    let mut reader = EDITOR
+        .with(|slot| slot.borrow_mut().take())
+        .expect("line editor not created");
+    let capturing = CAPTURING.replace(true);
+    let result = f(&mut reader);
+    CAPTURING.set(capturing);
+    if !capturing {
+        // Show anything printed after the last line was read
+        let _ = write_stdout(&take_pending_prompt());
+    }
+    EDITOR.with(|slot| {
+        let mut slot = slot.borrow_mut();
+        if slot.is_none() {
+            *slot = Some(reader);
+        }
+    });
+    result
+}
+
+/// Makes this thread's editor reads use the history file at `path` until the
+/// returned guard is dropped; `None` keeps the history in memory only.
+///
+/// A leading `~` in the path stands for the home directory.
+pub(crate) fn use_history(path: Option<&Path>) -> HistoryGuard {
+    let path = path.map(|path| expand_home(path, std::env::home_dir()));
+    HistoryGuard(HISTORY.replace(path))
+}
+
+/// Restores the previous history file when dropped.
+pub(crate) struct HistoryGuard(Option<PathBuf>);
+
+impl Drop for HistoryGuard {
+    fn drop(&mut self) {
+        HISTORY.set(self.0.take());
+    }
+}
+
+fn expand_home(path: &Path, home: Option<PathBuf>) -> PathBuf {
+    match (path.strip_prefix("~"), home) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Makes Tab in this thread's editor reads complete with `completer` until
+/// the returned guard is dropped.
+pub(crate) fn use_completer(completer: Option<Arc<dyn Completer + Send + Sync>>) -> CompleterGuard {
+    CompleterGuard(COMPLETER.replace(completer))
+}
+
+/// Restores the previous completer when dropped.
+pub(crate) struct CompleterGuard(Option<Arc<dyn Completer + Send + Sync>>);
+
+impl Drop for CompleterGuard {
+    fn drop(&mut self) {
+        COMPLETER.set(self.0.take());
+    }
+}
+
+type LineEditor = Editor<EditorHelper, FileHistory>;
+
+/// Connects the editor's Tab key to the completer in use.
+struct EditorHelper;
+
+impl rustyline::completion::Completer for EditorHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let completer = COMPLETER.with(|completer| completer.borrow().clone());
+        Ok(match completer {
+            Some(completer) => (word_start(line, pos), completer.complete(line, pos)),
+            None => (pos, Vec::new()),
+        })
+    }
+}
+
+impl Hinter for EditorHelper {
+    type Hint = String;
+}
+
+impl Highlighter for EditorHelper {}
+
+impl Validator for EditorHelper {}
+
+impl Helper for EditorHelper {}
+
+/// Returns whether prompts are currently being collected for the editor.
+pub(crate) fn capturing() -> bool {
+    CAPTURING.get()
+}
+
+/// Collects prompt text for the next editor read.
+pub(crate) struct PromptCapture;
+
+impl Write for PromptCapture {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        PENDING_PROMPT.with(|pending| pending.borrow_mut().push_str(&String::from_utf8_lossy(buf)));
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn take_pending_prompt() -> String {
+    PENDING_PROMPT.with(|pending| std::mem::take(&mut *pending.borrow_mut()))
+}
+
+fn write_stdout(text: &str) -> io::Result<()> {
+    if text.is_empty() {
+        return Ok(());
+    }
+    let mut out = io::stdout();
+    out.write_all(text.as_bytes())?;
+    out.flush()
+}
+
+/// Splits collected prompt text into the lines to print as they are and the
+/// final, unterminated line the editor shows before the cursor.
+///
+/// The editor measures that line to place the cursor, so its styles are
+/// stripped.
+fn split_prompt(prompt: &str) -> (&str, Cow<'_, str>) {
+    let (before, line) = match prompt.rfind('\n') {
+        Some(i) => prompt.split_at(i + 1),
+        None => ("", prompt),
+    };
+    (before, crate::strip_ansi(line))
+}
+
+/// Maps an editor failure to the error a line read reports.
+///
+/// Ctrl-D reads as EOF. Ctrl-C wraps `InputError::Interrupted`, which the
+/// crate's readers turn back into that variant.
+fn readline_error(err: ReadlineError) -> Option<io::Error> {
+    match err {
+        ReadlineError::Eof => None,
+        ReadlineError::Interrupted => Some(io::Error::other(InputError::<Infallible>::Interrupted)),
+        ReadlineError::Io(err) => Some(err),
+        err => Some(io::Error::other(err)),
+    }
+}
+
+/// A `BufRead` over lines typed into the editor, each ending in `\n`.
+///
+/// Non-blank lines are added to the history; when a history file is in use
+/// it is loaded before the first read from it, and each line is appended to
+/// it. A missing or unreadable file starts an empty history, and failures to
+/// save are ignored, so history never fails a read.
+struct EditorReader {
+    editor: LineEditor,
+    line: Vec<u8>,
+    pos: usize,
+    loaded: Option<PathBuf>,
+}
+
+impl EditorReader {
+    fn new(editor: LineEditor) -> Self {
+        EditorReader {
+            editor,
+            line: Vec::new(),
+            pos: 0,
+            loaded: None,
+        }
+    }
+
+    /// Swaps in the history for the file currently in use, if it changed.
+    fn switch_history(&mut self) {
+        let wanted = HISTORY.with(|history| history.borrow().clone());
+        if wanted == self.loaded {
+            return;
+        }
+        let _ = self.editor.clear_history();
+        if let Some(path) = &wanted {
+            let _ = self.editor.load_history(path);
+        }
+        self.loaded = wanted;
+    }
+
+    fn record(&mut self, line: &str) {
+        if line.trim().is_empty() {
+            return;
+        }
+        let _ = self.editor.add_history_entry(line);
+        if let Some(path) = &self.loaded {
+            let _ = self.editor.append_history(path);
+        }
+    }
+}
+
+impl Read for EditorReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        crate::buffered::read_from_buf(self, buf)
+    }
+}
+
+impl BufRead for EditorReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos >= self.line.len() {
+            let pending = take_pending_prompt();
+            let (before, prompt) = split_prompt(&pending);
+            write_stdout(before)?;
+            self.line.clear();
+            self.pos = 0;
+            self.switch_history();
+            match self.editor.readline(&*prompt) {
+                Ok(line) => {
+                    self.record(&line);
+                    self.line = line.into_bytes();
+                    self.line.push(b'\n');
+                }
+                Err(err) => {
+                    if let Some(err) = readline_error(err) {
+                        return Err(err);
+                    }
+                }
+            }
+        }
+        Ok(&self.line[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.line.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Only the last line of a collected prompt is drawn by the editor.
+    #[test]
+    fn test_split_prompt() {
+        assert_eq!(split_prompt("Name: "), ("", "Name: ".into()));
+        assert_eq!(
+            split_prompt("Please answer yes or no.\nDelete? "),
+            ("Please answer yes or no.\n", "Delete? ".into())
+        );
+        assert_eq!(split_prompt("Pick one:\n"), ("Pick one:\n", "".into()));
+    }
+
+    /// Styled prompts reach the editor as plain text, so its cursor math holds.
+    #[test]
+    fn test_split_prompt_strips_styles() {
+        let (before, prompt) =
+            split_prompt("\x1b[31mbad\x1b[0m\n\x1b[1mPort: \x1b[0m\x1b[2m[80] \x1b[0m");
+        assert_eq!(before, "\x1b[31mbad\x1b[0m\n");
+        assert_eq!(prompt, "Port: [80] ");
+    }
+
+    /// `~` expands to the home directory only as a whole leading component.
+    #[test]
+    fn test_expand_home() {
+        let home = Some(PathBuf::from("/home/ada"));
+        assert_eq!(
+            expand_home(Path::new("~/.mytool_history"), home.clone()),
+            Path::new("/home/ada/.mytool_history")
+        );
+        assert_eq!(
+            expand_home(Path::new("~ada/x"), home.clone()),
+            Path::new("~ada/x")
+        );
+        assert_eq!(
+            expand_home(Path::new("/tmp/history"), home),
+            Path::new("/tmp/history")
+        );
+        assert_eq!(expand_home(Path::new("~/x"), None), Path::new("~/x"));
+    }
+
+    /// Ctrl-C surfaces as `InputError::Interrupted` through a line read.
+    #[test]
+    fn test_readline_interrupted() {
+        assert!(readline_error(ReadlineError::Eof).is_none());
+        let err = readline_error(ReadlineError::Interrupted).unwrap();
+        let mut reader = io::BufReader::new(FailingReader(Some(err)));
+        let res = crate::read_input_from::<_, String>(&mut reader, None);
+        assert!(matches!(res, Err(InputError::Interrupted)));
+    }
+
+    struct FailingReader(Option<io::Error>);
+
+    impl Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(self.0.take().unwrap())
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::io::{self, BufRead, Read};
+
+use encoding_rs::{Decoder, Encoding};
+
+/// A reader wrapper that transcodes input in a legacy encoding, such as
+/// Shift_JIS or Latin-1, to UTF-8, so the crate's readers can parse data
+/// exported from older systems.
+///
+/// Malformed sequences become `U+FFFD`. A byte order mark for the declared
+/// encoding is removed.
+///
+/// # Usage:
+/// ```
+/// use input_macro::{read_input_from, DecodingReader};
+///
+/// let latin1 = &b"caf\xe9\n"[..];
+/// let mut reader = DecodingReader::for_label(latin1, "latin1").unwrap();
+/// assert_eq!(read_input_from::<_, String>(&mut reader, None).unwrap(), "caf\u{e9}");
+/// ```
+pub struct DecodingReader<R> {
+    inner: R,
+    decoder: Decoder,
+    decoded: Vec<u8>,
+    pos: usize,
+    finished: bool,
+}
+
+impl<R> DecodingReader<R> {
+    /// Wraps `inner`, whose bytes are in `encoding`.
+    pub fn new(inner: R, encoding: &'static Encoding) -> Self {
+        DecodingReader {
+            inner,
+            decoder: encoding.new_decoder_with_bom_removal(),
+            decoded: Vec::new(),
+            pos: 0,
+            finished: false,
+        }
+    }
+
+    /// Wraps `inner`, whose bytes are in the encoding named by `label`
+    /// (e.g. `"shift_jis"`, `"latin1"`, `"windows-1251"`), or returns `None`
+    /// if the label is unknown.
+    pub fn for_label(inner: R, label: &str) -> Option<Self> {
+        Encoding::for_label(label.as_bytes()).map(|encoding| Self::new(inner, encoding))
+    }
+
+    /// Returns the encoding being decoded.
+    pub fn encoding(&self) -> &'static Encoding {
+        self.decoder.encoding()
+    }
+
+    /// Returns the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> std::fmt::Debug for DecodingReader<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DecodingReader")
+            .field("encoding", &self.encoding().name())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: BufRead> Read for DecodingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        crate::buffered::read_from_buf(self, buf)
+    }
+}
+
+impl<R: BufRead> BufRead for DecodingReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while self.pos >= self.decoded.len() && !self.finished {
+            let src = self.inner.fill_buf()?;
+            let last = src.is_empty();
+            let capacity = self
+                .decoder
+                .max_utf8_buffer_length(src.len())
+                .ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::OutOfMemory, "input chunk too large")
+                })?;
+            self.decoded.resize(capacity, 0);
+            // With room for the worst case, the whole chunk is always decoded
+            let (_, read, written, _) = self.decoder.decode_to_utf8(src, &mut self.decoded, last);
+            self.decoded.truncate(written);
+            self.pos = 0;
+            self.inner.consume(read);
+            self.finished = last;
+        }
+        Ok(&self.decoded[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.decoded.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_input_from;
+    use std::io::BufReader;
+
+    /// Multi-byte characters split across chunks of the source still decode.
+    #[test]
+    fn test_shift_jis() {
+        let sjis = &b"\x93\xfa\x96\x7b\n42\n"[..];
+        let inner = BufReader::with_capacity(1, sjis);
+        let mut reader = DecodingReader::new(inner, encoding_rs::SHIFT_JIS);
+        assert_eq!(
+            read_input_from::<_, String>(&mut reader, None).unwrap(),
+            "\u{65e5}\u{672c}"
+        );
+        assert_eq!(read_input_from::<_, u8>(&mut reader, None).unwrap(), 42);
+        assert!(read_input_from::<_, u8>(&mut reader, None).is_err());
+    }
+
+    /// Unknown labels are rejected.
+    #[test]
+    fn test_unknown_label() {
+        assert!(DecodingReader::for_label(&b""[..], "klingon").is_none());
+        let reader = DecodingReader::for_label(&b""[..], "sjis").unwrap();
+        assert_eq!(reader.encoding(), encoding_rs::SHIFT_JIS);
+    }
+}
@@ -0,0 +1,126 @@
+use std::fmt::Arguments;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::{parse_line, read_input_from, InputError, Located};
+
+/// What a read returns when the input ends before a line is read.
+///
+/// Pass it to [`read_input_on_eof_from`] or [`Input::on_eof`](crate::Input::on_eof),
+/// or [`apply`](EofPolicy::apply) it to the result of any other read.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum EofPolicy<T> {
+    /// Return `Err(InputError::Eof)`.
+    #[default]
+    Error,
+    /// Return `Ok(None)`.
+    AsNone,
+    /// Return the given value.
+    AsDefault(T),
+    /// Handle EOF as if an empty line had been entered.
+    AsEmptyString,
+}
+
+impl<T> EofPolicy<T> {
+    /// Maps an `Eof` error in `result` according to the policy; every other
+    /// outcome passes through, with values wrapped in `Some`.
+    ///
+    /// # Usage:
+    /// ```
+    /// use input_macro::{read_input_from, EofPolicy};
+    ///
+    /// let mut reader = "".as_bytes();
+    /// let policy = EofPolicy::AsDefault(1);
+    /// assert_eq!(policy.apply(read_input_from::<_, u8>(&mut reader, None)).unwrap(), Some(1));
+    /// ```
+    pub fn apply(
+        self,
+        result: Result<T, InputError<T::Err>>,
+    ) -> Result<Option<T>, InputError<T::Err>>
+    where
+        T: FromStr,
+    {
+        match (result, self) {
+            (Err(InputError::Eof), EofPolicy::Error) => Err(InputError::Eof),
+            (Err(InputError::Eof), EofPolicy::AsNone) => Ok(None),
+            (Err(InputError::Eof), EofPolicy::AsDefault(value)) => Ok(Some(value)),
+            (Err(InputError::Eof), EofPolicy::AsEmptyString) => parse_line("").map(Some),
+            (result, _) => result.map(Some),
+        }
+    }
+}
+
+/// Like [`read_input_from`], but EOF is handled by `policy`.
+///
+/// Returns `Ok(None)` only under [`EofPolicy::AsNone`].
+///
+/// # Usage:
+/// ```
+/// use input_macro::{read_input_on_eof_from, EofPolicy};
+///
+/// let mut reader = std::io::Cursor::new("alice\n");
+/// let policy = || EofPolicy::AsEmptyString;
+/// let name: Option<String> = read_input_on_eof_from(&mut reader, None, policy()).unwrap();
+/// assert_eq!(name.as_deref(), Some("alice"));
+/// let name: Option<String> = read_input_on_eof_from(&mut reader, None, policy()).unwrap();
+/// assert_eq!(name.as_deref(), Some(""));
+/// ```
+pub fn read_input_on_eof_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    policy: EofPolicy<T>,
+) -> Result<Option<T>, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    policy.apply(read_input_from(reader, prompt))
+}
+
+/// A convenience wrapper around [`read_input_on_eof_from`] that reads from stdin.
+#[track_caller]
+pub fn read_input_on_eof<T>(
+    prompt: Option<Arguments<'_>>,
+    policy: EofPolicy<T>,
+) -> Result<Option<T>, Located<InputError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::__with_stdin(|reader| read_input_on_eof_from(reader, prompt, policy))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Each policy maps EOF to its own outcome and leaves other results alone.
+    #[test]
+    fn test_eof_policies() {
+        let eof = || Err(InputError::Eof);
+        assert!(matches!(
+            EofPolicy::<i32>::Error.apply(eof()),
+            Err(InputError::Eof)
+        ));
+        assert_eq!(EofPolicy::<i32>::AsNone.apply(eof()).unwrap(), None);
+        assert_eq!(EofPolicy::AsDefault(7).apply(eof()).unwrap(), Some(7));
+        assert!(matches!(
+            EofPolicy::<i32>::AsEmptyString.apply(eof()),
+            Err(InputError::Parse { ref input, .. }) if input.is_empty()
+        ));
+        assert_eq!(EofPolicy::AsDefault(7).apply(Ok(3)).unwrap(), Some(3));
+    }
+
+    /// The policy only applies once the reader is exhausted.
+    #[test]
+    fn test_read_input_on_eof_from() {
+        let mut reader = Cursor::new("1\n");
+        let read = |reader: &mut Cursor<&str>| {
+            read_input_on_eof_from::<_, i32>(reader, None, EofPolicy::AsNone)
+        };
+        assert_eq!(read(&mut reader).unwrap(), Some(1));
+        assert_eq!(read(&mut reader).unwrap(), None);
+    }
+}
@@ -0,0 +1,156 @@
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::{Decompress, InputError, InputReader};
+
+/// The buffer size for file reads, large enough that reading is rarely the
+/// bottleneck when ingesting big files.
+const FILE_BUFFER_SIZE: usize = 64 * 1024;
+
+/// An [`InputError`] from reading a file, with the file's path.
+#[derive(Debug)]
+pub struct FileError<E> {
+    /// The file that was read.
+    pub path: PathBuf,
+    /// One-based number of the line being read, or `None` if the file could
+    /// not be opened.
+    pub line: Option<usize>,
+    /// The underlying error.
+    pub error: InputError<E>,
+}
+
+impl<E: std::fmt::Display + std::fmt::Debug> std::fmt::Display for FileError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.path.display(), line, self.error),
+            None => write!(f, "{}: {}", self.path.display(), self.error),
+        }
+    }
+}
+
+impl<E: std::fmt::Display + std::fmt::Debug> std::error::Error for FileError<E> {}
+
+/// A file opened for reading, decompressed if it is gzip or zstd.
+type FileSource = Decompress<BufReader<File>>;
+
+/// Opens `path` for buffered reading, detecting its compression.
+fn open(path: &Path) -> io::Result<FileSource> {
+    Decompress::new(BufReader::with_capacity(
+        FILE_BUFFER_SIZE,
+        File::open(path)?,
+    ))
+}
+
+/// Reads every line of the file at `path`, parsing each into `T`.
+///
+/// A gzip or zstd file is decompressed first, with the `flate2` or `zstd`
+/// feature, as by [`Decompress`].
+///
+/// The first line that fails stops the read, and is reported as a
+/// [`FileError`] with the path and line number, as is a file that cannot be
+/// opened.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::read_lines_from_path;
+///
+/// let ports: Vec<u16> = read_lines_from_path("ports.txt").unwrap();
+/// ```
+pub fn read_lines_from_path<T>(path: impl AsRef<Path>) -> Result<Vec<T>, FileError<T::Err>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let path = path.as_ref();
+    let fail = |line, error| FileError {
+        path: path.to_path_buf(),
+        line,
+        error,
+    };
+    let mut input = InputReader::new(open(path).map_err(|err| fail(None, InputError::Io(err)))?);
+    let mut values = Vec::new();
+    loop {
+        match input.read() {
+            Ok(value) => values.push(value),
+            Err(InputError::Eof) => return Ok(values),
+            Err(error) => return Err(fail(Some(values.len() + 1), error)),
+        }
+    }
+}
+
+impl InputReader<Decompress<BufReader<File>>> {
+    /// Opens the file at `path` for line reads, with a buffer sized for bulk
+    /// input. A gzip or zstd file is decompressed, with the `flate2` or `zstd`
+    /// feature, as by [`Decompress`].
+    ///
+    /// An error opening the file names it, e.g. `data.txt: No such file or
+    /// directory`, and keeps the original error kind.
+    ///
+    /// # Usage:
+    /// ```no_run
+    /// use input_macro::InputReader;
+    ///
+    /// let mut input = InputReader::open("samples.txt").unwrap();
+    /// while let Ok(sample) = input.read::<f64>() {
+    ///     println!("{}", sample);
+    /// }
+    /// ```
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        open(path)
+            .map(InputReader::new)
+            .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit::TempFile;
+
+    /// Every line is parsed, and a bad one is reported with the path and line.
+    #[test]
+    fn test_read_lines_from_path() {
+        let good = TempFile::create("1\n2\r\n3").unwrap();
+        assert_eq!(read_lines_from_path::<u8>(&good.0).unwrap(), [1, 2, 3]);
+
+        let bad = TempFile::create("1\nx\n").unwrap();
+        let err = read_lines_from_path::<u8>(&bad.0).unwrap_err();
+        assert_eq!(err.line, Some(2));
+        let prefix = format!("{}:2: Parse error", bad.0.display());
+        assert!(err.to_string().starts_with(&prefix), "{}", err);
+    }
+
+    /// Compressed files read the same as plain ones.
+    #[cfg(feature = "flate2")]
+    #[test]
+    fn test_read_lines_from_gzip_path() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+        encoder.write_all(b"4\n5\n").unwrap();
+        let file = TempFile::create(encoder.finish().unwrap()).unwrap();
+        assert_eq!(read_lines_from_path::<u8>(&file.0).unwrap(), [4, 5]);
+        let mut input = InputReader::open(&file.0).unwrap();
+        assert_eq!(input.read::<u8>().unwrap(), 4);
+    }
+
+    /// A missing file keeps its error kind and is named in the message.
+    #[test]
+    fn test_open_missing() {
+        let path = std::env::temp_dir().join("input-macro-missing.txt");
+        let err = InputReader::open(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(
+            err.to_string().contains("input-macro-missing.txt"),
+            "{}",
+            err
+        );
+        let err = read_lines_from_path::<u8>(&path).unwrap_err();
+        assert_eq!(err.line, None);
+        assert_eq!(err.to_string().matches("missing").count(), 1, "{}", err);
+    }
+}
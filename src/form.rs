@@ -0,0 +1,423 @@
+use std::fmt::Display;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::{BoxError, Input, InputError, InputSource, Located};
+
+/// The answers collected by a [`Form`], in the order they were asked.
+///
+/// Each answer is kept as the `Display` text of the parsed value, so it can be
+/// read back as the original type with [`Answers::get`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    entries: Vec<(String, String)>,
+}
+
+impl Answers {
+    /// Parses the answer for `key` as `T`.
+    ///
+    /// Returns `None` if the step was not answered or the text does not parse as `T`.
+    pub fn get<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.get_str(key)?.parse().ok()
+    }
+
+    /// Returns the answer for `key` as text.
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns whether `key` was answered.
+    pub fn contains(&self, key: &str) -> bool {
+        self.get_str(key).is_some()
+    }
+
+    /// Iterates over `(key, answer)` pairs in the order they were asked.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Returns the number of answers.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether no step was answered.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn insert(&mut self, key: String, value: String) {
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = value,
+            None => self.entries.push((key, value)),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Answers {
+    /// Serializes the answers as a JSON object, in the order they were asked.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Loads answers from a JSON object, e.g. one written by [`Answers::to_json`].
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Serializes the answers as a TOML table, in the order they were asked.
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string(self)
+    }
+
+    /// Loads answers from a TOML table, e.g. one written by [`Answers::to_toml`].
+    pub fn from_toml(toml: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(toml)
+    }
+}
+
+/// Serializes as a map from step key to answer text.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Answers {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+/// Deserializes from a map whose values are strings, numbers, or booleans, so
+/// hand-written files like `port = 8080` load as well as saved ones.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Answers {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AnswersVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for AnswersVisitor {
+            type Value = Answers;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("a map of answers")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Answers, A::Error> {
+                let mut answers = Answers::default();
+                while let Some((key, AnswerText(value))) = map.next_entry()? {
+                    answers.insert(key, value);
+                }
+                Ok(answers)
+            }
+        }
+
+        deserializer.deserialize_map(AnswersVisitor)
+    }
+}
+
+/// A scalar answer read back as its text.
+#[cfg(feature = "serde")]
+struct AnswerText(String);
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AnswerText {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TextVisitor;
+
+        impl serde::de::Visitor<'_> for TextVisitor {
+            type Value = AnswerText;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("a string, number, or boolean")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<AnswerText, E> {
+                Ok(AnswerText(v.to_string()))
+            }
+
+            fn visit_bool<E>(self, v: bool) -> Result<AnswerText, E> {
+                Ok(AnswerText(v.to_string()))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<AnswerText, E> {
+                Ok(AnswerText(v.to_string()))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<AnswerText, E> {
+                Ok(AnswerText(v.to_string()))
+            }
+
+            fn visit_f64<E>(self, v: f64) -> Result<AnswerText, E> {
+                Ok(AnswerText(v.to_string()))
+            }
+        }
+
+        deserializer.deserialize_any(TextVisitor)
+    }
+}
+
+/// Runs one step given the reader, the answers so far, and the step's pre-filled
+/// default; `Ok(None)` means the step was skipped.
+type Step<'a> = Box<
+    dyn FnOnce(
+            &mut dyn BufRead,
+            &Answers,
+            Option<&str>,
+        ) -> Result<Option<String>, InputError<BoxError>>
+        + 'a,
+>;
+
+/// A sequence of typed prompts run one after another, collecting the answers
+/// under a key per step.
+///
+/// Each step is an [`Input`], so it keeps its own prompt, default, validators,
+/// and retries. The first step that fails aborts the form and its error is
+/// returned, with the parse error boxed; answering with EOF (Ctrl-D) is the
+/// usual way for a user to abort. Any reader set on a step's `Input` is
+/// ignored in favor of the form's.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::{Form, Input};
+///
+/// let answers = Form::new()
+///     .field::<String>("host", "Host: ")
+///     .step("port", Input::<u16>::new().prompt("Port: ").default(8080))
+///     .run()
+///     .unwrap();
+///
+/// let host: String = answers.get("host").unwrap();
+/// let port: u16 = answers.get("port").unwrap();
+/// ```
+#[derive(Default)]
+pub struct Form<'a> {
+    steps: Vec<(String, Step<'a>)>,
+    defaults: Answers,
+}
+
+impl<'a> Form<'a> {
+    /// Creates a form with no steps.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a step that runs `input` and stores its answer under `key`.
+    ///
+    /// A later step with the same key replaces the earlier answer.
+    pub fn step<T>(self, key: impl Into<String>, input: Input<'a, T>) -> Self
+    where
+        T: FromStr + Display + 'a,
+        T::Err: Display + std::fmt::Debug + Into<BoxError>,
+    {
+        self.step_with(key, move |_| Some(input))
+    }
+
+    /// Adds a step that runs `input` only if `condition` holds for the answers
+    /// collected so far; otherwise the step is skipped and `key` is left
+    /// unanswered.
+    ///
+    /// # Usage:
+    /// ```no_run
+    /// use input_macro::{Form, Input};
+    ///
+    /// let answers = Form::new()
+    ///     .field::<bool>("tls", "Enable TLS? ")
+    ///     .step_if("cert", Input::<String>::new().prompt("Cert path: "), |a| {
+    ///         a.get("tls") == Some(true)
+    ///     })
+    ///     .run()
+    ///     .unwrap();
+    /// ```
+    pub fn step_if<T, F>(self, key: impl Into<String>, input: Input<'a, T>, condition: F) -> Self
+    where
+        T: FromStr + Display + 'a,
+        T::Err: Display + std::fmt::Debug + Into<BoxError>,
+        F: FnOnce(&Answers) -> bool + 'a,
+    {
+        self.step_with(key, move |answers| condition(answers).then_some(input))
+    }
+
+    /// Adds a step whose `Input` is built from the answers collected so far,
+    /// so it can branch between prompts or derive a default from an earlier
+    /// answer. Returning `None` skips the step.
+    ///
+    /// # Usage:
+    /// ```no_run
+    /// use input_macro::{Form, Input};
+    ///
+    /// let answers = Form::new()
+    ///     .field::<bool>("tls", "Enable TLS? ")
+    ///     .step_with("port", |a| {
+    ///         let default = if a.get("tls") == Some(true) { 443 } else { 80 };
+    ///         Some(Input::<u16>::new().prompt("Port: ").default(default))
+    ///     })
+    ///     .run()
+    ///     .unwrap();
+    /// ```
+    pub fn step_with<T, F>(mut self, key: impl Into<String>, build: F) -> Self
+    where
+        T: FromStr + Display + 'a,
+        T::Err: Display + std::fmt::Debug + Into<BoxError>,
+        F: FnOnce(&Answers) -> Option<Input<'a, T>> + 'a,
+    {
+        let step: Step<'a> = Box::new(move |reader, answers, default| match build(answers) {
+            Some(mut input) => {
+                if let Some(value) = default.and_then(|d| d.parse::<T>().ok()) {
+                    input = input.default(value);
+                }
+                input
+                    .read_using(reader)
+                    .map(|value| Some(value.to_string()))
+                    .map_err(InputError::boxed)
+            }
+            None => Ok(None),
+        });
+        self.steps.push((key.into(), step));
+        self
+    }
+
+    /// Adds a step that asks for a `T` with `prompt` and no other options.
+    pub fn field<T>(self, key: impl Into<String>, prompt: impl Display) -> Self
+    where
+        T: FromStr + Display + 'a,
+        T::Err: Display + std::fmt::Debug + Into<BoxError>,
+    {
+        self.step(key, Input::<T>::new().prompt(prompt))
+    }
+
+    /// Pre-fills steps from earlier answers, such as a session saved to disk
+    /// with the `serde` feature.
+    ///
+    /// A step whose key appears in `defaults` uses that answer as its default,
+    /// replacing any default set on its `Input`. Answers that do not parse as
+    /// the step's type are ignored.
+    pub fn defaults(mut self, defaults: Answers) -> Self {
+        self.defaults = defaults;
+        self
+    }
+
+    /// Runs every step on `reader`, in the order they were added.
+    pub fn run_from(self, reader: &mut dyn BufRead) -> Result<Answers, InputError<BoxError>> {
+        let mut answers = Answers::default();
+        for (key, step) in self.steps {
+            if let Some(value) = step(reader, &answers, self.defaults.get_str(&key))? {
+                answers.insert(key, value);
+            }
+        }
+        Ok(answers)
+    }
+
+    /// Runs every step on `source`, printing prompts only if it [is
+    /// interactive](crate::InputSource::is_interactive).
+    pub fn run_from_source(
+        self,
+        source: &mut dyn InputSource,
+    ) -> Result<Answers, InputError<BoxError>> {
+        let _hidden = (!source.is_interactive()).then(crate::source::hide_prompts);
+        self.run_from(source)
+    }
+
+    /// Runs every step on stdin.
+    #[track_caller]
+    pub fn run(self) -> Result<Answers, Located<InputError<BoxError>>> {
+        crate::__with_stdin(|reader| self.run_from(reader))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Steps run in order and their answers read back as the original types.
+    #[test]
+    fn test_form_collects_answers() {
+        let mut reader = Cursor::new("db.local\n\n");
+        let answers = Form::new()
+            .field::<String>("host", "Host: ")
+            .step("port", Input::<u16>::new().prompt("Port: ").default(5432))
+            .run_from(&mut reader)
+            .unwrap();
+        assert_eq!(answers.get_str("host"), Some("db.local"));
+        assert_eq!(answers.get::<u16>("port"), Some(5432));
+        assert_eq!(
+            answers.iter().collect::<Vec<_>>(),
+            vec![("host", "db.local"), ("port", "5432")]
+        );
+    }
+
+    /// Conditional steps see earlier answers and are skipped when they do not apply.
+    #[test]
+    fn test_form_conditional_steps() {
+        let form = || {
+            Form::new()
+                .field::<bool>("tls", "TLS? ")
+                .step_if("cert", Input::<String>::new(), |a| {
+                    a.get("tls") == Some(true)
+                })
+                .step_with("port", |a| {
+                    let default = if a.get("tls") == Some(true) { 443 } else { 80 };
+                    Some(Input::<u16>::new().default(default))
+                })
+        };
+
+        let mut reader = Cursor::new("false\n\n");
+        let answers = form().run_from(&mut reader).unwrap();
+        assert!(!answers.contains("cert"));
+        assert_eq!(answers.get::<u16>("port"), Some(80));
+
+        let mut reader = Cursor::new("true\n/etc/cert.pem\n\n");
+        let answers = form().run_from(&mut reader).unwrap();
+        assert_eq!(answers.get_str("cert"), Some("/etc/cert.pem"));
+        assert_eq!(answers.get::<u16>("port"), Some(443));
+    }
+
+    /// Answers round-trip through JSON and TOML and pre-fill a later run.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_answers_serde_round_trip() {
+        let mut reader = Cursor::new("db.local\n5432\n");
+        let answers = Form::new()
+            .field::<String>("host", "Host: ")
+            .field::<u16>("port", "Port: ")
+            .run_from(&mut reader)
+            .unwrap();
+
+        let json = answers.to_json().unwrap();
+        assert_eq!(Answers::from_json(&json).unwrap(), answers);
+        let toml = answers.to_toml().unwrap();
+        assert_eq!(Answers::from_toml(&toml).unwrap(), answers);
+
+        let saved = Answers::from_toml("host = \"db.local\"\nport = 6543\n").unwrap();
+        let mut reader = Cursor::new("\n\n");
+        let answers = Form::new()
+            .field::<String>("host", "Host: ")
+            .field::<u16>("port", "Port: ")
+            .defaults(saved)
+            .run_from(&mut reader)
+            .unwrap();
+        assert_eq!(answers.get_str("host"), Some("db.local"));
+        assert_eq!(answers.get::<u16>("port"), Some(6543));
+    }
+
+    /// The first failing step aborts the form.
+    #[test]
+    fn test_form_aborts_on_error() {
+        let mut reader = Cursor::new("web\n");
+        let res = Form::new()
+            .field::<String>("host", "Host: ")
+            .field::<u16>("port", "Port: ")
+            .field::<bool>("tls", "TLS? ")
+            .run_from(&mut reader);
+        assert!(matches!(res, Err(InputError::Eof)));
+
+        let mut reader = Cursor::new("http\n");
+        let res = Form::new()
+            .field::<u16>("port", "Port: ")
+            .run_from(&mut reader);
+        assert!(matches!(res, Err(InputError::Parse { .. })));
+    }
+}
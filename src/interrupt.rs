@@ -0,0 +1,206 @@
+use std::fmt::Arguments;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::background::stdin_reader;
+use crate::{BackgroundReader, InputError, Located};
+
+/// Set while an interruptible read is waiting for a line.
+static WAITING: AtomicBool = AtomicBool::new(false);
+/// Set by [`interrupt`] until the waiting read notices it.
+static PENDING: AtomicBool = AtomicBool::new(false);
+
+/// Installs this crate's Ctrl-C handler, if no handler is installed yet.
+///
+/// While an interruptible read is waiting, Ctrl-C makes it return
+/// `Err(InputError::Interrupted)`. At any other time Ctrl-C gets its default
+/// action, which ends the process just as it would with no handler. The
+/// interruptible reads call this themselves.
+///
+/// The handler is only installed over the default action. If the
+/// application installed its own handler first, this returns
+/// `ctrlc::Error::MultipleHandlers`, and that handler is left in charge; it
+/// can call [`interrupt`] to get the same behavior.
+pub fn install_interrupt_handler() -> Result<(), ctrlc::Error> {
+    static INSTALLED: Mutex<bool> = Mutex::new(false);
+    let mut installed = INSTALLED.lock().unwrap_or_else(|e| e.into_inner());
+    if !*installed {
+        ctrlc::set_handler(on_ctrl_c)?;
+        *installed = true;
+    }
+    Ok(())
+}
+
+fn on_ctrl_c() {
+    if !interrupt() {
+        default_ctrl_c();
+    }
+}
+
+/// Does what Ctrl-C does with no handler installed: ends the process as
+/// killed by `SIGINT`.
+#[cfg(unix)]
+fn default_ctrl_c() {
+    // SAFETY: this runs on ctrlc's handler thread, not in a signal handler,
+    // and only restores the default action before raising the signal
+    unsafe {
+        libc::signal(libc::SIGINT, libc::SIG_DFL);
+        libc::raise(libc::SIGINT);
+    }
+}
+
+/// Does what Ctrl-C does with no handler installed: exits with
+/// `STATUS_CONTROL_C_EXIT`, as the default console handler does.
+#[cfg(windows)]
+fn default_ctrl_c() {
+    std::process::exit(0xC000_013A_u32 as i32);
+}
+
+/// Does what Ctrl-C does with no handler installed, as closely as possible.
+#[cfg(not(any(unix, windows)))]
+fn default_ctrl_c() {
+    std::process::exit(130);
+}
+
+/// Interrupts the interruptible read that is waiting, if there is one, and
+/// returns whether there was.
+///
+/// Call this from an application's own Ctrl-C handler to make reads return
+/// `Err(InputError::Interrupted)`.
+pub fn interrupt() -> bool {
+    let waiting = WAITING.load(Ordering::SeqCst);
+    if waiting {
+        PENDING.store(true, Ordering::SeqCst);
+    }
+    waiting
+}
+
+impl BackgroundReader {
+    /// Prints the optional prompt, then waits for a line until the user
+    /// presses Ctrl-C, and parses it into `T`.
+    ///
+    /// Returns `Err(InputError::Interrupted)` on Ctrl-C; a line that arrives
+    /// afterwards is kept for the next read. See [`install_interrupt_handler`].
+    pub fn read_interruptible<T>(
+        &mut self,
+        prompt: Option<Arguments<'_>>,
+    ) -> Result<T, InputError<T::Err>>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display + std::fmt::Debug,
+    {
+        // Another handler may be installed; it can still call `interrupt`
+        let _ = install_interrupt_handler();
+        let _waiting = Waiting::start();
+        self.read_until_stopped(prompt, || {
+            PENDING
+                .swap(false, Ordering::SeqCst)
+                .then_some(InputError::Interrupted)
+        })
+    }
+}
+
+/// Marks an interruptible read as waiting until dropped.
+struct Waiting;
+
+impl Waiting {
+    fn start() -> Self {
+        PENDING.store(false, Ordering::SeqCst);
+        WAITING.store(true, Ordering::SeqCst);
+        Waiting
+    }
+}
+
+impl Drop for Waiting {
+    fn drop(&mut self) {
+        WAITING.store(false, Ordering::SeqCst);
+        PENDING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Reads one line from stdin, returning `Err(InputError::Interrupted)` if the
+/// user presses Ctrl-C first.
+///
+/// Shares the background stdin reader of
+/// [`read_input_timeout`](crate::read_input_timeout), with the same caveat
+/// about mixing it with blocking reads.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::{read_input_interruptible, InputError, Located};
+///
+/// match read_input_interruptible::<String>(Some(format_args!("Name: "))) {
+///     Ok(name) => println!("Hello, {}!", name),
+///     Err(Located { error: InputError::Interrupted, .. }) => println!("Aborted."),
+///     Err(Located { error: InputError::Eof, .. }) => println!("No more input."),
+///     Err(e) => eprintln!("{}", e),
+/// }
+/// ```
+#[track_caller]
+pub fn read_input_interruptible<T>(
+    prompt: Option<Arguments<'_>>,
+) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let mut reader = stdin_reader().lock().unwrap_or_else(|e| e.into_inner());
+    crate::source::located(reader.read_interruptible(prompt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{self, BufReader, Write};
+    use std::thread;
+    use std::time::Duration;
+
+    /// An interrupt aborts a waiting read, and lines are otherwise read normally.
+    #[test]
+    fn test_read_interruptible() {
+        let (pipe_reader, mut pipe_writer) = io::pipe().unwrap();
+        let mut reader = BackgroundReader::spawn(BufReader::new(pipe_reader));
+        pipe_writer.write_all(b"x\n").unwrap();
+        assert!(matches!(
+            reader.read_interruptible::<i32>(None),
+            Err(InputError::Parse { .. })
+        ));
+
+        assert!(!interrupt());
+        let handle = thread::spawn(|| {
+            while !interrupt() {
+                thread::sleep(Duration::from_millis(5));
+            }
+        });
+        let res = reader.read_interruptible::<i32>(None);
+        assert!(matches!(res, Err(InputError::Interrupted)));
+        handle.join().unwrap();
+
+        pipe_writer.write_all(b"7\n").unwrap();
+        assert_eq!(reader.read_interruptible::<i32>(None).unwrap(), 7);
+    }
+
+    /// Outside a read, Ctrl-C ends the process by `SIGINT`, as with no handler.
+    /// The test runs itself in a child process to see that happen.
+    #[cfg(unix)]
+    #[test]
+    fn test_ctrl_c_outside_read() {
+        use std::os::unix::process::ExitStatusExt;
+
+        if std::env::var_os("INPUT_MACRO_CTRL_C_CHILD").is_some() {
+            install_interrupt_handler().unwrap();
+            // SAFETY: raising a signal that has a handler installed
+            unsafe { libc::raise(libc::SIGINT) };
+            thread::sleep(Duration::from_secs(10));
+            std::process::exit(0);
+        }
+        let status = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "interrupt::tests::test_ctrl_c_outside_read"])
+            .env("INPUT_MACRO_CTRL_C_CHILD", "1")
+            .stdout(std::process::Stdio::null())
+            .status()
+            .unwrap();
+        assert_eq!(status.signal(), Some(libc::SIGINT));
+    }
+}
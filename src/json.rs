@@ -0,0 +1,113 @@
+use std::fmt::Arguments;
+use std::io::BufRead;
+
+use serde::de::DeserializeOwned;
+
+use crate::{read_trimmed_line, InputError, Located};
+
+/// Reads one line and deserializes it as JSON into `T`.
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_json_line_from;
+///
+/// #[derive(serde::Deserialize)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let mut reader = std::io::Cursor::new("{\"x\": 1, \"y\": 2}\n");
+/// let p: Point = read_json_line_from(&mut reader, None).unwrap();
+/// assert_eq!((p.x, p.y), (1, 2));
+/// ```
+pub fn read_json_line_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<T, InputError<serde_json::Error>>
+where
+    R: BufRead + ?Sized,
+    T: DeserializeOwned,
+{
+    let mut input = String::new();
+    let line = read_trimmed_line(reader, prompt, &mut input)?;
+    serde_json::from_str(line).map_err(|source| InputError::parse(source, line))
+}
+
+/// A convenience wrapper around [`read_json_line_from`] that reads from stdin.
+#[track_caller]
+pub fn read_json_line<T: DeserializeOwned>(
+    prompt: Option<Arguments<'_>>,
+) -> Result<T, Located<InputError<serde_json::Error>>> {
+    crate::__with_stdin(|reader| read_json_line_from(reader, prompt))
+}
+
+/// Returns an iterator that deserializes each line of newline-delimited JSON
+/// (NDJSON) into `T`, ending at EOF.
+///
+/// Blank lines are skipped. A line that does not deserialize is yielded as
+/// `Err(InputError::Parse)` and iteration continues with the next line.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::json_lines;
+///
+/// // e.g. `jq -c '.items[]' data.json | mytool`
+/// for item in json_lines::<_, serde_json::Value>(std::io::stdin().lock()) {
+///     println!("{}", item.unwrap());
+/// }
+/// ```
+pub fn json_lines<R, T>(
+    mut reader: R,
+) -> impl Iterator<Item = Result<T, InputError<serde_json::Error>>>
+where
+    R: BufRead,
+    T: DeserializeOwned,
+{
+    std::iter::from_fn(move || loop {
+        let mut input = String::new();
+        match read_trimmed_line(&mut reader, None, &mut input) {
+            Ok(line) if line.trim().is_empty() => continue,
+            Ok(line) => {
+                return Some(
+                    serde_json::from_str(line).map_err(|source| InputError::parse(source, line)),
+                )
+            }
+            Err(InputError::Eof) => return None,
+            Err(err) => return Some(Err(err)),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Event {
+        id: u32,
+        kind: String,
+    }
+
+    /// One line deserializes into a struct; malformed JSON is a `Parse` error.
+    #[test]
+    fn test_read_json_line() {
+        let mut reader = Cursor::new("{\"id\": 1, \"kind\": \"start\"}\n{\"id\": \n");
+        let event: Event = read_json_line_from(&mut reader, None).unwrap();
+        assert_eq!(event.kind, "start");
+        let res = read_json_line_from::<_, Event>(&mut reader, None);
+        assert!(matches!(res, Err(InputError::Parse { .. })));
+    }
+
+    /// NDJSON lines are yielded in order, skipping blanks and surviving bad lines.
+    #[test]
+    fn test_json_lines() {
+        let input = "{\"id\": 1, \"kind\": \"a\"}\n\n[1]\n{\"id\": 2, \"kind\": \"b\"}";
+        let results: Vec<_> = json_lines::<_, Event>(Cursor::new(input)).collect();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().id, 1);
+        assert!(matches!(results[1], Err(InputError::Parse { .. })));
+        assert_eq!(results[2].as_ref().unwrap().id, 2);
+    }
+}
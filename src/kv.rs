@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::fmt::Arguments;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::{read_trimmed_line, InputError, StdinError};
+
+/// A failure on one `key=value` pair.
+#[derive(Debug)]
+pub enum KvError<E> {
+    /// The token has no `=`.
+    MissingSeparator { token: String },
+    /// The value for `key` failed to parse.
+    Invalid { key: String, error: E },
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for KvError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KvError::MissingSeparator { token } => write!(f, "expected key=value, got {:?}", token),
+            KvError::Invalid { key, error } => write!(f, "value for {:?}: {}", key, error),
+        }
+    }
+}
+
+impl<E: std::fmt::Display + std::fmt::Debug> std::error::Error for KvError<E> {}
+
+/// Reads one line of whitespace-separated `key=value` pairs into a map.
+///
+/// Values are parsed into `V` (usually `String`); a key given twice keeps its
+/// last value. Values cannot contain spaces; use [`read_kv_until_blank_from`]
+/// with one pair per line for that. An empty line yields an empty map.
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_kv_line_from;
+/// use std::collections::HashMap;
+///
+/// let mut reader = std::io::Cursor::new("width=80 height=24\n");
+/// let size: HashMap<String, u16> = read_kv_line_from(&mut reader, None).unwrap();
+/// assert_eq!(size["height"], 24);
+/// ```
+pub fn read_kv_line_from<R, V>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<HashMap<String, V>, InputError<KvError<V::Err>>>
+where
+    R: BufRead + ?Sized,
+    V: FromStr,
+{
+    let mut input = String::new();
+    let line = read_trimmed_line(reader, prompt, &mut input)?;
+    let mut map = HashMap::new();
+    for token in line.split_whitespace() {
+        let (key, value) = parse_pair(token)?;
+        map.insert(key, value);
+    }
+    Ok(map)
+}
+
+/// A convenience wrapper around [`read_kv_line_from`] that reads from stdin.
+#[track_caller]
+pub fn read_kv_line<V: FromStr>(
+    prompt: Option<Arguments<'_>>,
+) -> Result<HashMap<String, V>, StdinError<KvError<V::Err>>> {
+    crate::__with_stdin(|reader| read_kv_line_from(reader, prompt))
+}
+
+/// Reads `key=value` lines into a map until a blank line or EOF.
+///
+/// Each line holds one pair, with whitespace around the key and value
+/// trimmed, so values may contain spaces (`name = Jane Doe`). The prompt, if
+/// any, is printed before every line. A key given twice keeps its last value.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::read_kv_until_blank;
+/// use std::collections::HashMap;
+///
+/// println!("Enter settings, then an empty line:");
+/// let settings: HashMap<String, String> =
+///     read_kv_until_blank(Some(format_args!("> "))).unwrap();
+/// ```
+pub fn read_kv_until_blank_from<R, V>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<HashMap<String, V>, InputError<KvError<V::Err>>>
+where
+    R: BufRead + ?Sized,
+    V: FromStr,
+{
+    let mut map = HashMap::new();
+    loop {
+        let mut input = String::new();
+        let line = match read_trimmed_line(reader, prompt, &mut input) {
+            Ok(line) if line.trim().is_empty() => break,
+            Ok(line) => line,
+            Err(InputError::Eof) => break,
+            Err(err) => return Err(err),
+        };
+        let (key, value) = parse_pair(line)?;
+        map.insert(key, value);
+    }
+    Ok(map)
+}
+
+/// A convenience wrapper around [`read_kv_until_blank_from`] that reads from stdin.
+#[track_caller]
+pub fn read_kv_until_blank<V: FromStr>(
+    prompt: Option<Arguments<'_>>,
+) -> Result<HashMap<String, V>, StdinError<KvError<V::Err>>> {
+    crate::__with_stdin(|reader| read_kv_until_blank_from(reader, prompt))
+}
+
+fn parse_pair<V: FromStr>(token: &str) -> Result<(String, V), InputError<KvError<V::Err>>> {
+    let (key, value) = token.split_once('=').ok_or_else(|| {
+        let error = KvError::MissingSeparator {
+            token: token.to_string(),
+        };
+        InputError::parse(error, token)
+    })?;
+    let key = key.trim().to_string();
+    match value.trim().parse() {
+        Ok(value) => Ok((key, value)),
+        Err(error) => Err(InputError::parse(KvError::Invalid { key, error }, token)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Pairs on one line are split on whitespace; later keys win.
+    #[test]
+    fn test_read_kv_line() {
+        let mut reader = Cursor::new("a=1 b=2 a=3\nflag\n");
+        let map: HashMap<String, i32> = read_kv_line_from(&mut reader, None).unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!((map["a"], map["b"]), (3, 2));
+
+        let res = read_kv_line_from::<_, String>(&mut reader, None);
+        assert!(matches!(
+            res,
+            Err(InputError::Parse { source: KvError::MissingSeparator { ref token }, .. }) if token == "flag"
+        ));
+    }
+
+    /// Line-per-pair input stops at a blank line and keeps spaces in values.
+    #[test]
+    fn test_read_kv_until_blank() {
+        let mut reader = Cursor::new("name = Jane Doe\nrole=admin\n\nnext=1\n");
+        let map: HashMap<String, String> = read_kv_until_blank_from(&mut reader, None).unwrap();
+        assert_eq!(map["name"], "Jane Doe");
+        assert_eq!(map["role"], "admin");
+        assert!(!map.contains_key("next"));
+
+        let mut reader = Cursor::new("port=http\n");
+        let res = read_kv_until_blank_from::<_, u16>(&mut reader, None);
+        assert!(matches!(
+            res,
+            Err(InputError::Parse { source: KvError::Invalid { ref key, .. }, .. }) if key == "port"
+        ));
+    }
+}
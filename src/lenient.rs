@@ -0,0 +1,432 @@
+use std::fmt::{self, Arguments, Display};
+use std::io::BufRead;
+use std::ops::RangeBounds;
+use std::str::FromStr;
+
+use crate::{
+    describe_range, read_input_from, read_input_or_default_from, read_optional_from,
+    read_until_valid_from, read_vec_from, InputError, Located, StdinError, TokenError,
+};
+
+/// Human-oriented parsing, separate from a type's strict `FromStr`.
+///
+/// Implementations should be forgiving of how people type: surrounding
+/// whitespace, letter case, and common synonyms. The crate implements it for
+/// the std scalar types; `bool`, for example, accepts `y`/`yes`/`on`/`1` as
+/// well as `true`.
+///
+/// A blanket impl over `FromStr` would rule out lenient impls like the one for
+/// `bool`, and for any type with a strict `FromStr`, since Rust does not allow
+/// overlapping impls. So other `FromStr` types opt in with
+/// [`impl_from_input!`](crate::impl_from_input), or implement the trait by hand.
+///
+/// Each of the core readers has a `_lenient` counterpart that parses with
+/// this trait, such as [`read_vec_lenient`] and [`read_until_valid_lenient`];
+/// any other reader can parse leniently through [`Lenient`].
+///
+/// # Usage:
+/// ```
+/// use input_macro::FromInput;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Level { Low, High }
+///
+/// impl FromInput for Level {
+///     type Err = String;
+///     fn from_input(input: &str) -> Result<Self, String> {
+///         match input.trim().to_lowercase().as_str() {
+///             "low" => Ok(Level::Low),
+///             "high" => Ok(Level::High),
+///             other => Err(format!("unknown level {:?}", other)),
+///         }
+///     }
+/// }
+///
+/// assert_eq!(Level::from_input(" HIGH "), Ok(Level::High));
+/// ```
+pub trait FromInput: Sized {
+    /// The error returned when the input cannot be understood.
+    type Err;
+
+    /// Parses a line of user input, without its line ending.
+    fn from_input(input: &str) -> Result<Self, Self::Err>;
+}
+
+/// Implements [`FromInput`] for `FromStr` types by trimming the input and
+/// delegating to `FromStr`.
+///
+/// # Usage:
+/// ```
+/// # use input_macro::{impl_from_input, FromInput};
+/// # use std::net::Ipv4Addr;
+/// struct Host(Ipv4Addr);
+/// # impl std::str::FromStr for Host {
+/// #     type Err = std::net::AddrParseError;
+/// #     fn from_str(s: &str) -> Result<Self, Self::Err> { s.parse().map(Host) }
+/// # }
+/// impl_from_input!(Host);
+///
+/// assert!(Host::from_input(" 127.0.0.1 ").is_ok());
+/// ```
+#[macro_export]
+macro_rules! impl_from_input {
+    ($($t:ty),+ $(,)?) => {
+        $(
+            impl $crate::FromInput for $t {
+                type Err = <$t as ::std::str::FromStr>::Err;
+                fn from_input(input: &str) -> Result<Self, Self::Err> {
+                    input.trim().parse()
+                }
+            }
+        )+
+    };
+}
+
+impl_from_input!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, char);
+
+impl FromInput for String {
+    type Err = std::convert::Infallible;
+
+    fn from_input(input: &str) -> Result<Self, Self::Err> {
+        Ok(input.trim().to_string())
+    }
+}
+
+impl FromInput for bool {
+    type Err = ParseBoolInputError;
+
+    fn from_input(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "y" | "yes" | "true" | "on" | "1" => Ok(true),
+            "n" | "no" | "false" | "off" | "0" => Ok(false),
+            _ => Err(ParseBoolInputError(input.trim().to_string())),
+        }
+    }
+}
+
+/// The error returned when lenient `bool` parsing does not recognize the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBoolInputError(String);
+
+impl std::fmt::Display for ParseBoolInputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expected yes/no, true/false, on/off, or 1/0, got {:?}",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseBoolInputError {}
+
+/// Adapts a [`FromInput`] type to `FromStr`, so any read function can parse
+/// leniently.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::{read_vec, Lenient};
+///
+/// // Accepts e.g. "yes off 1"
+/// let flags: Vec<Lenient<bool>> = read_vec(None, None).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Lenient<T>(pub T);
+
+impl<T> Lenient<T> {
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Display> Display for Lenient<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: FromInput> FromStr for Lenient<T> {
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        T::from_input(s).map(Lenient)
+    }
+}
+
+/// Like [`read_input_from`], but parses with [`FromInput`] instead of `FromStr`.
+pub fn read_input_lenient_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromInput,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    read_input_from::<_, Lenient<T>>(reader, prompt).map(Lenient::into_inner)
+}
+
+/// The lenient counterpart of [`read_input`](crate::read_input).
+#[track_caller]
+pub fn read_input_lenient<T>() -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromInput,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::__with_stdin(|reader| read_input_lenient_from(reader, None))
+}
+
+/// The lenient counterpart of [`read_input_with_prompt`](crate::read_input_with_prompt).
+#[track_caller]
+pub fn read_input_with_prompt_lenient<T>(
+    prompt: Arguments<'_>,
+) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromInput,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::__with_stdin(|reader| read_input_lenient_from(reader, Some(prompt)))
+}
+
+/// The lenient counterpart of [`read_vec_from`].
+pub fn read_vec_lenient_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    delimiter: Option<char>,
+) -> Result<Vec<T>, InputError<TokenError<T::Err>>>
+where
+    R: BufRead + ?Sized,
+    T: FromInput,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let values = read_vec_from::<_, Lenient<T>>(reader, prompt, delimiter)?;
+    Ok(values.into_iter().map(Lenient::into_inner).collect())
+}
+
+/// The lenient counterpart of [`read_vec`](crate::read_vec).
+#[track_caller]
+pub fn read_vec_lenient<T>(
+    prompt: Option<Arguments<'_>>,
+    delimiter: Option<char>,
+) -> Result<Vec<T>, StdinError<TokenError<T::Err>>>
+where
+    T: FromInput,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::__with_stdin(|reader| read_vec_lenient_from(reader, prompt, delimiter))
+}
+
+/// The lenient counterpart of [`read_input_validated_from`](crate::read_input_validated_from).
+pub fn read_input_validated_lenient_from<R, T, F>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    mut validator: F,
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromInput,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+    F: FnMut(&T) -> Result<(), String>,
+{
+    let value = read_input_lenient_from(reader, prompt)?;
+    validator(&value).map_err(InputError::Invalid)?;
+    Ok(value)
+}
+
+/// The lenient counterpart of [`read_input_validated`](crate::read_input_validated).
+#[track_caller]
+pub fn read_input_validated_lenient<T, F>(
+    prompt: Option<Arguments<'_>>,
+    validator: F,
+) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromInput,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+    F: FnMut(&T) -> Result<(), String>,
+{
+    crate::__with_stdin(|reader| read_input_validated_lenient_from(reader, prompt, validator))
+}
+
+/// The lenient counterpart of [`read_in_range_from`](crate::read_in_range_from).
+pub fn read_in_range_lenient_from<R, T, B>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    range: B,
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromInput + PartialOrd + std::fmt::Display,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+    B: RangeBounds<T>,
+{
+    read_input_validated_lenient_from(reader, prompt, |value: &T| {
+        if range.contains(value) {
+            Ok(())
+        } else {
+            Err(format!("{} is outside {}", value, describe_range(&range)))
+        }
+    })
+}
+
+/// The lenient counterpart of [`read_in_range`](crate::read_in_range).
+#[track_caller]
+pub fn read_in_range_lenient<T, B>(
+    prompt: Option<Arguments<'_>>,
+    range: B,
+) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromInput + PartialOrd + std::fmt::Display,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+    B: RangeBounds<T>,
+{
+    crate::__with_stdin(|reader| read_in_range_lenient_from(reader, prompt, range))
+}
+
+/// The lenient counterpart of [`read_input_or_default_from`].
+pub fn read_input_or_default_lenient_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    default: T,
+    show_default: bool,
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromInput + std::fmt::Display,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    read_input_or_default_from(reader, prompt, Lenient(default), show_default)
+        .map(Lenient::into_inner)
+}
+
+/// The lenient counterpart of [`read_input_or_default`](crate::read_input_or_default).
+#[track_caller]
+pub fn read_input_or_default_lenient<T>(
+    prompt: Option<Arguments<'_>>,
+    default: T,
+    show_default: bool,
+) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromInput + std::fmt::Display,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::__with_stdin(|reader| {
+        read_input_or_default_lenient_from(reader, prompt, default, show_default)
+    })
+}
+
+/// The lenient counterpart of [`read_optional_from`].
+pub fn read_optional_lenient_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<Option<T>, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromInput,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    read_optional_from::<_, Lenient<T>>(reader, prompt).map(|value| value.map(Lenient::into_inner))
+}
+
+/// The lenient counterpart of [`read_optional`](crate::read_optional).
+#[track_caller]
+pub fn read_optional_lenient<T>(
+    prompt: Option<Arguments<'_>>,
+) -> Result<Option<T>, Located<InputError<T::Err>>>
+where
+    T: FromInput,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::__with_stdin(|reader| read_optional_lenient_from(reader, prompt))
+}
+
+/// The lenient counterpart of [`read_until_valid_from`].
+pub fn read_until_valid_lenient_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    max_attempts: usize,
+    retry_message: Option<Arguments<'_>>,
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromInput,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    read_until_valid_from::<_, Lenient<T>>(reader, prompt, max_attempts, retry_message)
+        .map(Lenient::into_inner)
+}
+
+/// The lenient counterpart of [`read_until_valid`](crate::read_until_valid).
+#[track_caller]
+pub fn read_until_valid_lenient<T>(
+    prompt: Option<Arguments<'_>>,
+    max_attempts: usize,
+    retry_message: Option<Arguments<'_>>,
+) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromInput,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::__with_stdin(|reader| {
+        read_until_valid_lenient_from(reader, prompt, max_attempts, retry_message)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Lenient `bool` accepts the usual human spellings in any case.
+    #[test]
+    fn test_bool_from_input() {
+        for yes in ["y", "YES", " on ", "1", "True"] {
+            assert_eq!(bool::from_input(yes), Ok(true));
+        }
+        for no in ["n", "No", "OFF", "0", "false"] {
+            assert_eq!(bool::from_input(no), Ok(false));
+        }
+        assert!(bool::from_input("maybe").is_err());
+    }
+
+    /// Numbers and strings are trimmed before parsing.
+    #[test]
+    fn test_trimmed_scalars() {
+        assert_eq!(i32::from_input("  42 "), Ok(42));
+        assert_eq!(String::from_input(" hi ").unwrap(), "hi");
+    }
+
+    /// The lenient readers go through `FromInput`, not `FromStr`.
+    #[test]
+    fn test_read_input_lenient() {
+        let mut reader = Cursor::new("on\n 7 \n");
+        let flag: bool = read_input_lenient_from(&mut reader, None).unwrap();
+        let n: u8 = read_input_lenient_from(&mut reader, None).unwrap();
+        assert!(flag);
+        assert_eq!(n, 7);
+    }
+
+    /// `Lenient` lets strict read functions parse leniently.
+    #[test]
+    fn test_lenient_adapter() {
+        let mut reader = Cursor::new("yes off 1\n");
+        let flags: Vec<Lenient<bool>> = crate::read_vec_from(&mut reader, None, None).unwrap();
+        assert_eq!(flags, vec![Lenient(true), Lenient(false), Lenient(true)]);
+    }
+
+    /// The other counterparts parse leniently and keep their own behavior.
+    #[test]
+    fn test_lenient_counterparts() {
+        let mut reader = Cursor::new("yes, OFF\n\nmaybe\n on \n 20 \n 5 \n");
+        let flags: Vec<bool> = read_vec_lenient_from(&mut reader, None, Some(',')).unwrap();
+        assert_eq!(flags, [true, false]);
+        let missing: Option<bool> = read_optional_lenient_from(&mut reader, None).unwrap();
+        assert_eq!(missing, None);
+        let flag: bool = read_until_valid_lenient_from(&mut reader, None, 2, None).unwrap();
+        assert!(flag);
+        let res = read_in_range_lenient_from::<_, u8, _>(&mut reader, None, 1..=10);
+        assert!(matches!(res, Err(InputError::Invalid(ref m)) if m == "20 is outside 1..=10"));
+        let n: u8 = read_input_or_default_lenient_from(&mut reader, None, 1, false).unwrap();
+        assert_eq!(n, 5);
+    }
+}
@@ -1,91 +1,1365 @@
+use std::convert::Infallible;
 use std::fmt::Arguments;
-use std::io::{self, BufRead, Write};
+use std::io::{self, BufRead};
+use std::ops::{Bound, RangeBounds};
 use std::str::FromStr;
 
-/// A unified error type indicating either an I/O error, a parse error, or EOF.
+// Lets `#[derive(Prompt)]` output, which names `::input_macro`, compile in this crate's tests
+extern crate self as input_macro;
+
+mod ansi;
+#[cfg(feature = "futures")]
+mod async_futures;
+#[cfg(feature = "tokio")]
+mod async_tokio;
+mod background;
+mod bom;
+mod buffered;
+mod builder;
+mod bulk;
+mod bytes;
+mod cancel;
+mod child;
+mod choice;
+#[cfg(feature = "clap")]
+mod cli;
+#[cfg(feature = "color")]
+mod color;
+#[cfg(feature = "rustyline")]
+mod complete;
+#[cfg(all(windows, feature = "windows-console"))]
+mod console;
+#[cfg(feature = "csv")]
+mod csv_record;
+mod decompress;
+#[cfg(feature = "miette")]
+mod diagnostic;
+mod edit;
+#[cfg(feature = "rustyline")]
+mod editor;
+#[cfg(feature = "encoding")]
+mod encoding;
+mod eof;
+mod file;
+mod form;
+#[cfg(feature = "ctrlc")]
+mod interrupt;
+#[cfg(feature = "serde_json")]
+mod json;
+mod kv;
+mod lenient;
+mod limits;
+mod lossy;
+mod multiline;
+mod number;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "rpassword")]
+mod password;
+mod pattern;
+#[cfg(feature = "serde_plain")]
+mod plain;
+mod prompt;
+mod reader;
+mod scanner;
+mod script;
+mod single;
+mod skip;
+mod source;
+mod stats;
+mod tcp;
+#[cfg(feature = "crossterm")]
+mod term;
+pub mod testing;
+mod theme;
+#[cfg(feature = "tracing")]
+mod trace;
+mod tracked;
+mod transcript;
+mod trim;
+mod tty;
+mod tuple;
+#[cfg(feature = "unicode")]
+mod unicode;
+mod words;
+
+pub use ansi::{read_input_strip_ansi, read_input_strip_ansi_from, strip_ansi};
+#[cfg(feature = "futures")]
+pub use async_futures::read_input_to_futures;
+#[cfg(feature = "tokio")]
+#[doc(hidden)]
+pub use async_tokio::__eof_as_none_async;
+#[cfg(feature = "tokio")]
+pub use async_tokio::{read_input_async, read_input_from_async};
+pub use background::{read_input_cancellable, read_input_timeout, BackgroundReader};
+pub use bom::SkipBom;
+pub use builder::{Input, Validation};
+pub use bulk::{
+    collect_all, collect_all_from, read_matrix, read_matrix_flat, read_matrix_flat_from,
+    read_matrix_from, read_n_lines, read_n_lines_from, Collected, LineFailure, MatrixError,
+};
+pub use bytes::{read_bytes_line, read_bytes_line_from, read_os_line, read_os_line_from};
+pub use cancel::{cancellable, CancelError, CancellationToken};
+pub use child::ChildSession;
+pub use choice::{
+    multi_select, multi_select_from, read_choice, read_choice_from, read_choice_matching,
+    read_choice_matching_from, select, select_from, select_matching, select_matching_from, Choices,
+    Matching,
+};
+#[cfg(feature = "clap")]
+pub use cli::{get_matches_interactive, try_get_matches_interactive_from};
+#[cfg(feature = "color")]
+pub use color::{color_choice, set_color_choice, Color, ColorChoice, Style};
+#[cfg(feature = "rustyline")]
+pub use complete::Completer;
+#[cfg(all(windows, feature = "windows-console"))]
+pub use console::WideConsoleReader;
+#[cfg(feature = "csv")]
+pub use csv_record::{read_csv_record, read_csv_record_from};
+pub use decompress::Decompress;
+pub use edit::read_via_editor;
+#[cfg(feature = "encoding")]
+pub use encoding::DecodingReader;
+pub use eof::{read_input_on_eof, read_input_on_eof_from, EofPolicy};
+pub use file::{read_lines_from_path, FileError};
+pub use form::{Answers, Form};
+#[cfg(feature = "derive")]
+pub use input_macro_derive::Prompt;
+#[cfg(feature = "ctrlc")]
+pub use interrupt::{install_interrupt_handler, interrupt, read_input_interruptible};
+#[cfg(feature = "serde_json")]
+pub use json::{json_lines, read_json_line, read_json_line_from};
+pub use kv::{
+    read_kv_line, read_kv_line_from, read_kv_until_blank, read_kv_until_blank_from, KvError,
+};
+pub use lenient::{
+    read_in_range_lenient, read_in_range_lenient_from, read_input_lenient, read_input_lenient_from,
+    read_input_or_default_lenient, read_input_or_default_lenient_from,
+    read_input_validated_lenient, read_input_validated_lenient_from,
+    read_input_with_prompt_lenient, read_optional_lenient, read_optional_lenient_from,
+    read_until_valid_lenient, read_until_valid_lenient_from, read_vec_lenient,
+    read_vec_lenient_from, FromInput, Lenient, ParseBoolInputError,
+};
+pub use limits::{limits, set_limits, Limits};
+pub use lossy::{read_input_lossy, read_input_lossy_from};
+pub use multiline::{
+    read_heredoc, read_heredoc_from, read_multiline, read_multiline_as, read_multiline_as_from,
+    read_multiline_from, Join,
+};
+pub use number::{collect_numbers, collect_numbers_from, read_numbers, read_numbers_from, Number};
+#[cfg(feature = "rayon")]
+pub use parallel::{collect_all_parallel, collect_all_parallel_from};
+#[cfg(feature = "rpassword")]
+pub use password::{read_masked, read_password};
+pub use pattern::PatternError;
+#[doc(hidden)]
+pub use pattern::{__check_pattern, __pattern_field, __pattern_fields};
+#[cfg(feature = "serde_plain")]
+pub use plain::{read_de, read_de_from};
+#[doc(hidden)]
+pub use prompt::__prompt_field;
+pub use prompt::Prompt;
+pub use reader::InputReader;
+pub use scanner::Scanner;
+pub use script::AnswerScript;
+pub use single::{read_char, read_char_from, CharInputError};
+pub use skip::{
+    read_nonblank, read_nonblank_from, read_uncommented, read_uncommented_from, COMMENT_PREFIXES,
+};
+#[doc(hidden)]
+pub use source::__with_stdin;
+pub use source::{
+    has_input_source, is_interactive, prompt_prefix, prompt_suffix, prompt_target,
+    prompt_visibility, reset_input_source, reset_prompt_output, set_input_source,
+    set_prompt_output, set_prompt_prefix, set_prompt_suffix, set_prompt_target,
+    set_prompt_visibility, stdin_session, InputSource, Located, PromptTarget, PromptVisibility,
+    StdinError, StdinSession,
+};
+pub use stats::{ReadEvent, ReadStats};
+pub use tcp::TcpSource;
+#[cfg(feature = "crossterm")]
+pub use term::{read_key, Key, KeyEvents};
+pub use theme::{set_theme, PlainTheme, Theme};
+pub use tracked::{LineError, TrackedReader};
+pub use transcript::{Recording, Transcript};
+pub use trim::{read_input_trim, read_input_trim_from, Trim};
+pub use tty::{read_from_tty, TtySource};
+pub use tuple::{read_tuple, read_tuple_from, FromTokens};
+#[cfg(feature = "unicode")]
+pub use unicode::{read_input_normalized, read_input_normalized_from, Normalization};
+pub use words::{read_shell_words, read_shell_words_from, ShellWordsError};
+
+/// A unified error type indicating an I/O error, a parse error, EOF, or another
+/// reason a read did not produce a value.
 #[derive(Debug)]
 pub enum InputError<E> {
     /// An I/O error occurred (e.g., closed stdin).
     Io(io::Error),
-    /// Failed to parse the input into the desired type.
-    Parse(E),
+    /// Failed to parse the input into the desired type; `input` is the text
+    /// that was parsed, without its line ending.
+    Parse { source: E, input: String },
     /// EOF encountered (read_line returned 0).
     Eof,
+    /// Every allowed attempt failed to parse; holds the number of attempts made.
+    AttemptsExhausted(usize),
+    /// The input parsed but was rejected by a validator.
+    Invalid(String),
+    /// No input arrived before the deadline.
+    Timeout,
+    /// The read was aborted through a [`CancellationToken`].
+    Cancelled,
+    /// The user pressed Ctrl-C during the read.
+    Interrupted,
+    /// The line was longer than the [`Limits`] allow; holds the limit in bytes.
+    TooLong(usize),
+}
+
+impl<E: std::fmt::Display + std::fmt::Debug> std::fmt::Display for InputError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Io(e) => write!(f, "I/O error: {}", e),
+            InputError::Parse { source, input } => {
+                write!(f, "Parse error: {} (input: {:?})", source, input)
+            }
+            InputError::Eof => write!(f, "EOF encountered"),
+            InputError::AttemptsExhausted(n) => write!(f, "No valid input after {} attempts", n),
+            InputError::Invalid(msg) => write!(f, "Invalid input: {}", msg),
+            InputError::Timeout => write!(f, "Timed out waiting for input"),
+            InputError::Cancelled => write!(f, "Read cancelled"),
+            InputError::Interrupted => write!(f, "Read interrupted"),
+            InputError::TooLong(n) => write!(f, "Line longer than {} bytes", n),
+        }
+    }
+}
+
+/// The wrapped `io::Error` or parse error is the [`source`](std::error::Error::source)
+/// of `Io` and `Parse` errors, so error reporters can walk the whole chain.
+impl<E: std::error::Error + 'static> std::error::Error for InputError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Io(e) => Some(e),
+            InputError::Parse { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Compares errors variant by variant; `Io` errors are equal when their
+/// [`kind`](io::Error::kind)s are, since `io::Error` itself has no equality.
+impl<E: PartialEq> PartialEq for InputError<E> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (InputError::Io(a), InputError::Io(b)) => a.kind() == b.kind(),
+            (
+                InputError::Parse { source, input },
+                InputError::Parse {
+                    source: other_source,
+                    input: other_input,
+                },
+            ) => source == other_source && input == other_input,
+            (InputError::Eof, InputError::Eof) => true,
+            (InputError::AttemptsExhausted(a), InputError::AttemptsExhausted(b)) => a == b,
+            (InputError::Invalid(a), InputError::Invalid(b)) => a == b,
+            (InputError::Timeout, InputError::Timeout) => true,
+            (InputError::Cancelled, InputError::Cancelled) => true,
+            (InputError::Interrupted, InputError::Interrupted) => true,
+            (InputError::TooLong(a), InputError::TooLong(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// Wraps an I/O error as `Io`, except that a wrapped
+/// `InputError::<Infallible>::Interrupted`, as a reader reports Ctrl-C with,
+/// becomes `Interrupted` again.
+impl<E> From<io::Error> for InputError<E> {
+    fn from(err: io::Error) -> Self {
+        match err
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<InputError<Infallible>>())
+        {
+            Some(InputError::Interrupted) => InputError::Interrupted,
+            _ => InputError::Io(err),
+        }
+    }
+}
+
+/// Lets functions returning `io::Result` use `?` on reads.
+///
+/// `Io` errors are unwrapped. `Eof` becomes `UnexpectedEof`, `Timeout` becomes
+/// `TimedOut`, `Parse`, `Invalid`, `AttemptsExhausted`, and `TooLong` become
+/// `InvalidData`, and `Cancelled` and `Interrupted` become `Other` (not
+/// `ErrorKind::Interrupted`, which readers treat as "try again"); these wrap
+/// the original `InputError`, which can be recovered with `io::Error::downcast`.
+impl<E> From<InputError<E>> for io::Error
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    fn from(err: InputError<E>) -> Self {
+        let kind = match err {
+            InputError::Io(e) => return e,
+            InputError::Eof => io::ErrorKind::UnexpectedEof,
+            InputError::Timeout => io::ErrorKind::TimedOut,
+            InputError::Parse { .. }
+            | InputError::Invalid(_)
+            | InputError::AttemptsExhausted(_)
+            | InputError::TooLong(_) => io::ErrorKind::InvalidData,
+            InputError::Cancelled | InputError::Interrupted => io::ErrorKind::Other,
+        };
+        io::Error::new(kind, err)
+    }
+}
+
+impl<E> InputError<E> {
+    /// Returns `true` if the input ended before a line was read.
+    pub fn is_eof(&self) -> bool {
+        matches!(self, InputError::Eof)
+    }
+
+    /// Returns `true` if the line failed to parse.
+    pub fn is_parse(&self) -> bool {
+        matches!(self, InputError::Parse { .. })
+    }
+
+    /// Returns `true` if reading failed with an I/O error.
+    pub fn is_io(&self) -> bool {
+        matches!(self, InputError::Io(_))
+    }
+
+    /// Returns the I/O error, if this is an `Io` error.
+    pub fn as_io(&self) -> Option<&io::Error> {
+        match self {
+            InputError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to the parse error, if this is a `Parse` error.
+    ///
+    /// # Usage:
+    /// ```
+    /// use input_macro::read_input_from;
+    ///
+    /// let err = read_input_from::<_, i8>(&mut "x\n".as_bytes(), None).unwrap_err();
+    /// let source: &dyn std::error::Error = err.as_parse().unwrap();
+    /// assert_eq!(source.to_string(), "invalid digit found in string");
+    /// ```
+    pub fn as_parse(&self) -> Option<&E> {
+        match self {
+            InputError::Parse { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+
+    /// Returns the parse error, if this is a `Parse` error.
+    ///
+    /// # Usage:
+    /// ```
+    /// use input_macro::read_input_from;
+    ///
+    /// let err = read_input_from::<_, u8>(&mut "300\n".as_bytes(), None).unwrap_err();
+    /// assert!(err.is_parse());
+    /// assert_eq!(err.into_parse().unwrap().to_string(), "number too large to fit in target type");
+    /// ```
+    pub fn into_parse(self) -> Option<E> {
+        match self {
+            InputError::Parse { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+
+    /// Converts the parse error with `f`, leaving the other variants unchanged.
+    pub fn map_parse<F>(self, f: impl FnOnce(E) -> F) -> InputError<F> {
+        match self {
+            InputError::Io(e) => InputError::Io(e),
+            InputError::Parse { source, input } => InputError::Parse {
+                source: f(source),
+                input,
+            },
+            InputError::Eof => InputError::Eof,
+            InputError::AttemptsExhausted(n) => InputError::AttemptsExhausted(n),
+            InputError::Invalid(msg) => InputError::Invalid(msg),
+            InputError::Timeout => InputError::Timeout,
+            InputError::Cancelled => InputError::Cancelled,
+            InputError::Interrupted => InputError::Interrupted,
+            InputError::TooLong(n) => InputError::TooLong(n),
+        }
+    }
+
+    /// Boxes the parse error, so reads of different types can share one
+    /// error type.
+    ///
+    /// `InputError<E>` cannot convert into `InputError<BoxError>` through
+    /// `From` (the impl would overlap the standard `From<T> for T`), so use
+    /// this with `map_err`, or return [`BoxError`] itself, which any
+    /// `InputError` with a `Send + Sync` parse error converts into with `?`.
+    ///
+    /// # Usage:
+    /// ```
+    /// use input_macro::{read_input_from, BoxError, InputError};
+    ///
+    /// fn read_entry(reader: &mut &[u8]) -> Result<(String, u32, f64), InputError<BoxError>> {
+    ///     let name: String = read_input_from(reader, None).map_err(InputError::boxed)?;
+    ///     let qty: u32 = read_input_from(reader, None).map_err(InputError::boxed)?;
+    ///     let price: f64 = read_input_from(reader, None).map_err(InputError::boxed)?;
+    ///     Ok((name, qty, price))
+    /// }
+    ///
+    /// let err = read_entry(&mut "bolt\nten\n".as_bytes()).unwrap_err();
+    /// assert!(err.is_parse());
+    /// ```
+    pub fn boxed(self) -> InputError<BoxError>
+    where
+        E: Into<BoxError>,
+    {
+        self.map_parse(Into::into)
+    }
+
+    /// Creates a `Parse` error for `input`.
+    pub(crate) fn parse(source: E, input: &str) -> Self {
+        InputError::Parse {
+            source,
+            input: input.to_string(),
+        }
+    }
+}
+
+/// A boxed error used where tokens of different types share one error type (see [`scan!`]).
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A failure on one token of a line holding several values.
+#[derive(Debug)]
+pub enum TokenError<E> {
+    /// The token at `index` (zero-based) failed to parse.
+    Invalid {
+        index: usize,
+        token: String,
+        error: E,
+    },
+    /// The line ended before the token at `index`.
+    Missing { index: usize },
+    /// The line has more tokens than expected; `index` is the first extra one.
+    Unexpected { index: usize, token: String },
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for TokenError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenError::Invalid {
+                index,
+                token,
+                error,
+            } => {
+                write!(f, "token {} ({:?}): {}", index, token, error)
+            }
+            TokenError::Missing { index } => write!(f, "missing token {}", index),
+            TokenError::Unexpected { index, token } => {
+                write!(f, "unexpected token {} ({:?})", index, token)
+            }
+        }
+    }
+}
+
+impl<E: std::fmt::Display + std::fmt::Debug> std::error::Error for TokenError<E> {}
+
+/// Prints the optional prompt (flushing stdout), reads one line into `buf`,
+/// and returns it without its line ending.
+/// Returns `Err(InputError::Eof)` if nothing was left to read.
+pub(crate) fn read_trimmed_line<'a, R, E>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    buf: &'a mut String,
+) -> Result<&'a str, InputError<E>>
+where
+    R: BufRead + ?Sized,
+{
+    read_raw_line(reader, prompt, buf).map(strip_line_ending)
+}
+
+/// Like [`read_trimmed_line`], but keeps the line ending.
+pub(crate) fn read_raw_line<'a, R, E>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    buf: &'a mut String,
+) -> Result<&'a str, InputError<E>>
+where
+    R: BufRead + ?Sized,
+{
+    if let Some(prompt_args) = prompt {
+        write_prompt(prompt_args).map_err(InputError::Io)?;
+    }
+
+    let bytes_read = limits::read_line_limited(reader, buf, limits().max_line_len);
+    #[cfg(feature = "tracing")]
+    if let Err(err) = &bytes_read {
+        trace::error(err);
+    }
+
+    // If 0, that's EOF — return Eof error
+    if bytes_read? == 0 {
+        #[cfg(feature = "tracing")]
+        trace::error(&InputError::<E>::Eof);
+        return Err(InputError::Eof);
+    }
+
+    Ok(buf)
+}
+
+/// Prints a prompt to the thread's prompt output if one is set, otherwise to
+/// the [`PromptTarget`] when the [`PromptVisibility`] allows it.
+/// The prompt gets the [prefix](set_prompt_prefix) and
+/// [suffix](set_prompt_suffix), and is drawn by the [`Theme`] in effect.
+pub(crate) fn write_prompt(prompt: Arguments<'_>) -> io::Result<()> {
+    write_hinted_prompt(Some(prompt), None)
+}
+
+/// Like [`write_prompt`], but follows the prompt, if any, with `hint`, the
+/// drawn hint for its default value; the suffix goes before the hint.
+pub(crate) fn write_hinted_prompt(
+    prompt: Option<Arguments<'_>>,
+    hint: Option<&dyn std::fmt::Display>,
+) -> io::Result<()> {
+    let prompt = prompt.map(source::affixed).unwrap_or_default();
+    let prompt = format_args!("{}{}", prompt, hint.unwrap_or(&""));
+    #[cfg(feature = "tracing")]
+    trace::prompt(prompt);
+    write_drawn(format_args!("{}", theme::prompt(&prompt)))
+}
+
+/// Like [`write_prompt`], but for the crate's own prompt text, such as a
+/// menu's `Choice [1-3]: `, which gets no prefix or suffix.
+pub(crate) fn write_own_prompt(text: Arguments<'_>) -> io::Result<()> {
+    #[cfg(feature = "tracing")]
+    trace::prompt(text);
+    write_drawn(format_args!("{}", theme::prompt(&text)))
+}
+
+/// Draws `prompt` as [`write_prompt`] would, for a writer of the caller's
+/// own, and records it in the transcript as written.
+pub(crate) fn draw_prompt(prompt: Arguments<'_>) -> String {
+    let prompt = source::affixed(prompt);
+    #[cfg(feature = "tracing")]
+    trace::prompt(format_args!("{}", prompt));
+    let drawn = theme::prompt(&prompt);
+    let _ = transcript::record_prompts(&mut io::sink(), |out| out.write_all(drawn.as_bytes()));
+    drawn
+}
+
+/// Prints a prompt followed by a newline.
+pub(crate) fn write_prompt_line(line: Arguments<'_>) -> io::Result<()> {
+    let line = source::affixed(line);
+    #[cfg(feature = "tracing")]
+    trace::prompt(format_args!("{}", line));
+    write_drawn(format_args!("{}\n", theme::prompt(&line)))
+}
+
+/// Prints a message shown before a read is retried, drawn by the [`Theme`]
+/// in effect, followed by a newline.
+pub(crate) fn write_error(message: &dyn std::fmt::Display) -> io::Result<()> {
+    write_drawn(format_args!("{}\n", theme::error(message)))
+}
+
+/// Prints option `number` of a menu, drawn by the [`Theme`] in effect,
+/// followed by a newline.
+pub(crate) fn write_menu_item(number: usize, option: &dyn std::fmt::Display) -> io::Result<()> {
+    write_drawn(format_args!("{}\n", theme::menu_item(number, option)))
+}
+
+/// Prints already drawn prompt text where [`write_prompt`] would.
+fn write_drawn(text: Arguments<'_>) -> io::Result<()> {
+    source::with_prompt_output(|out| {
+        out.write_fmt(text)?;
+        // Always flush so the user sees the prompt immediately
+        out.flush()
+    })
+}
+
+/// Prints a prompt on its own line; used by [`inputln!`].
+#[doc(hidden)]
+pub fn __prompt_line(prompt: Arguments<'_>) {
+    write_prompt_line(prompt).unwrap();
+}
+
+/// Parses `input` with `FromStr`, keeping it in the error on failure.
+pub(crate) fn parse_line<T: FromStr>(input: &str) -> Result<T, InputError<T::Err>> {
+    let result = input
+        .parse()
+        .map_err(|source| InputError::parse(source, input));
+    #[cfg(feature = "tracing")]
+    if let Err(err) = &result {
+        trace::error(err);
+    }
+    result
+}
+
+/// Strips the trailing `\r`/`\n` characters left by `read_line`.
+pub(crate) fn strip_line_ending(line: &str) -> &str {
+    line.trim_end_matches(['\r', '\n'].as_ref())
+}
+
+/// A single function that:
+/// 1. Optionally prints a prompt (and flushes).
+/// 2. Reads one line from the provided `BufRead`.
+/// 3. Returns `Err(InputError::Eof)` if EOF is reached.
+/// 4. Parses into type `T`, returning `Err(InputError::Parse)` on failure.
+/// 5. Returns `Err(InputError::Io)` on I/O failure.
+pub fn read_input_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let mut input = String::new();
+    let trimmed = read_trimmed_line(reader, prompt, &mut input)?;
+    parse_line(trimmed)
+}
+
+/// Like [`read_input_from`], but writes the prompt to `writer` (flushing it)
+/// instead of stdout, for UIs that do not prompt on stdout.
+///
+/// The prompt still gets the [prefix and suffix](set_prompt_prefix), is drawn
+/// by the [`Theme`] in effect, and is recorded by a [`Transcript`]; only
+/// where it goes changes, so [`set_prompt_output`] and the
+/// [visibility](set_prompt_visibility) do not apply.
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_input_to;
+///
+/// let mut reader = std::io::Cursor::new("42\n");
+/// let mut prompts = Vec::new();
+/// let n: u8 = read_input_to(&mut reader, &mut prompts, Some(format_args!("n: "))).unwrap();
+/// assert_eq!((n, prompts.as_slice()), (42, &b"n: "[..]));
+/// ```
+pub fn read_input_to<R, W, T>(
+    reader: &mut R,
+    writer: &mut W,
+    prompt: Option<Arguments<'_>>,
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    W: io::Write + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    if let Some(prompt_args) = prompt {
+        writer
+            .write_all(draw_prompt(prompt_args).as_bytes())
+            .map_err(InputError::Io)?;
+        writer.flush().map_err(InputError::Io)?;
+    }
+    read_input_from(reader, None)
+}
+
+/// Like [`read_input_from`], but reads the line into `buf`, which is cleared
+/// first, instead of a fresh `String`.
+///
+/// Passing the same buffer on every call lets loops over many lines reuse one
+/// allocation; see also [`InputReader`].
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_input_into;
+///
+/// let mut reader = std::io::Cursor::new("1\n2\n3\n");
+/// let mut buf = String::new();
+/// let mut sum = 0;
+/// while let Ok(n) = read_input_into::<_, u32>(&mut reader, None, &mut buf) {
+///     sum += n;
+/// }
+/// assert_eq!(sum, 6);
+/// ```
+pub fn read_input_into<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    buf: &mut String,
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    buf.clear();
+    let trimmed = read_trimmed_line(reader, prompt, buf)?;
+    parse_line(trimmed)
+}
+
+/// Like [`read_input_from`], but a record ends at `delim` instead of at a
+/// line ending, e.g. `b"\0"` for `find -print0` output, `b";"`, or `b"\r"`.
+///
+/// The delimiter is removed before parsing; a final record with no delimiter
+/// after it is still returned. An empty `delim` is rejected with
+/// `Err(InputError::Invalid)`.
+///
+/// # Usage:
+/// ```
+/// use input_macro::{read_until_from, InputError};
+///
+/// let mut reader = std::io::Cursor::new("a.txt\0b c.txt\0");
+/// let mut files = Vec::new();
+/// loop {
+///     match read_until_from::<_, String>(&mut reader, None, b"\0") {
+///         Ok(file) => files.push(file),
+///         Err(InputError::Eof) => break,
+///         Err(err) => panic!("{}", err),
+///     }
+/// }
+/// assert_eq!(files, ["a.txt", "b c.txt"]);
+/// ```
+pub fn read_until_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    delim: &[u8],
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    if delim.is_empty() {
+        return Err(InputError::Invalid("empty record delimiter".to_string()));
+    }
+    if let Some(prompt_args) = prompt {
+        write_prompt(prompt_args).map_err(InputError::Io)?;
+    }
+
+    let mut record = Vec::new();
+    if limits::read_until_limited(reader, &mut record, delim, limits().max_line_len)? == 0 {
+        return Err(InputError::Eof);
+    }
+    if record.ends_with(delim) {
+        record.truncate(record.len() - delim.len());
+    }
+
+    let record = String::from_utf8(record)
+        .map_err(|e| InputError::Io(io::Error::new(io::ErrorKind::InvalidData, e)))?;
+    parse_line(&record)
+}
+
+/// A convenience wrapper around [`read_until_from`] that reads from stdin.
+#[track_caller]
+pub fn read_until<T>(
+    prompt: Option<Arguments<'_>>,
+    delim: &[u8],
+) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    __with_stdin(|reader| read_until_from(reader, prompt, delim))
+}
+
+/// Like [`read_input_from`], but converts the line with `TryFrom<&str>`
+/// instead of `FromStr`, for types that only implement the former.
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_try_from_from;
+///
+/// struct Tag(String);
+///
+/// impl TryFrom<&str> for Tag {
+///     type Error = &'static str;
+///     fn try_from(s: &str) -> Result<Self, Self::Error> {
+///         match s.strip_prefix('#') {
+///             Some(name) if !name.is_empty() => Ok(Tag(name.to_string())),
+///             _ => Err("tags start with #"),
+///         }
+///     }
+/// }
+///
+/// let mut reader = std::io::Cursor::new("#rust\n");
+/// let tag: Tag = read_try_from_from(&mut reader, None).unwrap();
+/// assert_eq!(tag.0, "rust");
+/// ```
+pub fn read_try_from_from<R, T, E>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<T, InputError<E>>
+where
+    R: BufRead + ?Sized,
+    T: for<'a> TryFrom<&'a str, Error = E>,
+{
+    read_with_from(reader, prompt, |line| T::try_from(line))
+}
+
+/// A convenience wrapper around [`read_try_from_from`] that reads from stdin.
+#[track_caller]
+pub fn read_try_from<T, E>(prompt: Option<Arguments<'_>>) -> Result<T, Located<InputError<E>>>
+where
+    T: for<'a> TryFrom<&'a str, Error = E>,
+{
+    __with_stdin(|reader| read_try_from_from(reader, prompt))
+}
+
+/// Reads one line and parses every token into `T`.
+///
+/// Tokens are separated by whitespace, or by `delimiter` if one is given.
+/// An empty line yields an empty `Vec`. A token that fails to parse is
+/// reported as `InputError::Parse` holding a `TokenError::Invalid` with its index.
+pub fn read_vec_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    delimiter: Option<char>,
+) -> Result<Vec<T>, InputError<TokenError<T::Err>>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let mut input = String::new();
+    let line = read_trimmed_line(reader, prompt, &mut input)?;
+    parse_tokens(line, delimiter, false).map_err(|source| InputError::parse(source, line))
+}
+
+/// A convenience wrapper around [`read_vec_from`] that reads from stdin.
+#[track_caller]
+pub fn read_vec<T>(
+    prompt: Option<Arguments<'_>>,
+    delimiter: Option<char>,
+) -> Result<Vec<T>, StdinError<TokenError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    __with_stdin(|reader| read_vec_from(reader, prompt, delimiter))
+}
+
+/// Reads one line, splits it on `delim`, and parses every field into `T`.
+///
+/// With `trim`, whitespace around each field is removed first, so
+/// `"1, 2, 3"` splits on `','` into `1`, `2`, and `3`. An empty line yields
+/// an empty `Vec`; empty fields are kept, and fail to parse for most types.
+/// A field that fails to parse is reported as
+/// `InputError::Parse` holding a `TokenError::Invalid` with its index.
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_split_from;
+///
+/// let mut reader = std::io::Cursor::new("1, 2,3\n");
+/// let values: Vec<u8> = read_split_from(&mut reader, None, ',', true).unwrap();
+/// assert_eq!(values, [1, 2, 3]);
+/// ```
+pub fn read_split_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    delim: char,
+    trim: bool,
+) -> Result<Vec<T>, InputError<TokenError<T::Err>>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let mut input = String::new();
+    let line = read_trimmed_line(reader, prompt, &mut input)?;
+    parse_tokens(line, Some(delim), trim).map_err(|source| InputError::parse(source, line))
+}
+
+/// A convenience wrapper around [`read_split_from`] that reads from stdin.
+#[track_caller]
+pub fn read_split<T>(
+    prompt: Option<Arguments<'_>>,
+    delim: char,
+    trim: bool,
+) -> Result<Vec<T>, StdinError<TokenError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    __with_stdin(|reader| read_split_from(reader, prompt, delim, trim))
+}
+
+pub(crate) fn parse_tokens<T: FromStr>(
+    line: &str,
+    delimiter: Option<char>,
+    trim: bool,
+) -> Result<Vec<T>, TokenError<T::Err>> {
+    parse_tokens_with(line, delimiter, trim, str::parse)
+}
+
+/// Like [`parse_tokens`], but parses each token with `parse`.
+pub(crate) fn parse_tokens_with<T, E>(
+    line: &str,
+    delimiter: Option<char>,
+    trim: bool,
+    parse: impl Fn(&str) -> Result<T, E>,
+) -> Result<Vec<T>, TokenError<E>> {
+    let tokens: Box<dyn Iterator<Item = &str>> = match delimiter {
+        None => Box::new(line.split_whitespace()),
+        Some(_) if line.is_empty() => return Ok(Vec::new()),
+        Some(d) => Box::new(line.split(d)),
+    };
+    tokens
+        .map(|token| if trim { token.trim() } else { token })
+        .enumerate()
+        .map(|(index, token)| {
+            parse(token).map_err(|error| TokenError::Invalid {
+                index,
+                token: token.to_string(),
+                error,
+            })
+        })
+        .collect()
+}
+
+/// Reads one line and parses it with `parser` instead of `FromStr`.
+///
+/// The line is passed without its line ending. Prompting, EOF, and I/O errors
+/// behave as in [`read_input_from`]; an `Err` from `parser` becomes
+/// `InputError::Parse`. This suits input with several accepted formats or
+/// parsing that depends on context.
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_with_from;
+///
+/// // Accepts hex with a `0x` prefix as well as decimal
+/// let parse = |line: &str| match line.strip_prefix("0x") {
+///     Some(hex) => u32::from_str_radix(hex, 16),
+///     None => line.parse(),
+/// };
+/// let mut reader = std::io::Cursor::new("0xff\n");
+/// assert_eq!(read_with_from(&mut reader, None, parse).unwrap(), 255);
+/// ```
+pub fn read_with_from<R, T, E, F>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    parser: F,
+) -> Result<T, InputError<E>>
+where
+    R: BufRead + ?Sized,
+    F: FnOnce(&str) -> Result<T, E>,
+{
+    let mut input = String::new();
+    let line = read_trimmed_line(reader, prompt, &mut input)?;
+    parser(line).map_err(|source| InputError::parse(source, line))
+}
+
+/// A convenience wrapper around [`read_with_from`] that reads from stdin.
+#[track_caller]
+pub fn read_with<T, E, F>(
+    prompt: Option<Arguments<'_>>,
+    parser: F,
+) -> Result<T, Located<InputError<E>>>
+where
+    F: FnOnce(&str) -> Result<T, E>,
+{
+    __with_stdin(|reader| read_with_from(reader, prompt, parser))
+}
+
+/// Reads one line and passes it, without its line ending, to `f`, returning
+/// what `f` returns.
+///
+/// Unlike [`read_with_from`], `f` cannot fail and need not produce a parsed
+/// value, so callers that only need a transient view can slice or inspect
+/// the line without copying it into a `String` of their own.
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_str_with_from;
+///
+/// let mut reader = std::io::Cursor::new("GET /index.html HTTP/1.1\n");
+/// let is_get = read_str_with_from(&mut reader, None, |line| line.starts_with("GET ")).unwrap();
+/// assert!(is_get);
+/// ```
+pub fn read_str_with_from<R, U, F>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    f: F,
+) -> Result<U, InputError<Infallible>>
+where
+    R: BufRead + ?Sized,
+    F: FnOnce(&str) -> U,
+{
+    let mut input = String::new();
+    read_trimmed_line(reader, prompt, &mut input).map(f)
+}
+
+/// A convenience wrapper around [`read_str_with_from`] that reads from stdin.
+#[track_caller]
+pub fn read_str_with<U, F>(
+    prompt: Option<Arguments<'_>>,
+    f: F,
+) -> Result<U, Located<InputError<Infallible>>>
+where
+    F: FnOnce(&str) -> U,
+{
+    __with_stdin(|reader| read_str_with_from(reader, prompt, f))
+}
+
+/// Parses the next token for [`scan!`], advancing `index`.
+#[doc(hidden)]
+pub fn __scan_token<'a, T, I>(tokens: &mut I, index: &mut usize) -> Result<T, TokenError<BoxError>>
+where
+    I: Iterator<Item = &'a str>,
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    let at = *index;
+    *index += 1;
+    let token = tokens.next().ok_or(TokenError::Missing { index: at })?;
+    token.parse::<T>().map_err(|e| TokenError::Invalid {
+        index: at,
+        token: token.to_string(),
+        error: e.into(),
+    })
+}
+
+/// Fails if [`scan!`] left any tokens unconsumed.
+#[doc(hidden)]
+pub fn __scan_finish<'a, I>(tokens: &mut I, index: usize) -> Result<(), TokenError<BoxError>>
+where
+    I: Iterator<Item = &'a str>,
+{
+    match tokens.next() {
+        Some(token) => Err(TokenError::Unexpected {
+            index,
+            token: token.to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Like [`read_input_from`], but runs `validator` on the parsed value.
+///
+/// A rejected value is returned as `Err(InputError::Invalid)` with the
+/// validator's message.
+pub fn read_input_validated_from<R, T, F>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    mut validator: F,
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+    F: FnMut(&T) -> Result<(), String>,
+{
+    let value = read_input_from(reader, prompt)?;
+    validator(&value).map_err(InputError::Invalid)?;
+    Ok(value)
+}
+
+/// A convenience wrapper around [`read_input_validated_from`] that reads from stdin.
+#[track_caller]
+pub fn read_input_validated<T, F>(
+    prompt: Option<Arguments<'_>>,
+    validator: F,
+) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+    F: FnMut(&T) -> Result<(), String>,
+{
+    __with_stdin(|reader| read_input_validated_from(reader, prompt, validator))
+}
+
+/// Like [`read_input_from`], but rejects values outside `range`.
+///
+/// A rejected value is returned as `Err(InputError::Invalid)` with a message
+/// naming the allowed range, e.g. `"42 is outside 1..=10"`.
+pub fn read_in_range_from<R, T, B>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    range: B,
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr + PartialOrd + std::fmt::Display,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+    B: RangeBounds<T>,
+{
+    read_input_validated_from(reader, prompt, |value: &T| {
+        if range.contains(value) {
+            Ok(())
+        } else {
+            Err(format!("{} is outside {}", value, describe_range(&range)))
+        }
+    })
+}
+
+/// A convenience wrapper around [`read_in_range_from`] that reads from stdin.
+#[track_caller]
+pub fn read_in_range<T, B>(
+    prompt: Option<Arguments<'_>>,
+    range: B,
+) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromStr + PartialOrd + std::fmt::Display,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+    B: RangeBounds<T>,
+{
+    __with_stdin(|reader| read_in_range_from(reader, prompt, range))
+}
+
+/// Renders a range in Rust syntax, e.g. `1..=10` or `..5`.
+pub(crate) fn describe_range<T: std::fmt::Display, B: RangeBounds<T>>(range: &B) -> String {
+    let start = match range.start_bound() {
+        Bound::Included(v) => v.to_string(),
+        Bound::Excluded(v) => format!("{} (exclusive)", v),
+        Bound::Unbounded => String::new(),
+    };
+    match range.end_bound() {
+        Bound::Included(v) => format!("{}..={}", start, v),
+        Bound::Excluded(v) => format!("{}..{}", start, v),
+        Bound::Unbounded => format!("{}..", start),
+    }
+}
+
+/// Like [`read_input_from`], but an empty (or whitespace-only) line yields `default`.
+///
+/// If `show_default` is set, a hint such as `"[default: <value>] "`, drawn by
+/// the [`Theme`] in effect, is printed after the prompt.
+pub fn read_input_or_default_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    default: T,
+    show_default: bool,
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr + std::fmt::Display,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let mut input = String::new();
+    let line = if show_default {
+        let hint = theme::default_hint(&default);
+        write_hinted_prompt(prompt, Some(&hint)).map_err(InputError::Io)?;
+        read_trimmed_line(reader, None, &mut input)?
+    } else {
+        read_trimmed_line(reader, prompt, &mut input)?
+    };
+    if line.trim().is_empty() {
+        return Ok(default);
+    }
+    parse_line(line)
+}
+
+/// A convenience wrapper around [`read_input_or_default_from`] that reads from stdin.
+#[track_caller]
+pub fn read_input_or_default<T>(
+    prompt: Option<Arguments<'_>>,
+    default: T,
+    show_default: bool,
+) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromStr + std::fmt::Display,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    __with_stdin(|reader| read_input_or_default_from(reader, prompt, default, show_default))
+}
+
+/// Like [`read_input_from`], but an empty (or whitespace-only) line yields `Ok(None)`
+/// instead of being parsed.
+pub fn read_optional_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<Option<T>, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let mut input = String::new();
+    let line = read_trimmed_line(reader, prompt, &mut input)?;
+    if line.trim().is_empty() {
+        return Ok(None);
+    }
+    parse_line(line).map(Some)
+}
+
+/// A convenience wrapper around [`read_optional_from`] that reads from stdin.
+#[track_caller]
+pub fn read_optional<T>(
+    prompt: Option<Arguments<'_>>,
+) -> Result<Option<T>, Located<InputError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    __with_stdin(|reader| read_optional_from(reader, prompt))
+}
+
+/// Returns an iterator that parses each remaining line of `reader` into `T`.
+///
+/// Every line yields its own `Ok` or `Err`, so parse failures do not stop the
+/// iteration; it ends at EOF.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::lines_as;
+///
+/// let total: i64 = lines_as::<_, i64>(std::io::stdin().lock())
+///     .filter_map(Result::ok)
+///     .sum();
+/// ```
+pub fn lines_as<R, T>(mut reader: R) -> impl Iterator<Item = Result<T, InputError<T::Err>>>
+where
+    R: BufRead,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    std::iter::from_fn(move || match read_input_from(&mut reader, None) {
+        Err(InputError::Eof) => None,
+        result => Some(result),
+    })
 }
 
-impl<E: std::fmt::Display + std::fmt::Debug> std::fmt::Display for InputError<E> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            InputError::Io(e) => write!(f, "I/O error: {}", e),
-            InputError::Parse(e) => write!(f, "Parse error: {}", e),
-            InputError::Eof => write!(f, "EOF encountered"),
+/// Asks a yes/no question, re-prompting until the answer is understood.
+///
+/// Accepts `y`, `yes`, `n`, and `no` in any case. An empty line yields
+/// `default` if one is given; anything else prints a hint and asks again.
+///
+/// # Usage:
+/// ```
+/// use input_macro::confirm_from;
+///
+/// // "maybe" is not understood, so the question is asked again
+/// let mut reader = std::io::Cursor::new("maybe\nYes\n\n");
+/// let prompt = Some(format_args!("Delete? [y/N] "));
+/// assert!(confirm_from(&mut reader, prompt, Some(false)).unwrap());
+/// // An empty answer takes the default
+/// assert!(!confirm_from(&mut reader, prompt, Some(false)).unwrap());
+/// ```
+pub fn confirm_from<R: BufRead + ?Sized>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    default: Option<bool>,
+) -> Result<bool, InputError<Infallible>> {
+    loop {
+        let mut input = String::new();
+        let line = read_trimmed_line(reader, prompt, &mut input)?;
+        let answer = match parse_yes_no(line) {
+            None if line.trim().is_empty() => default,
+            answer => answer,
+        };
+        match answer {
+            Some(answer) => return Ok(answer),
+            None => write_error(&"Please answer yes or no.").map_err(InputError::Io)?,
         }
     }
 }
 
-impl<E: std::fmt::Display + std::fmt::Debug> std::error::Error for InputError<E> {}
+/// A convenience wrapper around [`confirm_from`] that reads from stdin.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::confirm;
+///
+/// if confirm(Some(format_args!("Delete? [y/N] ")), Some(false)).unwrap() {
+///     println!("Deleted.");
+/// }
+/// ```
+#[track_caller]
+pub fn confirm(
+    prompt: Option<Arguments<'_>>,
+    default: Option<bool>,
+) -> Result<bool, Located<InputError<Infallible>>> {
+    __with_stdin(|reader| confirm_from(reader, prompt, default))
+}
+
+/// Parses `y`/`yes`/`n`/`no`, ignoring case and surrounding whitespace.
+fn parse_yes_no(answer: &str) -> Option<bool> {
+    match answer.trim().to_ascii_lowercase().as_str() {
+        "y" | "yes" => Some(true),
+        "n" | "no" => Some(false),
+        _ => None,
+    }
+}
 
-/// A single function that:
-/// 1. Optionally prints a prompt (and flushes).
-/// 2. Reads one line from the provided `BufRead`.
-/// 3. Returns `Err(InputError::Eof)` if EOF is reached.
-/// 4. Parses into type `T`, returning `Err(InputError::Parse)` on failure.
-/// 5. Returns `Err(InputError::Io)` on I/O failure.
-pub fn read_input_from<R, T>(
+/// Reads the default answer from a `[Y/n]` or `[y/N]` hint; used by [`confirm!`].
+#[doc(hidden)]
+pub fn __confirm_default_from_hint(prompt: &str) -> Option<bool> {
+    if prompt.contains("[Y/n]") {
+        Some(true)
+    } else if prompt.contains("[y/N]") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Like [`read_input_from`], but re-prompts whenever the line fails to parse.
+///
+/// Makes up to `max_attempts` attempts (at least one), printing `retry_message`
+/// on its own line after each failure that another attempt follows, if one is
+/// given. Returns
+/// `Err(InputError::AttemptsExhausted)` once every attempt has failed;
+/// I/O errors and EOF are returned immediately.
+pub fn read_until_valid_from<R, T>(
     reader: &mut R,
     prompt: Option<Arguments<'_>>,
+    max_attempts: usize,
+    retry_message: Option<Arguments<'_>>,
 ) -> Result<T, InputError<T::Err>>
 where
-    R: BufRead,
+    R: BufRead + ?Sized,
     T: FromStr,
     T::Err: std::fmt::Display + std::fmt::Debug,
 {
-    if let Some(prompt_args) = prompt {
-        print!("{}", prompt_args);
-        // Always flush so the user sees the prompt immediately
-        io::stdout().flush().map_err(InputError::Io)?;
-    }
-
-    let mut input = String::new();
-    let bytes_read = reader.read_line(&mut input).map_err(InputError::Io)?;
-
-    // If 0, that's EOF — return Eof error
-    if bytes_read == 0 {
-        return Err(InputError::Eof);
+    let max_attempts = max_attempts.max(1);
+    for attempt in 1..=max_attempts {
+        match read_input_from(reader, prompt) {
+            Err(InputError::Parse { .. }) => {
+                if let Some(message) = retry_message.filter(|_| attempt < max_attempts) {
+                    write_error(&message).map_err(InputError::Io)?;
+                }
+            }
+            result => return result,
+        }
     }
+    Err(InputError::AttemptsExhausted(max_attempts))
+}
 
-    let trimmed = input.trim_end_matches(['\r', '\n'].as_ref());
-    trimmed.parse::<T>().map_err(InputError::Parse)
+/// A convenience wrapper around [`read_until_valid_from`] that reads from stdin.
+#[track_caller]
+pub fn read_until_valid<T>(
+    prompt: Option<Arguments<'_>>,
+    max_attempts: usize,
+    retry_message: Option<Arguments<'_>>,
+) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    __with_stdin(|reader| read_until_valid_from(reader, prompt, max_attempts, retry_message))
 }
 
 /// A convenience wrapper that reads from stdin (locking it), without printing a prompt.
-pub fn read_input<T>() -> Result<T, InputError<T::Err>>
+#[track_caller]
+pub fn read_input<T>() -> Result<T, Located<InputError<T::Err>>>
 where
     T: FromStr,
     T::Err: std::fmt::Display + std::fmt::Debug,
 {
-    let stdin = io::stdin();
-    let mut locked = stdin.lock();
-    read_input_from(&mut locked, None)
+    __with_stdin(|reader| read_input_from(reader, None))
 }
 
 /// A convenience wrapper that reads from stdin, printing the given prompt first.
-pub fn read_input_with_prompt<T>(prompt: Arguments<'_>) -> Result<T, InputError<T::Err>>
+#[track_caller]
+pub fn read_input_with_prompt<T>(prompt: Arguments<'_>) -> Result<T, Located<InputError<T::Err>>>
 where
     T: FromStr,
     T::Err: std::fmt::Display + std::fmt::Debug,
 {
-    let stdin = io::stdin();
-    let mut locked = stdin.lock();
-    read_input_from(&mut locked, Some(prompt))
+    __with_stdin(|reader| read_input_from(reader, Some(prompt)))
 }
 
 /// A macro that:
 /// - reads **one line** from stdin (as `String` by default),
 /// - returns `Ok(None)` if EOF is encountered (`InputError::Eof`).
 ///
+/// The target type is normally inferred from the binding, but it can also be
+/// given explicitly as the first argument, followed by an optional prompt.
+///
 /// # Usage:
 /// ```no_run
+/// # use input_macro::input;
 /// // No prompt
 /// let text: Option<String> = input!().unwrap();
 ///
@@ -95,83 +1369,241 @@ where
 /// // Formatted prompt
 /// let user = "Alice";
 /// let age: Option<String> = input!("Enter {}'s age: ", user).unwrap();
+///
+/// // Explicit target type
+/// let age = input!(i32, "Enter your age: ").unwrap();
+/// let count = input!(u64).unwrap();
 /// ```
 #[macro_export]
 macro_rules! input {
-    () => {{
-        // If you'd like a different type, just replace <String> below:
-        match $crate::read_input_from(&mut ::std::io::stdin().lock(), None) {
+    (@read $t:ty, $prompt:expr) => {
+        match $crate::__with_stdin(|reader| $crate::read_input_from::<_, $t>(reader, $prompt)) {
             Ok(val) => Ok(Some(val)),
-            Err($crate::InputError::Eof) => Ok(None),
+            Err($crate::Located {
+                error: $crate::InputError::Eof,
+                ..
+            }) => Ok(None),
             Err(err) => Err(err),
         }
-    }};
-    ($($arg:tt)*) => {{
-        match $crate::read_input_from(
-            &mut ::std::io::stdin().lock(),
-            Some(format_args!($($arg)*))
-        ) {
-            Ok(val) => Ok(Some(val)),
-            Err($crate::InputError::Eof) => Ok(None),
-            Err(err) => Err(err),
-        }
-    }};
+    };
+    () => {
+        $crate::input!(@read _, None)
+    };
+    ($fmt:literal $($arg:tt)*) => {
+        $crate::input!(@read _, Some(format_args!($fmt $($arg)*)))
+    };
+    ($t:ty) => {
+        $crate::input!(@read $t, None)
+    };
+    ($t:ty, $($arg:tt)+) => {
+        $crate::input!(@read $t, Some(format_args!($($arg)+)))
+    };
 }
 
 /// A macro that:
-/// - prints the prompt on its own line (with `println!`),
+/// - prints the prompt on its own line,
 /// - then reads one line,
 /// - returns `Ok(None)` on EOF,
-/// - otherwise parses into `String`.
+/// - otherwise parses into `String` (or the explicitly given type).
 ///
 /// # Usage:
 /// ```no_run
+/// # use input_macro::inputln;
 /// let line: Option<String> = inputln!("What's your favorite color?").unwrap();
+/// let lucky = inputln!(u8, "What's your lucky number?").unwrap();
 /// ```
 #[macro_export]
 macro_rules! inputln {
-    () => {{
-        match $crate::read_input_from(&mut ::std::io::stdin().lock(), None) {
+    (@read $t:ty) => {
+        match $crate::__with_stdin(|reader| $crate::read_input_from::<_, $t>(reader, None)) {
             Ok(val) => Ok(Some(val)),
-            Err($crate::InputError::Eof) => Ok(None),
+            Err($crate::Located {
+                error: $crate::InputError::Eof,
+                ..
+            }) => Ok(None),
             Err(err) => Err(err),
         }
+    };
+    (@prompt $($arg:tt)+) => {
+        $crate::__prompt_line(format_args!($($arg)+));
+    };
+    () => {{
+        $crate::inputln!(@read _)
     }};
-    ($($arg:tt)*) => {{
-        println!("{}", format_args!($($arg)*));
-        ::std::io::Write::flush(&mut ::std::io::stdout()).unwrap();
-        match $crate::read_input_from(&mut ::std::io::stdin().lock(), None) {
-            Ok(val) => Ok(Some(val)),
-            Err($crate::InputError::Eof) => Ok(None),
-            Err(err) => Err(err),
-        }
+    ($fmt:literal $($arg:tt)*) => {{
+        $crate::inputln!(@prompt $fmt $($arg)*);
+        $crate::inputln!(@read _)
+    }};
+    ($t:ty) => {{
+        $crate::inputln!(@read $t)
+    }};
+    ($t:ty, $($arg:tt)+) => {{
+        $crate::inputln!(@prompt $($arg)+);
+        $crate::inputln!(@read $t)
     }};
 }
 
 /// A macro that:
 /// - reads one line from stdin,
-/// - tries to parse into `String`,
+/// - tries to parse into `String` (or the explicitly given type),
 /// - **treats EOF as an error** (no `Ok(None)`).
 ///
 /// # Usage:
 /// ```no_run
+/// # use input_macro::input_no_eof;
 /// // No prompt
 /// let line: String = input_no_eof!().unwrap();
 ///
 /// // With prompt
 /// let age: i32 = input_no_eof!("Enter your age: ").unwrap();
+///
+/// // Explicit target type
+/// let height = input_no_eof!(f64, "Enter your height: ").unwrap();
 /// ```
 #[macro_export]
 macro_rules! input_no_eof {
-    () => {{
-        // If you want a different type, change <String> here:
-        $crate::read_input_from(&mut ::std::io::stdin().lock(), None)
-    }};
-    ($($arg:tt)*) => {{
-        $crate::read_input_from(
-            &mut ::std::io::stdin().lock(),
-            Some(format_args!($($arg)*))
-        )
+    (@read $t:ty, $prompt:expr) => {
+        $crate::__with_stdin(|reader| $crate::read_input_from::<_, $t>(reader, $prompt))
+    };
+    () => {
+        $crate::input_no_eof!(@read _, None)
+    };
+    ($fmt:literal $($arg:tt)*) => {
+        $crate::input_no_eof!(@read _, Some(format_args!($fmt $($arg)*)))
+    };
+    ($t:ty) => {
+        $crate::input_no_eof!(@read $t, None)
+    };
+    ($t:ty, $($arg:tt)+) => {
+        $crate::input_no_eof!(@read $t, Some(format_args!($($arg)+)))
+    };
+}
+
+/// A macro that:
+/// - reads one line from stdin,
+/// - splits it on whitespace, or matches it against a pattern, and parses
+///   each field into its annotated type, bound to its name,
+/// - returns the values as a tuple in field order, or `Ok(None)` on EOF.
+///
+/// Without a pattern, the line must hold exactly as many tokens as there are
+/// fields; a missing, extra, or unparsable token is reported as
+/// `InputError::Parse` holding a `TokenError`. With a pattern, each `{}` is a
+/// field and the rest literal text, as in [`read_pattern!`], and a mismatch
+/// is reported as a [`PatternError`]. An optional prompt goes before a `;`.
+///
+/// # Usage:
+/// ```no_run
+/// # use input_macro::scan;
+/// // Reads e.g. "3 4.5"
+/// let (a, b) = scan!(a: i32, b: f64).unwrap().unwrap();
+///
+/// // With prompt
+/// let (x, y) = scan!("Enter a point: "; x: i32, y: i32).unwrap().unwrap();
+///
+/// // With a pattern, reading e.g. "12:30"
+/// let (hour, minute) = scan!("Time: "; "{}:{}", hour: u8, minute: u8).unwrap().unwrap();
+/// ```
+#[macro_export]
+macro_rules! scan {
+    (@read $prompt:expr; $($name:ident : $t:ty),+) => {
+        match $crate::__with_stdin(|reader| $crate::read_with_from(
+            reader,
+            $prompt,
+            |line: &str| -> Result<_, $crate::TokenError<$crate::BoxError>> {
+                let mut tokens = line.split_whitespace();
+                let mut index = 0;
+                $(let $name = $crate::__scan_token::<$t, _>(&mut tokens, &mut index)?;)+
+                $crate::__scan_finish(&mut tokens, index)?;
+                Ok(($($name,)+))
+            },
+        )) {
+            Ok(val) => Ok(Some(val)),
+            Err($crate::Located {
+                error: $crate::InputError::Eof,
+                ..
+            }) => Ok(None),
+            Err(err) => Err(err),
+        }
+    };
+    ($pattern:literal, $($name:ident : $t:ty),+ $(,)?) => {
+        $crate::read_pattern!(@read None; $pattern, $($name: $t),+)
+    };
+    ($fmt:literal $(, $arg:expr)* ; $pattern:literal, $($name:ident : $t:ty),+ $(,)?) => {
+        $crate::read_pattern!(@read Some(format_args!($fmt $(, $arg)*)); $pattern, $($name: $t),+)
+    };
+    ($fmt:literal $(, $arg:expr)* ; $($name:ident : $t:ty),+ $(,)?) => {
+        $crate::scan!(@read Some(format_args!($fmt $(, $arg)*)); $($name: $t),+)
+    };
+    ($($name:ident : $t:ty),+ $(,)?) => {
+        $crate::scan!(@read None; $($name: $t),+)
+    };
+}
+
+/// A macro that:
+/// - reads one line from stdin,
+/// - parses it into the range's element type,
+/// - rejects values outside the range with `InputError::Invalid`,
+/// - returns `Ok(None)` on EOF.
+///
+/// # Usage:
+/// ```no_run
+/// # use input_macro::input_in_range;
+/// let level: Option<u8> = input_in_range!(1..=10, "Pick a level (1-10): ").unwrap();
+/// let ratio: Option<f64> = input_in_range!(0.0..1.0).unwrap();
+/// ```
+#[macro_export]
+macro_rules! input_in_range {
+    (@read $range:expr, $prompt:expr) => {
+        match $crate::__with_stdin(|reader| $crate::read_in_range_from(reader, $prompt, $range)) {
+            Ok(val) => Ok(Some(val)),
+            Err($crate::Located {
+                error: $crate::InputError::Eof,
+                ..
+            }) => Ok(None),
+            Err(err) => Err(err),
+        }
+    };
+    ($range:expr) => {
+        $crate::input_in_range!(@read $range, None)
+    };
+    ($range:expr, $($arg:tt)+) => {
+        $crate::input_in_range!(@read $range, Some(format_args!($($arg)+)))
+    };
+}
+
+/// A macro that:
+/// - asks a yes/no question on stdin (see [`confirm_from`]),
+/// - takes its default from a `[Y/n]` or `[y/N]` hint in the prompt,
+/// - returns `Ok(None)` on EOF.
+///
+/// # Usage:
+/// ```no_run
+/// # use input_macro::confirm;
+/// // Empty input means "no"
+/// let delete: Option<bool> = confirm!("Delete {}? [y/N] ", "notes.txt").unwrap();
+///
+/// // No hint: empty input re-prompts
+/// let sure = confirm!("Are you sure? ").unwrap();
+/// ```
+#[macro_export]
+macro_rules! confirm {
+    (@read $prompt:expr, $default:expr) => {
+        match $crate::__with_stdin(|reader| $crate::confirm_from(reader, $prompt, $default)) {
+            Ok(val) => Ok(Some(val)),
+            Err($crate::Located {
+                error: $crate::InputError::Eof,
+                ..
+            }) => Ok(None),
+            Err(err) => Err(err),
+        }
+    };
+    () => {
+        $crate::confirm!(@read None, None)
+    };
+    ($($arg:tt)+) => {{
+        let prompt = format!($($arg)+);
+        let default = $crate::__confirm_default_from_hint(&prompt);
+        $crate::confirm!(@read Some(format_args!("{}", prompt)), default)
     }};
 }
 
@@ -190,6 +1622,7 @@ mod tests {
 
     /// Test reading a floating-point number
     #[test]
+    #[allow(clippy::approx_constant)]
     fn test_read_input_float() {
         let mut reader = Cursor::new("3.14159\n");
         let res: Result<f64, _> = read_input_from(&mut reader, None);
@@ -217,7 +1650,19 @@ mod tests {
     fn test_read_input_parse_error() {
         let mut reader = Cursor::new("not an int\n");
         let res: Result<i32, _> = read_input_from(&mut reader, None);
-        assert!(matches!(res, Err(InputError::Parse(_))));
+        assert!(matches!(res, Err(InputError::Parse { .. })));
+    }
+
+    /// Parse errors keep the text that failed to parse, and show it.
+    #[test]
+    fn test_parse_error_keeps_input() {
+        let mut reader = Cursor::new("12o\r\n");
+        let err = read_input_from::<_, i32>(&mut reader, None).unwrap_err();
+        assert!(matches!(err, InputError::Parse { ref input, .. } if input == "12o"));
+        assert_eq!(
+            err.to_string(),
+            "Parse error: invalid digit found in string (input: \"12o\")"
+        );
     }
 
     /// Reading a standard string
@@ -261,7 +1706,7 @@ mod tests {
 
         // Read second line: parse error
         let second = read_input_from::<_, i32>(&mut reader, None);
-        assert!(matches!(second, Err(InputError::Parse(_))));
+        assert!(matches!(second, Err(InputError::Parse { .. })));
 
         // Next read is EOF (because we've consumed all input)
         let third = read_input_from::<_, i32>(&mut reader, None);
@@ -275,7 +1720,7 @@ mod tests {
         let mut reader = Cursor::new("\n");
         let res: Result<i32, _> = read_input_from(&mut reader, None);
         // Typically this is a parse error, because "" can't parse into i32
-        assert!(matches!(res, Err(InputError::Parse(_))));
+        assert!(matches!(res, Err(InputError::Parse { .. })));
     }
 
     /// Test that macros compile and work as expected (this is a basic usage check).
@@ -292,8 +1737,279 @@ mod tests {
         assert_eq!(result.unwrap(), "HelloFromMacro");
     }
 
+    /// `input!()` reads the thread's input source, then reports EOF as `Ok(None)`.
+    #[test]
+    fn test_input_macro_source() {
+        set_input_source(Box::new(Cursor::new("HelloFromMacro\n")));
+        let result: Option<String> = input!().unwrap();
+        assert_eq!(result.as_deref(), Some("HelloFromMacro"));
+        let result: Option<String> = input!().unwrap();
+        assert_eq!(result, None);
+        reset_input_source();
+    }
+
+    /// `read_input_to` writes the prompt to the given writer.
+    #[test]
+    fn test_read_input_to_writer() {
+        let mut reader = Cursor::new("7\n");
+        let mut out = Vec::new();
+        let n: i32 = read_input_to(&mut reader, &mut out, Some(format_args!("{}? ", "n"))).unwrap();
+        assert_eq!(n, 7);
+        assert_eq!(out, b"n? ");
+
+        let res = read_input_to::<_, _, i32>(&mut reader, &mut out, Some(format_args!("again: ")));
+        assert!(matches!(res, Err(InputError::Eof)));
+        assert_eq!(out, b"n? again: ");
+    }
+
+    /// The buffer holds only the latest line, so stale text never leaks into a parse.
+    #[test]
+    fn test_read_input_into_clears_buffer() {
+        let mut reader = Cursor::new("123\n4\n");
+        let mut buf = String::from("junk");
+        assert_eq!(
+            read_input_into::<_, u32>(&mut reader, None, &mut buf).unwrap(),
+            123
+        );
+        assert_eq!(
+            read_input_into::<_, u32>(&mut reader, None, &mut buf).unwrap(),
+            4
+        );
+        assert_eq!(buf, "4\n");
+        assert!(matches!(
+            read_input_into::<_, u32>(&mut reader, None, &mut buf),
+            Err(InputError::Eof)
+        ));
+    }
+
+    /// Reading into the same buffer keeps its allocation for shorter lines.
+    #[test]
+    fn test_read_input_into_reuses_buffer() {
+        let mut reader = Cursor::new("123456789\n4\nx\n");
+        let mut buf = String::new();
+        read_input_into::<_, u64>(&mut reader, None, &mut buf).unwrap();
+        let (capacity, storage) = (buf.capacity(), buf.as_ptr());
+        read_input_into::<_, u32>(&mut reader, None, &mut buf).unwrap();
+        assert!(read_input_into::<_, u32>(&mut reader, None, &mut buf).is_err());
+        assert_eq!((buf.capacity(), buf.as_ptr()), (capacity, storage));
+    }
+
+    /// Records end at a custom, possibly multi-byte, delimiter.
+    #[test]
+    fn test_read_until_custom_delimiter() {
+        let mut reader = Cursor::new("1;2;3");
+        let values: Vec<i32> =
+            std::iter::from_fn(|| read_until_from(&mut reader, None, b";").ok()).collect();
+        assert_eq!(values, vec![1, 2, 3]);
+
+        let mut reader = Cursor::new("a\r\nb\nc\r\n");
+        let first: String = read_until_from(&mut reader, None, b"\r\n").unwrap();
+        let second: String = read_until_from(&mut reader, None, b"\r\n").unwrap();
+        assert_eq!((first.as_str(), second.as_str()), ("a", "b\nc"));
+        let res = read_until_from::<_, String>(&mut reader, None, b"\r\n");
+        assert!(matches!(res, Err(InputError::Eof)));
+    }
+
+    /// An empty delimiter is an error, not a panic.
+    #[test]
+    fn test_read_until_empty_delimiter() {
+        let mut reader = Cursor::new("1;2");
+        let res = read_until_from::<_, i32>(&mut reader, None, b"");
+        assert!(matches!(res, Err(InputError::Invalid(_))));
+    }
+
+    /// Records that are not valid UTF-8 are reported as I/O errors.
+    #[test]
+    fn test_read_until_invalid_utf8() {
+        let mut reader = Cursor::new(vec![0xff, 0]);
+        let res = read_until_from::<_, String>(&mut reader, None, b"\0");
+        assert!(
+            matches!(res, Err(InputError::Io(ref e)) if e.kind() == io::ErrorKind::InvalidData)
+        );
+    }
+
+    /// `read_split_from` trims fields only when asked to.
+    #[test]
+    fn test_read_split() {
+        let mut reader = Cursor::new("1, 2 ,3\n1, 2\na\tb\t\n");
+        let values: Vec<i32> = read_split_from(&mut reader, None, ',', true).unwrap();
+        assert_eq!(values, vec![1, 2, 3]);
+
+        let res = read_split_from::<_, i32>(&mut reader, None, ',', false);
+        assert!(matches!(
+            res,
+            Err(InputError::Parse { source: TokenError::Invalid { index: 1, ref token, .. }, .. }) if token == " 2"
+        ));
+
+        let fields: Vec<String> = read_split_from(&mut reader, None, '\t', false).unwrap();
+        assert_eq!(fields, vec!["a", "b", ""]);
+    }
+
+    /// `read_with_from` parses with the given closure and keeps EOF handling.
+    #[test]
+    fn test_read_with_parser() {
+        let mut reader = Cursor::new("3x4\n3-4\n");
+        let parse = |line: &str| -> Result<(u32, u32), String> {
+            let (w, h) = line.split_once('x').ok_or("expected WxH")?;
+            Ok((
+                w.parse().map_err(|_| "bad width")?,
+                h.parse().map_err(|_| "bad height")?,
+            ))
+        };
+        assert_eq!(read_with_from(&mut reader, None, parse).unwrap(), (3, 4));
+        let res = read_with_from(&mut reader, None, parse);
+        assert!(matches!(res, Err(InputError::Parse { source: ref e, .. }) if e == "expected WxH"));
+        let res = read_with_from(&mut reader, None, parse);
+        assert!(matches!(res, Err(InputError::Eof)));
+    }
+
+    /// The callback borrows the trimmed line, and EOF never reaches it.
+    #[test]
+    fn test_read_str_with() {
+        let mut reader = Cursor::new("key=value\r\n");
+        let len = read_str_with_from(&mut reader, None, |line| {
+            line.split_once('=').map(|(key, _)| key.len())
+        });
+        assert_eq!(len.unwrap(), Some(3));
+        let res = read_str_with_from(&mut reader, None, |_| unreachable!());
+        assert!(matches!(res, Err::<(), _>(InputError::Eof)));
+    }
+
+    /// `read_try_from_from` converts through `TryFrom<&str>`.
+    #[test]
+    fn test_read_try_from() {
+        #[derive(Debug, PartialEq)]
+        struct Even(u32);
+
+        impl TryFrom<&str> for Even {
+            type Error = String;
+            fn try_from(s: &str) -> Result<Self, String> {
+                match s.parse::<u32>() {
+                    Ok(n) if n % 2 == 0 => Ok(Even(n)),
+                    _ => Err(format!("{:?} is not an even number", s)),
+                }
+            }
+        }
+
+        let mut reader = Cursor::new("4\n5\n");
+        let even: Even = read_try_from_from(&mut reader, None).unwrap();
+        assert_eq!(even, Even(4));
+        let res = read_try_from_from::<_, Even, _>(&mut reader, None);
+        assert!(
+            matches!(res, Err(InputError::Parse { source: ref e, .. }) if e == "\"5\" is not an even number")
+        );
+    }
+
+    /// The inspection helpers pick out each kind of error.
+    #[test]
+    fn test_error_inspection() {
+        let mut reader = Cursor::new("x\n");
+        let err = read_input_from::<_, i32>(&mut reader, None).unwrap_err();
+        assert!(err.is_parse() && !err.is_eof() && !err.is_io());
+        assert_eq!(err, InputError::parse("x".parse::<i32>().unwrap_err(), "x"));
+        assert!(err.into_parse().is_some());
+
+        let err = read_input_from::<_, i32>(&mut reader, None).unwrap_err();
+        assert_eq!(err, InputError::Eof);
+        assert!(err.as_io().is_none() && err.into_parse().is_none());
+
+        let err: InputError<Infallible> = InputError::Io(io::ErrorKind::BrokenPipe.into());
+        assert!(err.is_io());
+        assert_eq!(err.as_io().unwrap().kind(), io::ErrorKind::BrokenPipe);
+        assert_eq!(err, InputError::Io(io::ErrorKind::BrokenPipe.into()));
+        assert_ne!(err, InputError::Io(io::ErrorKind::Other.into()));
+    }
+
+    /// Parse errors of different types unify through `map_parse` and `boxed`.
+    #[test]
+    fn test_map_parse_and_boxed() {
+        let mut reader = Cursor::new("1.5\n-1\n");
+        let err = read_input_from::<_, i32>(&mut reader, None).unwrap_err();
+        let err = err.map_parse(|e| e.to_string());
+        assert_eq!(
+            err,
+            InputError::parse("invalid digit found in string".to_string(), "1.5")
+        );
+        let err = read_input_from::<_, u8>(&mut reader, None)
+            .map_err(InputError::boxed)
+            .unwrap_err();
+        assert!(matches!(err, InputError::Parse { ref input, .. } if input == "-1"));
+        assert!(read_input_from::<_, u8>(&mut reader, None)
+            .map_err(InputError::boxed)
+            .unwrap_err()
+            .is_eof());
+
+        let err: InputError<BoxError> = io::Error::from(io::ErrorKind::BrokenPipe).into();
+        assert!(err.is_io());
+    }
+
+    /// The I/O or parse error is reachable through `source`.
+    #[test]
+    fn test_error_source() {
+        use std::error::Error;
+
+        let err = read_input_from::<_, i32>(&mut Cursor::new("x\n"), None).unwrap_err();
+        let source = err.source().unwrap();
+        let parse = source.downcast_ref::<std::num::ParseIntError>().unwrap();
+        assert_eq!(parse.to_string(), "invalid digit found in string");
+        assert_eq!(*parse.kind(), std::num::IntErrorKind::InvalidDigit);
+
+        let err: InputError<Infallible> = io::Error::other("disk on fire").into();
+        assert_eq!(err.source().unwrap().to_string(), "disk on fire");
+        assert!(InputError::<Infallible>::Eof.source().is_none());
+    }
+
+    /// Read errors work with `?` in `anyhow::Result` functions; those whose
+    /// parse error is not an `Error`, such as a form's, go through `anyhow!`.
+    #[test]
+    fn test_anyhow() {
+        fn total(reader: &mut impl BufRead) -> anyhow::Result<i64> {
+            let a: i64 = read_input_from(reader, None)?;
+            let b: i64 = read_input_from(reader, None)?;
+            Ok(a + b)
+        }
+
+        assert_eq!(total(&mut Cursor::new("2\n40\n")).unwrap(), 42);
+        let err = total(&mut Cursor::new("2\nx\n")).unwrap_err();
+        assert!(err
+            .downcast_ref::<InputError<std::num::ParseIntError>>()
+            .unwrap()
+            .is_parse());
+
+        fn user(reader: &mut &[u8]) -> anyhow::Result<String> {
+            let answers = Form::new()
+                .field::<String>("user", "User: ")
+                .run_from(reader)
+                .map_err(|err| anyhow::anyhow!(err))?;
+            Ok(answers.get_str("user").unwrap().to_string())
+        }
+
+        assert_eq!(user(&mut "admin\n".as_bytes()).unwrap(), "admin");
+        let err = user(&mut "".as_bytes()).unwrap_err();
+        assert!(err.downcast_ref::<InputError<BoxError>>().unwrap().is_eof());
+    }
+
+    /// Read errors convert into `io::Error` with a matching kind.
+    #[test]
+    fn test_into_io_error() {
+        fn read_port(reader: &mut impl BufRead) -> io::Result<u16> {
+            Ok(read_input_from(reader, None)?)
+        }
+
+        assert_eq!(read_port(&mut Cursor::new("80\n")).unwrap(), 80);
+        let err = read_port(&mut Cursor::new("http\n")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let inner = err
+            .downcast::<InputError<std::num::ParseIntError>>()
+            .unwrap();
+        assert!(matches!(inner, InputError::Parse { ref input, .. } if input == "http"));
+        let err = read_port(&mut Cursor::new("")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
     /// Check that a custom `Read` implementation that returns an error triggers `InputError::Io`.
     #[test]
+    #[allow(clippy::io_other_error)]
     fn test_io_error() {
         struct ErrorReader;
 
@@ -316,4 +2032,293 @@ mod tests {
         let res: Result<String, _> = read_input_from(&mut reader, None);
         assert!(matches!(res, Err(InputError::Io(_))));
     }
+
+    /// `scan!`'s token helpers parse each token and flag missing and extra ones.
+    #[test]
+    fn test_scan_tokens() {
+        let mut tokens = "3 4.5".split_whitespace();
+        let mut index = 0;
+        let a: i32 = __scan_token(&mut tokens, &mut index).unwrap();
+        let b: f64 = __scan_token(&mut tokens, &mut index).unwrap();
+        assert_eq!((a, b), (3, 4.5));
+        assert!(__scan_finish(&mut tokens, index).is_ok());
+
+        let missing = __scan_token::<i32, _>(&mut tokens, &mut index);
+        assert!(matches!(missing, Err(TokenError::Missing { index: 2 })));
+
+        let mut tokens = "1 x 2".split_whitespace();
+        let mut index = 0;
+        let _: i32 = __scan_token(&mut tokens, &mut index).unwrap();
+        let bad = __scan_token::<i32, _>(&mut tokens, &mut index);
+        assert!(
+            matches!(bad, Err(TokenError::Invalid { index: 1, ref token, .. }) if token == "x")
+        );
+        let _: i32 = __scan_token(&mut tokens, &mut index).unwrap();
+        assert!(__scan_finish(&mut tokens, index).is_ok());
+
+        let mut tokens = "1 2".split_whitespace();
+        let mut index = 0;
+        let _: i32 = __scan_token(&mut tokens, &mut index).unwrap();
+        let extra = __scan_finish(&mut tokens, index);
+        assert!(matches!(
+            extra,
+            Err(TokenError::Unexpected { index: 1, .. })
+        ));
+    }
+
+    /// `scan!` parses a whole line into its fields, and rejects lines with
+    /// too few or too many tokens.
+    #[test]
+    fn test_scan_macro() {
+        set_input_source(Box::new(Cursor::new("3 4.5\n1\n1 2 3\n1 x\n")));
+        let (a, b) = scan!(a: i32, b: f64).unwrap().unwrap();
+        assert_eq!((a, b), (3, 4.5));
+        let missing = scan!("Point: "; x: i32, y: i32);
+        assert!(matches!(
+            missing,
+            Err(Located {
+                error: InputError::Parse {
+                    source: TokenError::Missing { index: 1 },
+                    ..
+                },
+                ..
+            })
+        ));
+        let extra = scan!(x: i32, y: i32);
+        assert!(matches!(
+            extra,
+            Err(Located {
+                error: InputError::Parse {
+                    source: TokenError::Unexpected { index: 2, ref token },
+                    ..
+                },
+                ..
+            }) if token == "3"
+        ));
+        let invalid = scan!(x: i32, y: i32);
+        assert!(matches!(
+            invalid,
+            Err(Located {
+                error: InputError::Parse {
+                    source: TokenError::Invalid { index: 1, .. },
+                    ..
+                },
+                ..
+            })
+        ));
+        assert!(scan!(x: i32).unwrap().is_none());
+        reset_input_source();
+    }
+
+    /// `scan!` with a pattern positions fields by the literals around them.
+    #[test]
+    fn test_scan_macro_pattern() {
+        set_input_source(Box::new(Cursor::new("(1, 2)\n(1 2)\n")));
+        let (x, y) = scan!("({},{})", x: i32, y: i32).unwrap().unwrap();
+        assert_eq!((x, y), (1, 2));
+        let res = scan!("Point {}: ", 1; "({},{})", x: i32, y: i32);
+        assert!(matches!(
+            res,
+            Err(Located {
+                error: InputError::Parse {
+                    source: PatternError::Literal { .. },
+                    ..
+                },
+                ..
+            })
+        ));
+        reset_input_source();
+    }
+
+    /// Whitespace-separated tokens are all parsed into the `Vec`.
+    #[test]
+    fn test_read_vec_whitespace() {
+        let mut reader = Cursor::new("1  2\t3\n");
+        let res: Vec<i32> = read_vec_from(&mut reader, None, None).unwrap();
+        assert_eq!(res, vec![1, 2, 3]);
+    }
+
+    /// A custom delimiter splits fields; an empty line yields an empty `Vec`.
+    #[test]
+    fn test_read_vec_delimiter() {
+        let mut reader = Cursor::new("4,5,6\n\n");
+        let res: Vec<u8> = read_vec_from(&mut reader, None, Some(',')).unwrap();
+        assert_eq!(res, vec![4, 5, 6]);
+        let empty: Vec<u8> = read_vec_from(&mut reader, None, Some(',')).unwrap();
+        assert!(empty.is_empty());
+    }
+
+    /// The first token that fails to parse is reported with its index.
+    #[test]
+    fn test_read_vec_reports_token_index() {
+        let mut reader = Cursor::new("1 2 x 4\n");
+        let res = read_vec_from::<_, i32>(&mut reader, None, None);
+        assert!(matches!(
+            res,
+            Err(InputError::Parse { source: TokenError::Invalid { index: 2, ref token, .. }, .. }) if token == "x"
+        ));
+    }
+
+    /// Parse failures are retried until a line parses.
+    #[test]
+    fn test_read_until_valid_retries() {
+        let mut reader = Cursor::new("x\ny\n5\n");
+        let res: Result<i32, _> = read_until_valid_from(&mut reader, None, 3, None);
+        assert_eq!(res.unwrap(), 5);
+    }
+
+    /// Running out of attempts reports how many were made.
+    #[test]
+    fn test_read_until_valid_exhausted() {
+        let mut reader = Cursor::new("x\ny\n5\n");
+        let res: Result<i32, _> =
+            read_until_valid_from(&mut reader, None, 2, Some(format_args!("Try again")));
+        assert!(matches!(res, Err(InputError::AttemptsExhausted(2))));
+    }
+
+    /// The retry message is printed only before another attempt.
+    #[test]
+    fn test_read_until_valid_retry_message() {
+        let (res, prompts) = crate::testing::ScriptedReader::new()
+            .respond("x")
+            .respond("y")
+            .record(|| read_until_valid::<i32>(None, 2, Some(format_args!("Try again"))));
+        assert!(matches!(
+            res,
+            Err(Located {
+                error: InputError::AttemptsExhausted(2),
+                ..
+            })
+        ));
+        assert_eq!(prompts.concat(), "Try again\n");
+    }
+
+    /// EOF ends the retry loop immediately.
+    #[test]
+    fn test_read_until_valid_eof() {
+        let mut reader = Cursor::new("x\n");
+        let res: Result<i32, _> = read_until_valid_from(&mut reader, None, 5, None);
+        assert!(matches!(res, Err(InputError::Eof)));
+    }
+
+    /// Values accepted by the validator are returned unchanged.
+    #[test]
+    fn test_validated_accepts() {
+        let mut reader = Cursor::new("42\n");
+        let res = read_input_validated_from(&mut reader, None, |n: &u32| {
+            (*n < 150).then_some(()).ok_or("age out of range".into())
+        });
+        assert_eq!(res.unwrap(), 42);
+    }
+
+    /// A rejected value surfaces the validator's message as `Invalid`.
+    #[test]
+    fn test_validated_rejects() {
+        let mut reader = Cursor::new("200\n");
+        let res = read_input_validated_from(&mut reader, None, |n: &u32| {
+            (*n < 150).then_some(()).ok_or("age out of range".into())
+        });
+        assert!(matches!(res, Err(InputError::Invalid(ref msg)) if msg == "age out of range"));
+    }
+
+    /// Values inside the range are accepted.
+    #[test]
+    fn test_in_range_accepts() {
+        let mut reader = Cursor::new("10\n");
+        let res: Result<u8, _> = read_in_range_from(&mut reader, None, 1..=10);
+        assert_eq!(res.unwrap(), 10);
+    }
+
+    /// The error for an out-of-range value names the allowed range.
+    #[test]
+    fn test_in_range_rejects_with_range() {
+        let mut reader = Cursor::new("42\n0\n");
+        let res: Result<u8, _> = read_in_range_from(&mut reader, None, 1..=10);
+        assert!(matches!(res, Err(InputError::Invalid(ref msg)) if msg == "42 is outside 1..=10"));
+        let res: Result<i32, _> = read_in_range_from(&mut reader, None, 1..);
+        assert!(matches!(res, Err(InputError::Invalid(ref msg)) if msg == "0 is outside 1.."));
+    }
+
+    /// An empty or blank line yields the default; anything else is parsed.
+    #[test]
+    fn test_or_default() {
+        let mut reader = Cursor::new("\n  \n9000\n");
+        let first: u16 = read_input_or_default_from(&mut reader, None, 8080, false).unwrap();
+        let second: u16 = read_input_or_default_from(&mut reader, None, 8080, true).unwrap();
+        let third: u16 = read_input_or_default_from(&mut reader, None, 8080, false).unwrap();
+        assert_eq!((first, second, third), (8080, 8080, 9000));
+    }
+
+    /// EOF is still reported rather than mapped to the default.
+    #[test]
+    fn test_or_default_eof() {
+        let mut reader = Cursor::new("");
+        let res: Result<u16, _> = read_input_or_default_from(&mut reader, None, 8080, false);
+        assert!(matches!(res, Err(InputError::Eof)));
+    }
+
+    /// Blank lines map to `None`, other lines are parsed.
+    #[test]
+    fn test_read_optional() {
+        let mut reader = Cursor::new("\n \t\n7\nx\n");
+        assert_eq!(
+            read_optional_from::<_, i32>(&mut reader, None).unwrap(),
+            None
+        );
+        assert_eq!(
+            read_optional_from::<_, i32>(&mut reader, None).unwrap(),
+            None
+        );
+        assert_eq!(
+            read_optional_from::<_, i32>(&mut reader, None).unwrap(),
+            Some(7)
+        );
+        assert!(matches!(
+            read_optional_from::<_, i32>(&mut reader, None),
+            Err(InputError::Parse { .. })
+        ));
+        assert!(matches!(
+            read_optional_from::<_, i32>(&mut reader, None),
+            Err(InputError::Eof)
+        ));
+    }
+
+    /// Each line is parsed independently and iteration stops at EOF.
+    #[test]
+    fn test_lines_as() {
+        let reader = Cursor::new("1\n2\nx\n4\n");
+        let results: Vec<_> = lines_as::<_, i32>(reader).collect();
+        assert_eq!(results.len(), 4);
+        assert!(matches!(results[2], Err(InputError::Parse { .. })));
+        let ok: Vec<i32> = results.into_iter().filter_map(Result::ok).collect();
+        assert_eq!(ok, vec![1, 2, 4]);
+    }
+
+    /// Yes/no answers are case-insensitive and unknown answers re-prompt.
+    #[test]
+    fn test_confirm_answers() {
+        let mut reader = Cursor::new("YES\nmaybe\nn\n");
+        assert!(confirm_from(&mut reader, None, None).unwrap());
+        assert!(!confirm_from(&mut reader, None, None).unwrap());
+        assert!(matches!(
+            confirm_from(&mut reader, None, None),
+            Err(InputError::Eof)
+        ));
+    }
+
+    /// An empty line takes the default, or re-prompts when there is none.
+    #[test]
+    fn test_confirm_default() {
+        let mut reader = Cursor::new("\n\ny\n");
+        assert!(!confirm_from(&mut reader, None, Some(false)).unwrap());
+        assert!(confirm_from(&mut reader, None, None).unwrap());
+    }
+
+    /// `confirm!` reads its default from the `[Y/n]` hint.
+    #[test]
+    fn test_confirm_default_from_hint() {
+        assert_eq!(__confirm_default_from_hint("Go? [Y/n] "), Some(true));
+        assert_eq!(__confirm_default_from_hint("Delete? [y/N]"), Some(false));
+        assert_eq!(__confirm_default_from_hint("Sure? "), None);
+    }
 }
@@ -0,0 +1,274 @@
+use std::cell::Cell;
+use std::convert::Infallible;
+use std::io::{self, BufRead, Read};
+
+use crate::InputError;
+
+/// The length cap used when no other is set: 1 MiB.
+const DEFAULT_MAX_LINE_LEN: usize = 1024 * 1024;
+
+/// How much of an overlong record is skipped per read while looking for its end.
+const SKIP_CHUNK: u64 = 8 * 1024;
+
+thread_local! {
+    static LIMITS: Cell<Limits> = const {
+        Cell::new(Limits {
+            max_line_len: DEFAULT_MAX_LINE_LEN,
+        })
+    };
+}
+
+/// Bounds on how much a single read may consume, so a hostile or broken pipe
+/// cannot make a read allocate without limit.
+///
+/// # Usage:
+/// ```
+/// use input_macro::{read_input_from, set_limits, InputError, Limits};
+///
+/// set_limits(Limits { max_line_len: 8 });
+/// let mut reader = std::io::Cursor::new("much too long\n7\n");
+/// let res = read_input_from::<_, String>(&mut reader, None);
+/// assert!(matches!(res, Err(InputError::TooLong(8))));
+/// // The rest of the long line was discarded
+/// assert_eq!(read_input_from::<_, u8>(&mut reader, None).unwrap(), 7);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// The longest line or record, in bytes and excluding its `\n` or other
+    /// delimiter, that a read accepts; longer ones fail with
+    /// `InputError::TooLong`. Defaults to 1 MiB.
+    pub max_line_len: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_line_len: DEFAULT_MAX_LINE_LEN,
+        }
+    }
+}
+
+/// Sets the limits for this thread's reads.
+///
+/// Like [`set_input_source`](crate::set_input_source), this is per thread.
+/// Readers that own their input, such as [`InputReader`](crate::InputReader)
+/// and [`Scanner`](crate::Scanner), can also be given their own limits, and
+/// a [`BackgroundReader`](crate::BackgroundReader) or async read keeps the
+/// limits of the thread it was started on.
+pub fn set_limits(limits: Limits) {
+    LIMITS.set(limits);
+}
+
+/// Returns the limits in effect for this thread's reads.
+pub fn limits() -> Limits {
+    LIMITS.get()
+}
+
+/// Makes this thread's reads use `limits`, if any, until the returned guard
+/// is dropped.
+pub(crate) fn use_limits(limits: Option<Limits>) -> LimitsGuard {
+    LimitsGuard(limits.map(|limits| LIMITS.replace(limits)))
+}
+
+/// Restores the limits this thread used before when dropped.
+pub(crate) struct LimitsGuard(Option<Limits>);
+
+impl Drop for LimitsGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0 {
+            LIMITS.set(previous);
+        }
+    }
+}
+
+/// Appends one line from `reader` to `buf`, like `read_line`, but reads at
+/// most `max_len` bytes plus the `\n`.
+///
+/// A longer line is consumed and discarded up to its end, and
+/// `Err(InputError::TooLong)` is returned with `buf` unchanged.
+pub(crate) fn read_line_limited<R, E>(
+    reader: &mut R,
+    buf: &mut String,
+    max_len: usize,
+) -> Result<usize, InputError<E>>
+where
+    R: BufRead + ?Sized,
+{
+    // Read into the string's own storage, which keeps its capacity
+    let start = buf.len();
+    let mut bytes = std::mem::take(buf).into_bytes();
+    let result = read_until_limited(reader, &mut bytes, b"\n", max_len);
+    if result.is_err() {
+        bytes.truncate(start);
+    }
+    match String::from_utf8(bytes) {
+        Ok(line) => {
+            *buf = line;
+            result
+        }
+        Err(err) => {
+            let mut bytes = err.into_bytes();
+            bytes.truncate(start);
+            // What is left was already a `String`
+            *buf = String::from_utf8(bytes).unwrap_or_default();
+            Err(InputError::Io(invalid_utf8()))
+        }
+    }
+}
+
+/// The error `read_line` fails with on invalid UTF-8.
+pub(crate) fn invalid_utf8() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "stream did not contain valid UTF-8",
+    )
+}
+
+/// Like [`read_line_limited`] with this thread's [`limits`], for the bulk
+/// readers: a line that is too long is `Ok(None)`, and other failures are
+/// I/O errors.
+pub(crate) fn read_line_or_skip<R>(reader: &mut R, buf: &mut String) -> io::Result<Option<usize>>
+where
+    R: BufRead + ?Sized,
+{
+    match read_line_limited::<_, Infallible>(reader, buf, limits().max_line_len) {
+        Ok(n) => Ok(Some(n)),
+        Err(InputError::TooLong(_)) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Like [`read_line_limited`], but appends raw bytes, which need not be
+/// UTF-8, up to and including `delim`, which must not be empty.
+pub(crate) fn read_until_limited<R, E>(
+    reader: &mut R,
+    bytes: &mut Vec<u8>,
+    delim: &[u8],
+    max_len: usize,
+) -> Result<usize, InputError<E>>
+where
+    R: BufRead + ?Sized,
+{
+    let start = bytes.len();
+    let last = delim[delim.len() - 1];
+    let cap = (max_len as u64).saturating_add(delim.len() as u64);
+    let mut limited = (&mut *reader).take(cap);
+    loop {
+        let n = limited.read_until(last, bytes).map_err(InputError::from)?;
+        if n == 0 || bytes[start..].ends_with(delim) {
+            break;
+        }
+    }
+    let n = bytes.len() - start;
+    if n as u64 == cap && !bytes[start..].ends_with(delim) {
+        // Keep what may be the start of a delimiter cut off by the cap
+        let tail = bytes.len() - (delim.len() - 1).min(n);
+        let mut seen = bytes.split_off(tail);
+        bytes.truncate(start);
+        skip_until(reader, delim, &mut seen).map_err(InputError::from)?;
+        return Err(InputError::TooLong(max_len));
+    }
+    Ok(n)
+}
+
+/// Discards everything up to and including the next `delim`, whose start
+/// may already be in `seen`, without buffering more than a chunk at a time.
+fn skip_until<R>(reader: &mut R, delim: &[u8], seen: &mut Vec<u8>) -> io::Result<()>
+where
+    R: BufRead + ?Sized,
+{
+    let last = delim[delim.len() - 1];
+    loop {
+        if (&mut *reader).take(SKIP_CHUNK).read_until(last, seen)? == 0 {
+            return Ok(());
+        }
+        if seen.ends_with(delim) {
+            return Ok(());
+        }
+        let keep = seen.len() - (delim.len() - 1).min(seen.len());
+        seen.drain(..keep);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn read(reader: &mut Cursor<&[u8]>, max_len: usize) -> Result<String, InputError<()>> {
+        let mut buf = String::new();
+        read_line_limited(reader, &mut buf, max_len).map(|_| buf)
+    }
+
+    /// Lines up to the limit are read whole; longer ones are skipped and reported.
+    #[test]
+    fn test_read_line_limited() {
+        let mut reader = Cursor::new(&b"abcd\nabcde\nab\nabcdef"[..]);
+        assert_eq!(read(&mut reader, 4).unwrap(), "abcd\n");
+        assert!(matches!(read(&mut reader, 4), Err(InputError::TooLong(4))));
+        assert_eq!(read(&mut reader, 4).unwrap(), "ab\n");
+        assert!(matches!(read(&mut reader, 4), Err(InputError::TooLong(4))));
+        assert_eq!(read(&mut reader, 4).unwrap(), "");
+    }
+
+    /// Invalid UTF-8 is still an I/O error, as with `read_line`.
+    #[test]
+    fn test_read_line_limited_invalid_utf8() {
+        let mut reader = Cursor::new(&b"\xff\n"[..]);
+        let err = read(&mut reader, 16).unwrap_err();
+        assert_eq!(err.as_io().unwrap().kind(), io::ErrorKind::InvalidData);
+    }
+
+    /// A line cut off in the middle of a character is too long, not invalid.
+    #[test]
+    fn test_read_line_limited_cut_in_character() {
+        let mut reader = Cursor::new("aé\nb\n".as_bytes());
+        assert!(matches!(read(&mut reader, 1), Err(InputError::TooLong(1))));
+        assert_eq!(read(&mut reader, 1).unwrap(), "b\n");
+    }
+
+    /// Lines are appended to what the buffer holds, in its own storage.
+    #[test]
+    fn test_read_line_limited_appends() {
+        let mut reader = Cursor::new(&b"def\n\xff\n"[..]);
+        let mut buf = String::with_capacity(64);
+        buf.push_str("abc");
+        let capacity = buf.capacity();
+        read_line_limited::<_, ()>(&mut reader, &mut buf, 16).unwrap();
+        assert_eq!(buf, "abcdef\n");
+        assert!(read_line_limited::<_, ()>(&mut reader, &mut buf, 16).is_err());
+        assert_eq!(buf, "abcdef\n");
+        assert_eq!(buf.capacity(), capacity);
+    }
+
+    /// Records end at a delimiter of several bytes, even one split by the cap.
+    #[test]
+    fn test_read_until_limited_delimiter() {
+        let mut reader = Cursor::new(&b"ab;;abcd;;x;;"[..]);
+        let mut bytes = Vec::new();
+        assert_eq!(
+            read_until_limited::<_, ()>(&mut reader, &mut bytes, b";;", 3).unwrap(),
+            4
+        );
+        assert_eq!(bytes, b"ab;;");
+        bytes.clear();
+        // The cap of 3 + 2 bytes ends after "abcd;", in the middle of the delimiter
+        let res = read_until_limited::<_, ()>(&mut reader, &mut bytes, b";;", 3);
+        assert!(matches!(res, Err(InputError::TooLong(3))));
+        read_until_limited::<_, ()>(&mut reader, &mut bytes, b";;", 3).unwrap();
+        assert_eq!(bytes, b"x;;");
+    }
+
+    /// Per-reader limits apply only while their guard lives.
+    #[test]
+    fn test_use_limits() {
+        let default = limits();
+        {
+            let _limits = use_limits(Some(Limits { max_line_len: 2 }));
+            assert_eq!(limits().max_line_len, 2);
+            let _unchanged = use_limits(None);
+            assert_eq!(limits().max_line_len, 2);
+        }
+        assert_eq!(limits(), default);
+    }
+}
@@ -0,0 +1,68 @@
+use std::fmt::Arguments;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::limits::read_until_limited;
+use crate::{limits, parse_line, strip_line_ending, write_prompt, InputError, Located};
+
+/// Like [`read_input_from`](crate::read_input_from), but replaces invalid
+/// UTF-8 in the line with `U+FFFD` instead of failing with an I/O error.
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_input_lossy_from;
+///
+/// let mut reader = std::io::Cursor::new(&b"caf\xe9\n"[..]);
+/// let name: String = read_input_lossy_from(&mut reader, None).unwrap();
+/// assert_eq!(name, "caf\u{fffd}");
+/// ```
+pub fn read_input_lossy_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    if let Some(prompt_args) = prompt {
+        write_prompt(prompt_args).map_err(InputError::Io)?;
+    }
+    let mut bytes = Vec::new();
+    if read_until_limited(reader, &mut bytes, b"\n", limits().max_line_len)? == 0 {
+        return Err(InputError::Eof);
+    }
+    parse_line(strip_line_ending(&String::from_utf8_lossy(&bytes)))
+}
+
+/// A convenience wrapper around [`read_input_lossy_from`] that reads from stdin.
+#[track_caller]
+pub fn read_input_lossy<T>(prompt: Option<Arguments<'_>>) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::__with_stdin(|reader| read_input_lossy_from(reader, prompt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Valid fields still parse on a line holding invalid UTF-8.
+    #[test]
+    fn test_read_input_lossy() {
+        let mut reader = Cursor::new(&b"\xff\xfe 7\r\n12\n"[..]);
+        let line: String = read_input_lossy_from(&mut reader, None).unwrap();
+        assert_eq!(line, "\u{fffd}\u{fffd} 7");
+        assert_eq!(
+            read_input_lossy_from::<_, u8>(&mut reader, None).unwrap(),
+            12
+        );
+        assert!(matches!(
+            read_input_lossy_from::<_, u8>(&mut reader, None),
+            Err(InputError::Eof)
+        ));
+    }
+}
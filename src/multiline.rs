@@ -0,0 +1,213 @@
+use std::convert::Infallible;
+use std::fmt::Arguments;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::{parse_line, read_trimmed_line, InputError, Located};
+
+/// How the lines of a multi-line answer are joined before parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Join<'a> {
+    /// One `\n` between lines, with no trailing newline.
+    #[default]
+    Newline,
+    /// One space between lines, e.g. to reflow a pasted paragraph.
+    Space,
+    /// Nothing between lines, e.g. for a key pasted across several lines.
+    Concat,
+    /// The given separator between lines.
+    With(&'a str),
+}
+
+impl Join<'_> {
+    fn separator(&self) -> &str {
+        match self {
+            Join::Newline => "\n",
+            Join::Space => " ",
+            Join::Concat => "",
+            Join::With(separator) => separator,
+        }
+    }
+}
+
+/// Prints the optional prompt, then reads lines until an empty line or EOF
+/// and returns them joined with `\n`.
+///
+/// The empty line is consumed but not included. EOF before any line fails
+/// with `Err(InputError::Eof)`.
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_multiline_from;
+///
+/// let mut reader = std::io::Cursor::new("1 Main St\nSpringfield\n\nnext\n");
+/// let address = read_multiline_from(&mut reader, None).unwrap();
+/// assert_eq!(address, "1 Main St\nSpringfield");
+/// ```
+pub fn read_multiline_from<R>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<String, InputError<Infallible>>
+where
+    R: BufRead + ?Sized,
+{
+    read_multiline_as_from(reader, prompt, Join::Newline)
+}
+
+/// A convenience wrapper around [`read_multiline_from`] that reads from stdin.
+#[track_caller]
+pub fn read_multiline(
+    prompt: Option<Arguments<'_>>,
+) -> Result<String, Located<InputError<Infallible>>> {
+    crate::__with_stdin(|reader| read_multiline_from(reader, prompt))
+}
+
+/// Like [`read_multiline_from`], but joins the lines as `join` says and
+/// parses the result into `T`.
+///
+/// # Usage:
+/// ```
+/// use input_macro::{read_multiline_as_from, Join};
+///
+/// let mut reader = std::io::Cursor::new("12\n34\n\n");
+/// let n: u32 = read_multiline_as_from(&mut reader, None, Join::Concat).unwrap();
+/// assert_eq!(n, 1234);
+/// ```
+pub fn read_multiline_as_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    join: Join<'_>,
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let lines = read_lines_until(reader, prompt, str::is_empty, true)?;
+    parse_line(&lines.join(join.separator()))
+}
+
+/// A convenience wrapper around [`read_multiline_as_from`] that reads from stdin.
+#[track_caller]
+pub fn read_multiline_as<T>(
+    prompt: Option<Arguments<'_>>,
+    join: Join<'_>,
+) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::__with_stdin(|reader| read_multiline_as_from(reader, prompt, join))
+}
+
+/// Prints the optional prompt, then reads until the line `terminator`, in the
+/// manner of a shell heredoc or an SMTP `DATA` block, and returns the lines
+/// before it joined with `\n`.
+///
+/// The terminator must match a whole line exactly, apart from the line
+/// ending, and is consumed but not included. EOF before it fails with
+/// `Err(InputError::Eof)`, since the block may be incomplete.
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_heredoc_from;
+///
+/// let mut reader = std::io::Cursor::new("Hello,\n\nBye.\n.\nQUIT\n");
+/// let body = read_heredoc_from(&mut reader, None, ".").unwrap();
+/// assert_eq!(body, "Hello,\n\nBye.");
+/// ```
+pub fn read_heredoc_from<R>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    terminator: &str,
+) -> Result<String, InputError<Infallible>>
+where
+    R: BufRead + ?Sized,
+{
+    let lines = read_lines_until(reader, prompt, |line| line == terminator, false)?;
+    Ok(lines.join("\n"))
+}
+
+/// A convenience wrapper around [`read_heredoc_from`] that reads from stdin.
+#[track_caller]
+pub fn read_heredoc(
+    prompt: Option<Arguments<'_>>,
+    terminator: &str,
+) -> Result<String, Located<InputError<Infallible>>> {
+    crate::__with_stdin(|reader| read_heredoc_from(reader, prompt, terminator))
+}
+
+/// Prints the optional prompt, then reads lines, without their line endings,
+/// until one for which `is_end` holds, which is consumed but not returned.
+///
+/// With `eof_ends`, EOF after at least one line also ends the block;
+/// otherwise, and before any line, EOF is `Err(InputError::Eof)`.
+fn read_lines_until<R, E>(
+    reader: &mut R,
+    mut prompt: Option<Arguments<'_>>,
+    is_end: impl Fn(&str) -> bool,
+    eof_ends: bool,
+) -> Result<Vec<String>, InputError<E>>
+where
+    R: BufRead + ?Sized,
+{
+    let mut lines = Vec::new();
+    loop {
+        let mut input = String::new();
+        let line = match read_trimmed_line(reader, prompt.take(), &mut input) {
+            Err(InputError::Eof) if eof_ends && !lines.is_empty() => break,
+            result => result?,
+        };
+        if is_end(line) {
+            break;
+        }
+        lines.push(line.to_string());
+    }
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// EOF ends a block like an empty line does, and only an empty first read fails.
+    #[test]
+    fn test_read_multiline() {
+        let mut reader = Cursor::new("a\r\n  b\n\n\nc\n");
+        assert_eq!(read_multiline_from(&mut reader, None).unwrap(), "a\n  b");
+        assert_eq!(read_multiline_from(&mut reader, None).unwrap(), "");
+        assert_eq!(read_multiline_from(&mut reader, None).unwrap(), "c");
+        assert!(matches!(
+            read_multiline_from(&mut reader, None),
+            Err(InputError::Eof)
+        ));
+    }
+
+    /// Blank lines belong to a heredoc, which needs its terminator.
+    #[test]
+    fn test_read_heredoc() {
+        let mut reader = Cursor::new("a\n\nEOF \nEOF\r\nb\n");
+        assert_eq!(
+            read_heredoc_from(&mut reader, None, "EOF").unwrap(),
+            "a\n\nEOF "
+        );
+        assert!(matches!(
+            read_heredoc_from(&mut reader, None, "EOF"),
+            Err(InputError::Eof)
+        ));
+    }
+
+    /// Each joining strategy places its separator between lines only.
+    #[test]
+    fn test_read_multiline_as_join() {
+        let read = |join| {
+            let mut reader = Cursor::new("x\ny\nz\n");
+            read_multiline_as_from::<_, String>(&mut reader, None, join).unwrap()
+        };
+        assert_eq!(read(Join::Newline), "x\ny\nz");
+        assert_eq!(read(Join::Space), "x y z");
+        assert_eq!(read(Join::Concat), "xyz");
+        assert_eq!(read(Join::With(", ")), "x, y, z");
+    }
+}
@@ -0,0 +1,178 @@
+use std::fmt::Arguments;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+use crate::bulk::collect_all_with;
+use crate::{
+    parse_tokens_with, read_trimmed_line, Collected, InputError, Located, StdinError, TokenError,
+};
+
+/// A type that the bulk numeric readers, such as [`collect_numbers_from`],
+/// can parse with a faster parser than `str::parse`.
+///
+/// Implemented for the primitive integers and floats. With the `lexical`
+/// feature these parse with `lexical-core`; a token it rejects is handed to
+/// `str::parse`, so errors are the usual `ParseIntError` or `ParseFloatError`
+/// with the usual messages. Without the feature, and by default for other
+/// implementors, [`parse_number`](Number::parse_number) is `str::parse`.
+pub trait Number: FromStr {
+    /// Parses `text`, which has no surrounding whitespace, into a number.
+    fn parse_number(text: &str) -> Result<Self, Self::Err> {
+        text.parse()
+    }
+}
+
+macro_rules! impl_number {
+    ($($t:ty),+) => {
+        $(
+            impl Number for $t {
+                #[cfg(feature = "lexical")]
+                fn parse_number(text: &str) -> Result<Self, Self::Err> {
+                    lexical_core::parse(text.as_bytes()).or_else(|_| text.parse())
+                }
+            }
+        )+
+    };
+}
+
+impl_number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+/// Like [`read_vec_from`](crate::read_vec_from), but parses each token with
+/// [`Number::parse_number`].
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_numbers_from;
+///
+/// let mut reader = std::io::Cursor::new("0.5 1e3 -2\n");
+/// let values: Vec<f64> = read_numbers_from(&mut reader, None, None).unwrap();
+/// assert_eq!(values, [0.5, 1000.0, -2.0]);
+/// ```
+pub fn read_numbers_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    delimiter: Option<char>,
+) -> Result<Vec<T>, InputError<TokenError<T::Err>>>
+where
+    R: BufRead + ?Sized,
+    T: Number,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let mut input = String::new();
+    let line = read_trimmed_line(reader, prompt, &mut input)?;
+    parse_tokens_with(line, delimiter, false, T::parse_number)
+        .map_err(|source| InputError::parse(source, line))
+}
+
+/// A convenience wrapper around [`read_numbers_from`] that reads from stdin.
+#[track_caller]
+pub fn read_numbers<T>(
+    prompt: Option<Arguments<'_>>,
+    delimiter: Option<char>,
+) -> Result<Vec<T>, StdinError<TokenError<T::Err>>>
+where
+    T: Number,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::__with_stdin(|reader| read_numbers_from(reader, prompt, delimiter))
+}
+
+/// Like [`collect_all_from`](crate::collect_all_from), but parses each line
+/// with [`Number::parse_number`], for inputs of one number per line.
+///
+/// # Usage:
+/// ```
+/// use input_macro::collect_numbers_from;
+///
+/// let mut reader = std::io::Cursor::new("1.5\n2.5\nn/a\n");
+/// let collected = collect_numbers_from::<_, f64>(&mut reader).unwrap();
+/// assert_eq!(collected.values, [1.5, 2.5]);
+/// assert_eq!(collected.failures[0].line, 3);
+/// ```
+pub fn collect_numbers_from<R, T>(reader: &mut R) -> io::Result<Collected<T, T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: Number,
+{
+    collect_all_with(reader, T::parse_number)
+}
+
+/// A convenience wrapper around [`collect_numbers_from`] that reads all of stdin.
+#[track_caller]
+pub fn collect_numbers<T: Number>() -> Result<Collected<T, T::Err>, Located<io::Error>> {
+    crate::__with_stdin(|reader| collect_numbers_from(reader))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Debug;
+    use std::io::Cursor;
+
+    fn assert_same<T>(inputs: &[&str])
+    where
+        T: Number + Debug,
+        T::Err: Debug,
+    {
+        // Compared as text, so that NaN matches itself
+        for input in inputs {
+            let fast = format!("{:?}", T::parse_number(input));
+            assert_eq!(fast, format!("{:?}", input.parse::<T>()), "{:?}", input);
+        }
+    }
+
+    /// Whatever the parser, results and errors match `str::parse`.
+    #[test]
+    fn test_parse_number_matches_std() {
+        let integers = [
+            "0", "-0", "+7", "42", "-128", "128", "255", "256", "", "-", "+", "1_0", " 1", "0x10",
+            "1.0", "٣",
+        ];
+        assert_same::<i8>(&integers);
+        assert_same::<u8>(&integers);
+        assert_same::<i64>(&[
+            "9223372036854775807",
+            "9223372036854775808",
+            "-9223372036854775808",
+        ]);
+        assert_same::<u128>(&integers);
+        let floats = [
+            "1",
+            "-1.5",
+            "+.5",
+            "5.",
+            "1e10",
+            "1E-3",
+            "1e",
+            ".",
+            "e5",
+            "inf",
+            "-Infinity",
+            "NaN",
+            "nan",
+            "1e400",
+            "0x1p3",
+            "1_000.0",
+            " 1.0",
+            "1.0 ",
+        ];
+        assert_same::<f64>(&floats);
+        assert_same::<f32>(&floats);
+    }
+
+    /// Tokens are parsed as numbers, and bad ones are reported by index.
+    #[test]
+    fn test_read_numbers() {
+        let mut reader = Cursor::new("1,2,3\n4 five\n");
+        let values: Vec<u32> = read_numbers_from(&mut reader, None, Some(',')).unwrap();
+        assert_eq!(values, [1, 2, 3]);
+        let res = read_numbers_from::<_, u32>(&mut reader, None, None);
+        assert!(matches!(
+            res,
+            Err(InputError::Parse {
+                source: TokenError::Invalid { index: 1, .. },
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,168 @@
+use std::io::{self, BufRead};
+use std::str::FromStr;
+use std::sync::mpsc;
+
+use rayon::prelude::*;
+
+use crate::{strip_line_ending, Collected, InputError, LineFailure, Located};
+
+/// Lines read before a batch is handed to the pool.
+const BATCH_LINES: usize = 16 * 1024;
+
+/// Like [`collect_all_from`](crate::collect_all_from), but parses the lines on
+/// rayon's thread pool while this thread keeps reading.
+///
+/// Lines are read in batches on the calling thread, so `reader` need not be
+/// `Send`, and each batch is parsed in parallel as soon as it is read. The
+/// values and failures come back in input order, exactly as
+/// `collect_all_from` would return them. This pays off for huge inputs when
+/// parsing, not reading, is the bottleneck.
+///
+/// # Usage:
+/// ```
+/// use input_macro::collect_all_parallel_from;
+///
+/// let input: String = (1..=1000).map(|n| format!("{}\n", n)).collect();
+/// let collected = collect_all_parallel_from::<_, u64>(&mut input.as_bytes()).unwrap();
+/// assert_eq!(collected.values.iter().sum::<u64>(), 500_500);
+/// ```
+pub fn collect_all_parallel_from<R, T>(reader: &mut R) -> io::Result<Collected<T, T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr + Send,
+    T::Err: Send,
+{
+    // The pool's threads have limits of their own
+    let max_len = crate::limits().max_line_len;
+    let (sender, receiver) = mpsc::channel();
+    rayon::in_place_scope(|scope| -> io::Result<()> {
+        let mut first_line = 1;
+        for batch in 0.. {
+            let lines = read_batch(reader)?;
+            if lines.is_empty() {
+                break;
+            }
+            let sender = sender.clone();
+            let start = first_line;
+            first_line += lines.len();
+            scope.spawn(move |_| {
+                let parsed: Vec<_> = lines
+                    .into_par_iter()
+                    .enumerate()
+                    .map(|(i, text)| {
+                        let Some(text) = text else {
+                            return Err(LineFailure {
+                                line: start + i,
+                                text: String::new(),
+                                error: InputError::TooLong(max_len),
+                            });
+                        };
+                        text.parse::<T>().map_err(|error| LineFailure {
+                            line: start + i,
+                            error: InputError::parse(error, &text),
+                            text,
+                        })
+                    })
+                    .collect();
+                // The receiver outlives the scope
+                let _ = sender.send((batch, parsed));
+            });
+        }
+        Ok(())
+    })?;
+    drop(sender);
+
+    let mut batches: Vec<_> = receiver.into_iter().collect();
+    batches.sort_unstable_by_key(|(batch, _)| *batch);
+    let mut collected = Collected {
+        values: Vec::new(),
+        failures: Vec::new(),
+    };
+    for (_, parsed) in batches {
+        for result in parsed {
+            match result {
+                Ok(value) => collected.values.push(value),
+                Err(failure) => collected.failures.push(failure),
+            }
+        }
+    }
+    Ok(collected)
+}
+
+/// A convenience wrapper around [`collect_all_parallel_from`] that reads all of stdin.
+#[track_caller]
+pub fn collect_all_parallel<T>() -> Result<Collected<T, T::Err>, Located<io::Error>>
+where
+    T: FromStr + Send,
+    T::Err: Send,
+{
+    crate::__with_stdin(|reader| collect_all_parallel_from(reader))
+}
+
+/// Reads up to [`BATCH_LINES`] lines, without their line endings, with
+/// `None` for each line over the limits.
+fn read_batch<R: BufRead + ?Sized>(reader: &mut R) -> io::Result<Vec<Option<String>>> {
+    let mut lines = Vec::with_capacity(BATCH_LINES);
+    while lines.len() < BATCH_LINES {
+        let mut line = String::new();
+        match crate::limits::read_line_or_skip(reader, &mut line)? {
+            Some(0) => break,
+            Some(_) => {
+                line.truncate(strip_line_ending(&line).len());
+                lines.push(Some(line));
+            }
+            None => lines.push(None),
+        }
+    }
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collect_all_from;
+    use std::io::Cursor;
+
+    /// Across several batches, the result matches the sequential one.
+    #[test]
+    fn test_matches_sequential() {
+        let input: String = (0..BATCH_LINES * 3 + 5)
+            .map(|n| match n % 1000 {
+                7 => "bad\n".to_string(),
+                _ => format!("{}\r\n", n),
+            })
+            .collect();
+        let parallel = collect_all_parallel_from::<_, usize>(&mut Cursor::new(&input)).unwrap();
+        let sequential = collect_all_from::<_, usize>(&mut Cursor::new(&input)).unwrap();
+        assert_eq!(parallel.values, sequential.values);
+        let lines = |c: &Collected<usize, _>| -> Vec<(usize, String)> {
+            c.failures
+                .iter()
+                .map(|f| (f.line, f.text.clone()))
+                .collect()
+        };
+        assert_eq!(lines(&parallel), lines(&sequential));
+        assert_eq!(parallel.failures[1].line, 1008);
+    }
+
+    /// An over-long line is reported as the sequential read reports it.
+    #[test]
+    fn test_too_long() {
+        let _limits = crate::limits::use_limits(Some(crate::Limits { max_line_len: 4 }));
+        let input = "1\n123456789\n3\n";
+        let collected = collect_all_parallel_from::<_, i32>(&mut Cursor::new(input)).unwrap();
+        assert_eq!(collected.values, [1, 3]);
+        assert_eq!(collected.failures[0].line, 2);
+        assert!(matches!(
+            collected.failures[0].error,
+            InputError::TooLong(4)
+        ));
+    }
+
+    /// Empty input yields an empty, clean result.
+    #[test]
+    fn test_empty() {
+        let collected = collect_all_parallel_from::<_, i32>(&mut Cursor::new("")).unwrap();
+        assert!(collected.values.is_empty() && collected.is_clean());
+    }
+}
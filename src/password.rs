@@ -0,0 +1,130 @@
+//! Hidden and masked input for secrets, behind the `rpassword` feature.
+
+use std::convert::Infallible;
+use std::fmt::Arguments;
+use std::io;
+
+use rpassword::{Config, ConfigBuilder};
+
+use crate::{InputError, Located};
+
+/// Reads a secret from the terminal without echoing it.
+///
+/// The prompt, if any, is written to the terminal rather than stdout, so it
+/// stays visible even when stdout is redirected. Pressing Ctrl-D on an empty
+/// line returns `Err(InputError::Eof)`.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::read_password;
+///
+/// let secret = read_password(Some(format_args!("Password: "))).unwrap();
+/// ```
+#[track_caller]
+pub fn read_password(
+    prompt: Option<Arguments<'_>>,
+) -> Result<String, Located<InputError<Infallible>>> {
+    crate::source::located(read_secret(
+        prompt,
+        ConfigBuilder::new().password_feedback_hide().build(),
+    ))
+}
+
+/// Reads a secret from the terminal, echoing `mask` for each typed character.
+///
+/// The terminal is put in raw mode for the duration of the read, so Backspace
+/// removes a mask character as expected. Otherwise behaves like [`read_password`].
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::read_masked;
+///
+/// let pin = read_masked(Some(format_args!("PIN: ")), '*').unwrap();
+/// ```
+#[track_caller]
+pub fn read_masked(
+    prompt: Option<Arguments<'_>>,
+    mask: char,
+) -> Result<String, Located<InputError<Infallible>>> {
+    crate::source::located(read_secret(
+        prompt,
+        ConfigBuilder::new().password_feedback_mask(mask).build(),
+    ))
+}
+
+/// Reads a secret using `config`, mapping rpassword's errors into `InputError`.
+pub(crate) fn read_secret(
+    prompt: Option<Arguments<'_>>,
+    config: Config,
+) -> Result<String, InputError<Infallible>> {
+    let result = match prompt {
+        Some(prompt_args) => {
+            rpassword::prompt_password_with_config(crate::source::affixed(prompt_args), config)
+        }
+        None => rpassword::read_password_with_config(config),
+    };
+    result.map_err(|e| match e.kind() {
+        io::ErrorKind::UnexpectedEof => InputError::Eof,
+        _ => InputError::Io(e),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::sync::{Arc, Mutex};
+
+    /// The secret is returned without its line ending.
+    #[test]
+    fn test_read_secret() {
+        let config = ConfigBuilder::new()
+            .input_reader(Cursor::new("hunter2\n"))
+            .output_discard()
+            .build();
+        let secret = read_secret(Some(format_args!("Password: ")), config).unwrap();
+        assert_eq!(secret, "hunter2");
+    }
+
+    /// Masked reads return the typed characters, not the mask.
+    #[test]
+    fn test_read_secret_masked() {
+        let config = ConfigBuilder::new()
+            .password_feedback_mask('#')
+            .input_reader(Cursor::new("1234\n"))
+            .output_discard()
+            .build();
+        assert_eq!(read_secret(None, config).unwrap(), "1234");
+    }
+
+    /// A shared buffer that rpassword can own as its output.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// The prompt gets the prefix and suffix like any other.
+    #[test]
+    fn test_read_secret_affixes() {
+        let output = Shared::default();
+        let config = ConfigBuilder::new()
+            .input_reader(Cursor::new("hunter2\n"))
+            .output_writer(output.clone())
+            .build();
+        crate::set_prompt_prefix("myctl> ");
+        crate::set_prompt_suffix(": ");
+        let secret = read_secret(Some(format_args!("Password")), config);
+        crate::set_prompt_prefix("");
+        crate::set_prompt_suffix("");
+        assert_eq!(secret.unwrap(), "hunter2");
+        assert_eq!(&*output.0.lock().unwrap(), b"myctl> Password: ");
+    }
+}
@@ -0,0 +1,241 @@
+use std::str::FromStr;
+
+use crate::BoxError;
+
+/// The error reported by [`read_pattern!`](crate::read_pattern) when a line
+/// does not fit its pattern.
+#[derive(Debug)]
+pub enum PatternError {
+    /// The literal text `expected` was not found at or after byte `offset`
+    /// of the line.
+    Literal { expected: String, offset: usize },
+    /// Field `index` (zero-based) held `text`, which failed to parse.
+    Field {
+        index: usize,
+        text: String,
+        error: BoxError,
+    },
+}
+
+impl std::fmt::Display for PatternError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatternError::Literal { expected, offset } => {
+                write!(f, "expected {:?} at byte {}", expected, offset)
+            }
+            PatternError::Field { index, text, error } => {
+                write!(f, "field {} ({:?}): {}", index, text, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+/// Checks that `pattern` has `fields` placeholders with literal text between
+/// each two of them, panicking otherwise.
+///
+/// [`read_pattern!`](crate::read_pattern) calls this in a constant, so a bad
+/// pattern fails to compile.
+#[doc(hidden)]
+pub const fn __check_pattern(pattern: &str, fields: usize) {
+    let bytes = pattern.as_bytes();
+    let mut placeholders = 0;
+    // Bytes of literal text since the last placeholder
+    let mut literal = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'{' && i + 1 < bytes.len() && bytes[i + 1] == b'}' {
+            if placeholders > 0 && literal == 0 {
+                panic!("pattern has adjacent `{{}}` with no separator");
+            }
+            placeholders += 1;
+            literal = 0;
+            i += 2;
+        } else {
+            literal += 1;
+            i += 1;
+        }
+    }
+    if placeholders != fields {
+        panic!("pattern needs one `{{}}` per field");
+    }
+}
+
+/// Splits `line` into the text matched by each `{}` of `pattern`, trimmed.
+///
+/// Each field runs to the first occurrence of the literal after it, and the
+/// last one to the end of the line, which must end with the pattern's final
+/// literal. `pattern` must pass [`__check_pattern`] for `fields`.
+#[doc(hidden)]
+pub fn __pattern_fields<'a>(
+    pattern: &str,
+    line: &'a str,
+    fields: usize,
+) -> Result<Vec<&'a str>, PatternError> {
+    let literals: Vec<&str> = pattern.split("{}").collect();
+    let missing = |expected: &str, offset| PatternError::Literal {
+        expected: expected.to_string(),
+        offset,
+    };
+
+    let mut rest = line
+        .strip_prefix(literals[0])
+        .ok_or_else(|| missing(literals[0], 0))?;
+    let mut values = Vec::with_capacity(fields);
+    for literal in &literals[1..fields] {
+        let offset = line.len() - rest.len();
+        let end = rest.find(literal).ok_or_else(|| missing(literal, offset))?;
+        values.push(rest[..end].trim());
+        rest = &rest[end + literal.len()..];
+    }
+    let last = literals[fields];
+    let offset = line.len() - rest.len();
+    let field = rest
+        .strip_suffix(last)
+        .ok_or_else(|| missing(last, offset))?;
+    values.push(field.trim());
+    Ok(values)
+}
+
+/// Parses the next field for [`read_pattern!`](crate::read_pattern), advancing `index`.
+#[doc(hidden)]
+pub fn __pattern_field<'a, T, I>(fields: &mut I, index: &mut usize) -> Result<T, PatternError>
+where
+    I: Iterator<Item = &'a str>,
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    let at = *index;
+    *index += 1;
+    // `__pattern_fields` yields exactly one text per field
+    let text = fields.next().unwrap_or_default();
+    text.parse().map_err(|error: T::Err| PatternError::Field {
+        index: at,
+        text: text.to_string(),
+        error: error.into(),
+    })
+}
+
+/// A macro that:
+/// - reads one line from stdin,
+/// - matches it against a pattern in which each `{}` is a field and the rest
+///   is literal text,
+/// - parses each field, trimmed, into its annotated type,
+/// - returns the values as a tuple, or `Ok(None)` on EOF.
+///
+/// Fields are positioned by the literal text around them, as with `sscanf`,
+/// so they may hold spaces. A line that does not fit, or a field that fails
+/// to parse, is reported as `InputError::Parse` holding a [`PatternError`].
+///
+/// # Usage:
+/// ```
+/// # use input_macro::{read_pattern, set_input_source};
+/// # set_input_source(Box::new(std::io::Cursor::new("12-31: closed for holidays\n")));
+/// // Reads e.g. "12-31: closed for holidays"
+/// let (month, day, note) = read_pattern!("{}-{}: {}", month: u32, day: u32, note: String)
+///     .unwrap()
+///     .unwrap();
+/// assert_eq!((month, day, note.as_str()), (12, 31, "closed for holidays"));
+/// ```
+///
+/// The pattern is checked when the macro is compiled: it needs one `{}` per
+/// field, with literal text between each two, so neither of these builds.
+/// ```compile_fail
+/// # use input_macro::read_pattern;
+/// let _ = read_pattern!("{} {}", x: u8);
+/// ```
+/// ```compile_fail
+/// # use input_macro::read_pattern;
+/// let _ = read_pattern!("{}{}", x: u8, y: u8);
+/// ```
+#[macro_export]
+macro_rules! read_pattern {
+    (@read $prompt:expr; $pattern:literal, $($name:ident : $t:ty),+) => {
+        match $crate::__with_stdin(|reader| $crate::read_with_from(
+            reader,
+            $prompt,
+            |line: &str| -> Result<_, $crate::PatternError> {
+                const COUNT: usize = [$(stringify!($name)),+].len();
+                const _: () = $crate::__check_pattern($pattern, COUNT);
+                let mut fields = $crate::__pattern_fields($pattern, line, COUNT)?.into_iter();
+                let mut index = 0;
+                Ok(($($crate::__pattern_field::<$t, _>(&mut fields, &mut index)?,)+))
+            },
+        )) {
+            Ok(val) => Ok(Some(val)),
+            Err($crate::Located {
+                error: $crate::InputError::Eof,
+                ..
+            }) => Ok(None),
+            Err(err) => Err(err),
+        }
+    };
+    ($pattern:literal, $($name:ident : $t:ty),+ $(,)?) => {
+        $crate::read_pattern!(@read None; $pattern, $($name: $t),+)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{reset_input_source, set_input_source, InputError, Located};
+    use std::io::Cursor;
+
+    /// Fields split on the literals around them, which may repeat inside the last field.
+    #[test]
+    fn test_pattern_fields() {
+        let fields = __pattern_fields("[{}] {}: {}", "[ERROR] db: retry: 3", 3).unwrap();
+        assert_eq!(fields, ["ERROR", "db", "retry: 3"]);
+        assert_eq!(__pattern_fields("{}", " x ", 1).unwrap(), ["x"]);
+        assert_eq!(__pattern_fields("({},{})", "(1,2)", 2).unwrap(), ["1", "2"]);
+    }
+
+    /// Missing literals are reported with where they were expected.
+    #[test]
+    fn test_pattern_mismatch() {
+        let err = __pattern_fields("{}-{}", "12/31", 2).unwrap_err();
+        assert!(
+            matches!(err, PatternError::Literal { ref expected, offset: 0 } if expected == "-")
+        );
+        let err = __pattern_fields("({},{})", "(1,2", 2).unwrap_err();
+        assert!(matches!(err, PatternError::Literal { offset: 3, .. }));
+        assert_eq!(err.to_string(), "expected \")\" at byte 3");
+    }
+
+    /// The macro parses each field into its own type and reports bad fields by index.
+    #[test]
+    fn test_read_pattern() {
+        set_input_source(Box::new(Cursor::new("7 - x\n")));
+        let res = crate::read_pattern!("{} - {}", a: u8, b: u8);
+        assert!(matches!(
+            res,
+            Err(Located {
+                error: InputError::Parse {
+                    source: PatternError::Field { index: 1, .. },
+                    ..
+                },
+                ..
+            })
+        ));
+        assert_eq!(crate::read_pattern!("{} - {}", a: u8, b: u8).unwrap(), None);
+        reset_input_source();
+    }
+
+    /// Patterns are checked in constants, and one with the wrong number of
+    /// placeholders is rejected.
+    #[test]
+    #[should_panic(expected = "needs one `{}` per field")]
+    fn test_pattern_arity() {
+        const _: () = __check_pattern("[{}] {}: {}", 3);
+        const _: () = __check_pattern("{{}}", 1);
+        __check_pattern("{}", 2);
+    }
+
+    /// Placeholders with nothing between them are rejected.
+    #[test]
+    #[should_panic(expected = "adjacent `{}`")]
+    fn test_pattern_adjacent() {
+        __check_pattern("{}{}", 2);
+    }
+}
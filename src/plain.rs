@@ -0,0 +1,76 @@
+use std::fmt::Arguments;
+use std::io::BufRead;
+
+use serde::de::DeserializeOwned;
+
+use crate::{read_trimmed_line, InputError, Located};
+
+/// Reads one line and deserializes it with `serde_plain`, for scalar-like
+/// types that implement `Deserialize` but not `FromStr`.
+///
+/// Unit-variant enums match their serialized names (honoring
+/// `#[serde(rename_all = ...)]`), and numbers, `bool`, and strings parse as
+/// they would with `FromStr`.
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_de_from;
+///
+/// #[derive(Debug, PartialEq, serde::Deserialize)]
+/// #[serde(rename_all = "kebab-case")]
+/// enum Mode {
+///     ReadOnly,
+///     ReadWrite,
+/// }
+///
+/// let mut reader = std::io::Cursor::new("read-write\n");
+/// let mode: Mode = read_de_from(&mut reader, None).unwrap();
+/// assert_eq!(mode, Mode::ReadWrite);
+/// ```
+pub fn read_de_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<T, InputError<serde_plain::Error>>
+where
+    R: BufRead + ?Sized,
+    T: DeserializeOwned,
+{
+    let mut input = String::new();
+    let line = read_trimmed_line(reader, prompt, &mut input)?;
+    serde_plain::from_str(line).map_err(|source| InputError::parse(source, line))
+}
+
+/// A convenience wrapper around [`read_de_from`] that reads from stdin.
+#[track_caller]
+pub fn read_de<T: DeserializeOwned>(
+    prompt: Option<Arguments<'_>>,
+) -> Result<T, Located<InputError<serde_plain::Error>>> {
+    crate::__with_stdin(|reader| read_de_from(reader, prompt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Level {
+        Debug,
+        Info,
+    }
+
+    /// Serde-only enums and plain scalars deserialize from a line.
+    #[test]
+    fn test_read_de() {
+        let mut reader = Cursor::new("info\ndebug\n42\nloud\n");
+        let level: Level = read_de_from(&mut reader, None).unwrap();
+        assert_eq!(level, Level::Info);
+        let level: Level = read_de_from(&mut reader, None).unwrap();
+        assert_eq!(level, Level::Debug);
+        let n: u8 = read_de_from(&mut reader, None).unwrap();
+        assert_eq!(n, 42);
+        let res = read_de_from::<_, Level>(&mut reader, None);
+        assert!(matches!(res, Err(InputError::Parse { .. })));
+    }
+}
@@ -0,0 +1,119 @@
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::{BoxError, Input, InputError, Located};
+
+/// A type that can be filled in interactively, one question per field.
+///
+/// With the `derive` feature, `#[derive(Prompt)]` implements this for structs
+/// with named fields. Each field can carry a `#[prompt(...)]` attribute with
+/// `text = "..."` (the prompt), `default = "..."` (parsed into the field on an
+/// empty line), and `validate = path` (a `bool` or `Result<(), String>` check).
+///
+/// # Usage:
+/// ```no_run
+/// # #[cfg(feature = "derive")]
+/// # fn run() {
+/// use input_macro::Prompt;
+///
+/// fn unprivileged(port: &u16) -> bool {
+///     *port >= 1024
+/// }
+///
+/// #[derive(Prompt)]
+/// struct Server {
+///     #[prompt(text = "Host: ", default = "localhost")]
+///     host: String,
+///     #[prompt(text = "Port: ", default = "8080", validate = unprivileged)]
+///     port: u16,
+/// }
+///
+/// let server = Server::prompt().unwrap();
+/// # }
+/// ```
+pub trait Prompt: Sized {
+    /// Asks for every field, reading answers from `reader`.
+    fn prompt_from(reader: &mut dyn BufRead) -> Result<Self, InputError<BoxError>>;
+
+    /// Asks for every field on stdin.
+    #[track_caller]
+    fn prompt() -> Result<Self, Located<InputError<BoxError>>> {
+        crate::__with_stdin(|reader| Self::prompt_from(reader))
+    }
+}
+
+/// Runs one field's read for `#[derive(Prompt)]`, boxing its parse error.
+#[doc(hidden)]
+pub fn __prompt_field<T>(input: Input<'_, T>) -> Result<T, InputError<BoxError>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug + Into<BoxError>,
+{
+    input.read().map_err(|err| err.into_inner().boxed())
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn unprivileged(port: &u16) -> bool {
+        *port >= 1024
+    }
+
+    #[derive(Debug, PartialEq, crate::Prompt)]
+    struct Server {
+        #[prompt(text = "Host: ", default = "localhost")]
+        host: String,
+        #[prompt(default = "8080", validate = unprivileged)]
+        port: u16,
+        verbose: bool,
+    }
+
+    /// Fields are asked in order, with defaults for empty answers.
+    #[test]
+    fn test_derive_prompt() {
+        let mut reader = Cursor::new("\n9000\ntrue\n");
+        let server = Server::prompt_from(&mut reader).unwrap();
+        assert_eq!(
+            server,
+            Server {
+                host: "localhost".to_string(),
+                port: 9000,
+                verbose: true,
+            }
+        );
+    }
+
+    /// A field's parse or validation failure stops the prompt.
+    #[test]
+    fn test_derive_prompt_errors() {
+        let mut reader = Cursor::new("db\n80\ntrue\n");
+        let res = Server::prompt_from(&mut reader);
+        assert!(matches!(res, Err(InputError::Invalid(_))));
+
+        let mut reader = Cursor::new("db\nhttp\n");
+        let res = Server::prompt_from(&mut reader);
+        assert!(matches!(res, Err(InputError::Parse { .. })));
+    }
+
+    #[derive(Debug, crate::Prompt)]
+    struct Retries {
+        #[prompt(default = "many")]
+        #[allow(dead_code)]
+        count: u8,
+    }
+
+    /// A `default` that does not parse is reported instead of panicking.
+    #[test]
+    fn test_derive_prompt_bad_default() {
+        let res = Retries::prompt_from(&mut Cursor::new("3\n"));
+        match res {
+            Err(InputError::Invalid(message)) => assert_eq!(
+                message,
+                "invalid `default` for field `count`: invalid digit found in string"
+            ),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
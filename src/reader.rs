@@ -0,0 +1,229 @@
+use std::convert::Infallible;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::limits::use_limits;
+use crate::stats::Meter;
+use crate::{read_input_into, read_trimmed_line, Input, InputError, Limits, ReadEvent, ReadStats};
+
+/// A line reader that owns its reader and reuses one buffer for every line.
+///
+/// Each [`read_input_from`](crate::read_input_from) call allocates a fresh
+/// `String`; reading through an `InputReader` instead allocates only when a
+/// line is longer than any before it, which matters in loops over millions
+/// of piped lines.
+///
+/// The reader also keeps [`ReadStats`] of what it has read, and can report
+/// each [`ReadEvent`] to a hook as it happens, for services that export
+/// ingestion metrics.
+///
+/// # Usage:
+/// ```
+/// use input_macro::InputReader;
+///
+/// let mut input = InputReader::new(std::io::Cursor::new("3\n10\n20\n30\n"));
+/// let n: usize = input.read().unwrap();
+/// let sum: u64 = (0..n).map(|_| input.read::<u64>().unwrap()).sum();
+/// assert_eq!(sum, 60);
+/// ```
+#[derive(Debug)]
+pub struct InputReader<R> {
+    reader: R,
+    buf: String,
+    meter: Meter,
+    limits: Option<Limits>,
+}
+
+impl<R: BufRead> InputReader<R> {
+    /// Wraps `reader` in a new input reader.
+    pub fn new(reader: R) -> Self {
+        InputReader {
+            reader,
+            buf: String::new(),
+            meter: Meter::default(),
+            limits: None,
+        }
+    }
+
+    /// Reads with `limits` instead of the ones set for the thread with
+    /// [`set_limits`](crate::set_limits).
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = Some(limits);
+    }
+
+    /// Reads the next line and returns it without its line ending, borrowed
+    /// from the internal buffer until the next read.
+    ///
+    /// Returns `Err(InputError::Eof)` once the input is exhausted.
+    pub fn read_line_ref(&mut self) -> Result<&str, InputError<Infallible>> {
+        self.buf.clear();
+        let _limits = use_limits(self.limits);
+        let mut reader = self.meter.wrap(&mut self.reader);
+        let result = read_trimmed_line(&mut reader, None, &mut self.buf);
+        reader.finish();
+        result
+    }
+
+    /// Reads the next line and parses it into `T`, as
+    /// [`read_input_into`] does.
+    pub fn read<T>(&mut self) -> Result<T, InputError<T::Err>>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display + std::fmt::Debug,
+    {
+        let _limits = use_limits(self.limits);
+        let mut reader = self.meter.wrap(&mut self.reader);
+        let result = read_input_into(&mut reader, None, &mut self.buf);
+        let meter = reader.finish();
+        if let Err(InputError::Parse { .. }) = result {
+            meter.emit(ReadEvent::ParseFailure);
+        }
+        result
+    }
+
+    /// Runs `input` on this reader, with its prompt, default, validation, and
+    /// retries, as [`Input::read`] does; a reader set on `input` is ignored.
+    ///
+    /// # Usage:
+    /// ```
+    /// use input_macro::{Input, InputReader};
+    ///
+    /// let mut input = InputReader::new(std::io::Cursor::new("x\n80\n"));
+    /// let port = input.read_input(Input::<u16>::new().max_attempts(3)).unwrap();
+    /// assert_eq!(port, 80);
+    /// assert_eq!(input.stats().retries, 1);
+    /// ```
+    pub fn read_input<T>(&mut self, input: Input<'_, T>) -> Result<T, InputError<T::Err>>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display + std::fmt::Debug,
+    {
+        let _limits = use_limits(self.limits);
+        let mut reader = self.meter.wrap(&mut self.reader);
+        let mut events = Vec::new();
+        let result = input.read_opt_observed(&mut reader, &mut |err, retrying| {
+            if let InputError::Parse { .. } = err {
+                events.push(ReadEvent::ParseFailure);
+            }
+            if retrying {
+                events.push(ReadEvent::Retry);
+            }
+        });
+        let meter = reader.finish();
+        for event in events {
+            meter.emit(event);
+        }
+        result?.ok_or(InputError::Eof)
+    }
+
+    /// Returns the counts of what has been read so far.
+    pub fn stats(&self) -> ReadStats {
+        self.meter.stats
+    }
+
+    /// Calls `hook` with each [`ReadEvent`] from now on, replacing any hook
+    /// set before, e.g. to feed a metrics system's counters.
+    ///
+    /// # Usage:
+    /// ```
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    /// use input_macro::{InputReader, ReadEvent};
+    ///
+    /// static FAILURES: AtomicU64 = AtomicU64::new(0);
+    ///
+    /// let mut input = InputReader::new(std::io::Cursor::new("1\nx\n3\n"));
+    /// input.set_event_hook(|event| {
+    ///     if let ReadEvent::ParseFailure = event {
+    ///         FAILURES.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    /// });
+    /// while !matches!(input.read::<u8>(), Err(input_macro::InputError::Eof)) {}
+    /// assert_eq!(FAILURES.load(Ordering::Relaxed), 1);
+    /// ```
+    pub fn set_event_hook(&mut self, hook: impl FnMut(&ReadEvent) + Send + 'static) {
+        self.meter.hook = Some(Box::new(hook));
+    }
+
+    /// Returns the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Consumes the input reader, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Lines are read one at a time through the same buffer, which keeps its capacity.
+    #[test]
+    fn test_reuses_buffer() {
+        let mut input = InputReader::new(Cursor::new("a longer first line\r\nb\n"));
+        assert_eq!(input.read_line_ref().unwrap(), "a longer first line");
+        let (capacity, storage) = (input.buf.capacity(), input.buf.as_ptr());
+        assert_eq!(input.read_line_ref().unwrap(), "b");
+        assert_eq!(
+            (input.buf.capacity(), input.buf.as_ptr()),
+            (capacity, storage)
+        );
+        assert!(matches!(input.read_line_ref(), Err(InputError::Eof)));
+    }
+
+    /// Lines, bytes, failures, retries, and EOFs are counted and reported.
+    #[test]
+    fn test_stats() {
+        let mut input = InputReader::new(Cursor::new("1\r\nx\n\nabc\n80\n9"));
+        let (sender, events) = std::sync::mpsc::channel();
+        input.set_event_hook(move |event| sender.send(*event).unwrap());
+        assert_eq!(input.read::<u8>().unwrap(), 1);
+        assert!(input.read::<u8>().is_err());
+        let port = Input::<u16>::new()
+            .skip_blank(true)
+            .max_attempts(3)
+            .validate(|p| *p < 1024);
+        assert_eq!(input.read_input(port).unwrap(), 80);
+        assert_eq!(input.read_line_ref().unwrap(), "9");
+        assert!(input.read_line_ref().is_err());
+        assert!(input.read::<u8>().is_err());
+        assert_eq!(
+            input.stats(),
+            ReadStats {
+                lines: 6,
+                bytes: 14,
+                parse_failures: 2,
+                retries: 1,
+                eofs: 2,
+            }
+        );
+        let events: Vec<_> = events.try_iter().collect();
+        assert_eq!(
+            events[..2],
+            [ReadEvent::Line { bytes: 3 }, ReadEvent::Line { bytes: 2 }]
+        );
+        assert_eq!(events[2], ReadEvent::ParseFailure);
+        assert_eq!(events.len(), 11);
+    }
+
+    /// A line that fails to parse is still consumed.
+    #[test]
+    fn test_read_parse_error() {
+        let mut input = InputReader::new(Cursor::new("x\n7\n"));
+        assert!(matches!(input.read::<u8>(), Err(InputError::Parse { .. })));
+        assert_eq!(input.read::<u8>().unwrap(), 7);
+    }
+
+    /// A reader's own limits apply to its reads only.
+    #[test]
+    fn test_reader_limits() {
+        let mut input = InputReader::new(Cursor::new("12345\n6\n"));
+        input.set_limits(Limits { max_line_len: 4 });
+        assert!(matches!(input.read::<u32>(), Err(InputError::TooLong(4))));
+        assert_eq!(crate::limits(), Limits::default());
+        assert_eq!(input.read::<u8>().unwrap(), 6);
+    }
+}
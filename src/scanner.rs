@@ -0,0 +1,128 @@
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::limits::read_line_limited;
+use crate::{InputError, Limits};
+
+/// A whitespace-token reader that ignores line boundaries.
+///
+/// Lines are buffered internally and handed out one token at a time, so input
+/// like "a count followed by that many numbers on arbitrary lines" can be read
+/// with repeated calls to [`Scanner::next`].
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::Scanner;
+///
+/// let mut scanner = Scanner::new(std::io::stdin().lock());
+/// let n: usize = scanner.next().unwrap();
+/// let values: Vec<i64> = (0..n).map(|_| scanner.next().unwrap()).collect();
+/// ```
+#[derive(Debug)]
+pub struct Scanner<R> {
+    reader: R,
+    buf: String,
+    pos: usize,
+    limits: Option<Limits>,
+}
+
+impl<R: BufRead> Scanner<R> {
+    /// Wraps `reader` in a new scanner.
+    pub fn new(reader: R) -> Self {
+        Scanner {
+            reader,
+            buf: String::new(),
+            pos: 0,
+            limits: None,
+        }
+    }
+
+    /// Reads with `limits` instead of the ones set for the thread with
+    /// [`set_limits`](crate::set_limits).
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = Some(limits);
+    }
+
+    /// Reads the next whitespace-separated token, reading more lines as needed,
+    /// and parses it into `T`.
+    ///
+    /// Returns `Err(InputError::Eof)` once the input holds no more tokens.
+    /// A token that fails to parse is still consumed.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<T>(&mut self) -> Result<T, InputError<T::Err>>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display + std::fmt::Debug,
+    {
+        loop {
+            let rest = &self.buf[self.pos..];
+            let start = rest.len() - rest.trim_start().len();
+            let rest = &rest[start..];
+            if !rest.is_empty() {
+                let len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                self.pos += start + len;
+                return crate::parse_line(&rest[..len]);
+            }
+
+            self.buf.clear();
+            self.pos = 0;
+            let max_len = self.limits.unwrap_or_else(crate::limits).max_line_len;
+            if read_line_limited(&mut self.reader, &mut self.buf, max_len)? == 0 {
+                return Err(InputError::Eof);
+            }
+        }
+    }
+
+    /// Consumes the scanner, returning the underlying reader.
+    ///
+    /// Any tokens still buffered from the current line are discarded.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Tokens are handed out across line boundaries and blank lines.
+    #[test]
+    fn test_tokens_across_lines() {
+        let mut scanner = Scanner::new(Cursor::new("3\n10 20\n\n  30  \n"));
+        let n: usize = scanner.next().unwrap();
+        let values: Vec<i32> = (0..n).map(|_| scanner.next().unwrap()).collect();
+        assert_eq!(values, vec![10, 20, 30]);
+        assert!(matches!(scanner.next::<i32>(), Err(InputError::Eof)));
+    }
+
+    /// A token that fails to parse is consumed, so scanning can continue.
+    #[test]
+    fn test_parse_error_consumes_token() {
+        let mut scanner = Scanner::new(Cursor::new("x 7"));
+        assert!(matches!(
+            scanner.next::<i32>(),
+            Err(InputError::Parse { .. })
+        ));
+        assert_eq!(scanner.next::<i32>().unwrap(), 7);
+    }
+
+    /// Mixed types can be read from the same line.
+    #[test]
+    fn test_mixed_types() {
+        let mut scanner = Scanner::new(Cursor::new("alice 30 1.5\n"));
+        let name: String = scanner.next().unwrap();
+        let age: u8 = scanner.next().unwrap();
+        let score: f64 = scanner.next().unwrap();
+        assert_eq!((name.as_str(), age, score), ("alice", 30, 1.5));
+    }
+
+    /// A scanner's own limits override the thread's, and a long line is skipped.
+    #[test]
+    fn test_scanner_limits() {
+        let mut scanner = Scanner::new(Cursor::new("1 2 3 4\n5\n"));
+        scanner.set_limits(Limits { max_line_len: 4 });
+        assert!(matches!(scanner.next::<i32>(), Err(InputError::TooLong(4))));
+        assert_eq!(scanner.next::<i32>().unwrap(), 5);
+    }
+}
@@ -0,0 +1,277 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::rc::Rc;
+
+use crate::testing::Installed;
+
+#[derive(Default)]
+struct Shared {
+    keyed: Vec<(String, VecDeque<String>)>,
+    ordered: VecDeque<String>,
+    pending: String,
+}
+
+impl Shared {
+    /// Takes the answer for the prompt written since the previous answer.
+    fn next_answer(&mut self) -> io::Result<Option<String>> {
+        let written = std::mem::take(&mut self.pending);
+        if self.keyed.is_empty() {
+            return Ok(self.ordered.pop_front());
+        }
+        let prompt = written
+            .lines()
+            .map(str::trim)
+            .rfind(|line| !line.is_empty())
+            .unwrap_or_default();
+        // The longest key wins, so `Name` does not answer `Name of pet: `
+        self.keyed
+            .iter_mut()
+            .filter(|(key, answers)| prompt.starts_with(key.as_str()) && !answers.is_empty())
+            .max_by_key(|(key, _)| key.len())
+            .and_then(|(_, answers)| answers.pop_front())
+            .map(Some)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("no answer for prompt {:?}", prompt),
+                )
+            })
+    }
+}
+
+/// Answers read from a file or reader, played into the crate's stdin readers
+/// so a wizard can run unattended, e.g. as `mytool --answers answers.txt`.
+///
+/// Answers are given [keyed](AnswerScript::keyed) by prompt, or
+/// [in order](AnswerScript::in_order). Either way the flow runs exactly as
+/// it would interactively: answers go through the same parsing, validation,
+/// defaults, and retries, and prompts are written (to the script, not the
+/// terminal) so keyed answers can be matched to them.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::{AnswerScript, Form};
+///
+/// fn wizard() -> Form<'static> {
+///     Form::new()
+///         .field::<String>("host", "Host: ")
+///         .field::<u16>("port", "Port: ")
+/// }
+///
+/// let answers = match std::env::args().nth(1).as_deref() {
+///     Some("--answers") => {
+///         let path = std::env::args().nth(2).expect("a path after --answers");
+///         let script = AnswerScript::open(path).unwrap();
+///         script.run(|| wizard().run())
+///     }
+///     _ => wizard().run(),
+/// }
+/// .unwrap();
+/// println!("{:?}", answers.get::<u16>("port"));
+/// ```
+pub struct AnswerScript {
+    shared: Rc<RefCell<Shared>>,
+    line: io::Cursor<Vec<u8>>,
+}
+
+impl AnswerScript {
+    fn new(shared: Shared) -> Self {
+        AnswerScript {
+            shared: Rc::new(RefCell::new(shared)),
+            line: io::Cursor::new(Vec::new()),
+        }
+    }
+
+    /// Reads answers keyed by prompt, one `prompt = answer` per line.
+    ///
+    /// Blank lines and lines starting with `#` are skipped, and both sides
+    /// are trimmed, so `Port =` answers with an empty line, which takes the
+    /// default of a read that has one. An answer is used for the next prompt
+    /// whose last line starts with its key; when several keys match, the
+    /// longest wins. Each answer is used once, and a key given several times
+    /// answers its prompt that many times, in order. A prompt with no answer
+    /// left fails the read with an `ErrorKind::NotFound` I/O error naming the
+    /// prompt, so an incomplete file stops the run rather than hanging it.
+    ///
+    /// Fails if reading fails or a line has no `=`.
+    pub fn keyed(reader: impl BufRead) -> io::Result<Self> {
+        let mut shared = Shared::default();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, answer) = line.split_once('=').ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: expected `prompt = answer`", index + 1),
+                )
+            })?;
+            let (key, answer) = (key.trim(), answer.trim().to_string());
+            match shared.keyed.iter_mut().find(|(k, _)| k == key) {
+                Some((_, answers)) => answers.push_back(answer),
+                None => shared
+                    .keyed
+                    .push((key.to_string(), VecDeque::from([answer]))),
+            }
+        }
+        Ok(AnswerScript::new(shared))
+    }
+
+    /// Reads one answer per line, given to the reads in the order they happen.
+    ///
+    /// Lines are used as they are, without trimming, and a blank line is an
+    /// empty answer. Once the answers run out, reads see EOF.
+    ///
+    /// Fails if reading fails.
+    pub fn in_order(reader: impl BufRead) -> io::Result<Self> {
+        let ordered = reader.lines().collect::<io::Result<_>>()?;
+        Ok(AnswerScript::new(Shared {
+            ordered,
+            ..Shared::default()
+        }))
+    }
+
+    /// Reads [keyed](AnswerScript::keyed) answers from the file at `path`.
+    ///
+    /// An error names the file, e.g. `answers.txt: line 3: expected
+    /// `prompt = answer``, and keeps the original error kind.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        File::open(path)
+            .and_then(|file| AnswerScript::keyed(BufReader::new(file)))
+            .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))
+    }
+
+    /// Runs `f` with the script as this thread's input source and prompt
+    /// output (see [`set_input_source`](crate::set_input_source)), and
+    /// returns its result.
+    ///
+    /// The thread's previous source and output are restored afterwards.
+    pub fn run<T>(self, f: impl FnOnce() -> T) -> T {
+        let output = PromptCapture(Rc::clone(&self.shared));
+        let _guard = Installed::new(Box::new(self), Box::new(output));
+        f()
+    }
+}
+
+impl std::fmt::Debug for AnswerScript {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let shared = self.shared.borrow();
+        f.debug_struct("AnswerScript")
+            .field("keyed", &shared.keyed)
+            .field("ordered", &shared.ordered)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Read for AnswerScript {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        crate::buffered::read_from_buf(self, buf)
+    }
+}
+
+impl BufRead for AnswerScript {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let exhausted = self.line.position() as usize >= self.line.get_ref().len();
+        if exhausted {
+            if let Some(answer) = self.shared.borrow_mut().next_answer()? {
+                self.line = io::Cursor::new(format!("{}\n", answer).into_bytes());
+            }
+        }
+        self.line.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.line.consume(amt);
+    }
+}
+
+/// Prompts are written to a script too, so keyed answers can be matched.
+impl crate::InputSource for AnswerScript {
+    fn is_interactive(&self) -> bool {
+        true
+    }
+}
+
+/// Collects prompt text for the [`AnswerScript`] it belongs to.
+struct PromptCapture(Rc<RefCell<Shared>>);
+
+impl Write for PromptCapture {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .borrow_mut()
+            .pending
+            .push_str(&String::from_utf8_lossy(buf));
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Form, Input, InputError, Located};
+
+    fn form() -> Form<'static> {
+        Form::new()
+            .field::<String>("name", "Name: ")
+            .field::<String>("pet", "Name of pet: ")
+            .step("port", Input::<u16>::new().prompt("Port: ").default(80))
+    }
+
+    /// Keyed answers go to their prompts whatever the file order, with
+    /// defaults, comments, and the longest matching key.
+    #[test]
+    fn test_keyed() {
+        let file = "# setup\nName of pet = Rex\nPort =\n\nName = Alice\n";
+        let script = AnswerScript::keyed(file.as_bytes()).unwrap();
+        let answers = script.run(|| form().run()).unwrap();
+        assert_eq!(answers.get::<String>("name").as_deref(), Some("Alice"));
+        assert_eq!(answers.get::<String>("pet").as_deref(), Some("Rex"));
+        assert_eq!(answers.get::<u16>("port"), Some(80));
+    }
+
+    /// A prompt without an answer fails instead of waiting; a rejected
+    /// answer is not reused for the retry.
+    #[test]
+    fn test_keyed_missing() {
+        let script = AnswerScript::keyed("Port = high\n".as_bytes()).unwrap();
+        let err = script
+            .run(|| Input::<u16>::new().prompt("Port: ").max_attempts(3).read())
+            .unwrap_err();
+        match err.error {
+            InputError::Io(err) => {
+                assert_eq!(err.kind(), io::ErrorKind::NotFound);
+                assert!(err.to_string().contains("\"Port:\""), "{}", err);
+            }
+            err => panic!("unexpected error: {:?}", err),
+        }
+        let err = AnswerScript::keyed("Port: 80\n".as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "line 1: expected `prompt = answer`");
+    }
+
+    /// Ordered answers are used as given, then reads see EOF.
+    #[test]
+    fn test_in_order() {
+        let script = AnswerScript::in_order(" Bob\nTom\n\n".as_bytes()).unwrap();
+        let (answers, rest) = script.run(|| (form().run(), crate::read_input::<String>()));
+        let answers = answers.unwrap();
+        assert_eq!(answers.get::<String>("name").as_deref(), Some(" Bob"));
+        assert_eq!(answers.get::<u16>("port"), Some(80));
+        assert!(matches!(
+            rest,
+            Err(Located {
+                error: InputError::Eof,
+                ..
+            })
+        ));
+    }
+}
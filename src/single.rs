@@ -0,0 +1,90 @@
+use std::fmt::Arguments;
+use std::io::BufRead;
+
+use crate::{read_trimmed_line, InputError, Located};
+
+/// The error returned when a line does not hold exactly one character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CharInputError {
+    /// The line was empty or only whitespace.
+    Empty,
+    /// The line held more than one character; holds the trimmed line.
+    TooMany(String),
+}
+
+impl std::fmt::Display for CharInputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CharInputError::Empty => write!(f, "expected a single character, got nothing"),
+            CharInputError::TooMany(input) => {
+                write!(f, "expected a single character, got {:?}", input)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CharInputError {}
+
+/// Prints the optional prompt, then reads a line that must hold exactly one
+/// character, ignoring surrounding whitespace.
+///
+/// Fails with [`InputError::Parse`] holding a [`CharInputError`] when the
+/// line is blank or longer than one character.
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_char_from;
+///
+/// let mut reader = std::io::Cursor::new(" q \n");
+/// let action = read_char_from(&mut reader, Some(format_args!("(a)dd, (d)elete, (q)uit: ")));
+/// assert_eq!(action.unwrap(), 'q');
+/// ```
+pub fn read_char_from<R>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<char, InputError<CharInputError>>
+where
+    R: BufRead + ?Sized,
+{
+    let mut input = String::new();
+    let line = read_trimmed_line(reader, prompt, &mut input)?;
+    let answer = line.trim();
+    let mut chars = answer.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        (None, _) => Err(InputError::parse(CharInputError::Empty, line)),
+        (Some(_), Some(_)) => Err(InputError::parse(
+            CharInputError::TooMany(answer.to_string()),
+            line,
+        )),
+    }
+}
+
+/// A convenience wrapper around [`read_char_from`] that reads from stdin.
+#[track_caller]
+pub fn read_char(
+    prompt: Option<Arguments<'_>>,
+) -> Result<char, Located<InputError<CharInputError>>> {
+    crate::__with_stdin(|reader| read_char_from(reader, prompt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Blank and multi-character lines are told apart.
+    #[test]
+    fn test_read_char() {
+        let mut reader = Cursor::new("a\n  \nadd\n\u{e9}\n");
+        let mut read = || read_char_from(&mut reader, None).map_err(InputError::into_parse);
+        assert_eq!(read(), Ok('a'));
+        assert_eq!(read(), Err(Some(CharInputError::Empty)));
+        assert_eq!(
+            read(),
+            Err(Some(CharInputError::TooMany("add".to_string())))
+        );
+        assert_eq!(read(), Ok('\u{e9}'));
+        assert_eq!(read(), Err(None));
+    }
+}
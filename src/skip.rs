@@ -0,0 +1,144 @@
+use std::fmt::Arguments;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::{parse_line, read_raw_line, strip_line_ending, InputError, Located};
+
+/// Returns `true` for empty and whitespace-only lines.
+pub(crate) fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Common comment prefixes for file-style input: `#`, `//`, and `;`.
+pub const COMMENT_PREFIXES: &[&str] = &["#", "//", ";"];
+
+/// Returns `true` if `line`, ignoring leading whitespace, starts with one of
+/// `prefixes`.
+pub(crate) fn is_comment(line: &str, prefixes: &[&str]) -> bool {
+    let line = line.trim_start();
+    prefixes.iter().any(|prefix| line.starts_with(prefix))
+}
+
+/// Prints the optional prompt once, then reads lines into `buf` until one is
+/// not `skipped`, returning it with its line ending.
+/// Returns `Err(InputError::Eof)` if the input ends first.
+pub(crate) fn read_line_skipping<'a, R, E>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    buf: &'a mut String,
+    mut skipped: impl FnMut(&str) -> bool,
+) -> Result<&'a str, InputError<E>>
+where
+    R: BufRead + ?Sized,
+{
+    read_raw_line(reader, prompt, buf)?;
+    while skipped(buf) {
+        buf.clear();
+        read_raw_line(reader, None, buf)?;
+    }
+    Ok(buf)
+}
+
+/// Like [`read_input_from`](crate::read_input_from), but silently skips empty
+/// and whitespace-only lines, parsing the first line with content.
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_nonblank_from;
+///
+/// let mut reader = std::io::Cursor::new("\n   \n3\n");
+/// assert_eq!(read_nonblank_from::<_, u8>(&mut reader, None).unwrap(), 3);
+/// ```
+pub fn read_nonblank_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let mut input = String::new();
+    let line = read_line_skipping(reader, prompt, &mut input, is_blank)?;
+    parse_line(strip_line_ending(line))
+}
+
+/// A convenience wrapper around [`read_nonblank_from`] that reads from stdin.
+#[track_caller]
+pub fn read_nonblank<T>(prompt: Option<Arguments<'_>>) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::__with_stdin(|reader| read_nonblank_from(reader, prompt))
+}
+
+/// Like [`read_input_from`](crate::read_input_from), but silently skips lines
+/// whose first non-whitespace characters are one of `prefixes`, such as
+/// [`COMMENT_PREFIXES`].
+///
+/// # Usage:
+/// ```
+/// use input_macro::{read_uncommented_from, COMMENT_PREFIXES};
+///
+/// let mut reader = std::io::Cursor::new("# retries\n  ; per host\n3\n");
+/// let retries: u8 = read_uncommented_from(&mut reader, None, COMMENT_PREFIXES).unwrap();
+/// assert_eq!(retries, 3);
+/// ```
+pub fn read_uncommented_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    prefixes: &[&str],
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let mut input = String::new();
+    let line = read_line_skipping(reader, prompt, &mut input, |line| {
+        is_comment(line, prefixes)
+    })?;
+    parse_line(strip_line_ending(line))
+}
+
+/// A convenience wrapper around [`read_uncommented_from`] that reads from stdin.
+#[track_caller]
+pub fn read_uncommented<T>(
+    prompt: Option<Arguments<'_>>,
+    prefixes: &[&str],
+) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::__with_stdin(|reader| read_uncommented_from(reader, prompt, prefixes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Blank lines are skipped before each value, and EOF ends the read.
+    #[test]
+    fn test_read_nonblank() {
+        let mut reader = Cursor::new("\r\n one \n\t\ntwo\n\n  \n");
+        let read = |reader: &mut Cursor<&str>| read_nonblank_from::<_, String>(reader, None);
+        assert_eq!(read(&mut reader).unwrap(), " one ");
+        assert_eq!(read(&mut reader).unwrap(), "two");
+        assert!(matches!(read(&mut reader), Err(InputError::Eof)));
+    }
+
+    /// Comment lines are skipped, but blank lines and trailing comments are not.
+    #[test]
+    fn test_read_uncommented() {
+        let mut reader = Cursor::new("// header\n  # note\na # b\n\n-- sql\n");
+        let read = |reader: &mut Cursor<&str>, prefixes| {
+            read_uncommented_from::<_, String>(reader, None, prefixes)
+        };
+        assert_eq!(read(&mut reader, COMMENT_PREFIXES).unwrap(), "a # b");
+        assert_eq!(read(&mut reader, COMMENT_PREFIXES).unwrap(), "");
+        assert!(matches!(read(&mut reader, &["--"]), Err(InputError::Eof)));
+    }
+}
@@ -0,0 +1,665 @@
+use std::cell::{Cell, RefCell};
+use std::fmt::Arguments;
+use std::io::{self, BufRead, IsTerminal, StdinLock, Write};
+use std::marker::PhantomData;
+use std::panic::Location;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use crate::bom::SkipBom;
+use crate::InputError;
+
+thread_local! {
+    static SOURCE: RefCell<Option<Box<dyn BufRead>>> = const { RefCell::new(None) };
+    static OUTPUT: RefCell<Option<Box<dyn Write>>> = const { RefCell::new(None) };
+    static SESSIONS: Cell<usize> = const { Cell::new(0) };
+    static PROMPTS_HIDDEN: Cell<bool> = const { Cell::new(false) };
+    static SESSION_LOCK: RefCell<Option<SkipBom<StdinLock<'static>>>> = const { RefCell::new(None) };
+    /// The text around every prompt, set with [`set_prompt_prefix`] and
+    /// [`set_prompt_suffix`], in that order.
+    static AFFIXES: RefCell<(String, String)> = const { RefCell::new((String::new(), String::new())) };
+}
+
+static STDIN_BOM_CHECKED: AtomicBool = AtomicBool::new(false);
+
+/// A source of line input, which may or may not have a person behind it.
+///
+/// Every `InputSource` is a [`BufRead`], so it can be passed wherever the
+/// crate takes a reader: the `*_from` functions, [`Scanner`](crate::Scanner),
+/// [`Form::run_from`](crate::Form::run_from), or [`set_input_source`]. Code
+/// written against `&mut dyn InputSource` can then be handed locked stdin in
+/// production and a [`Cursor`](std::io::Cursor) or
+/// [`ScriptedReader`](crate::testing::ScriptedReader) in tests. Reading
+/// through [`Input::source`](crate::Input::source) or
+/// [`Form::run_from_source`](crate::Form::run_from_source) also consults
+/// [`is_interactive`](InputSource::is_interactive), so prompts are only
+/// printed when someone is there to answer them.
+///
+/// # Usage:
+/// ```
+/// use input_macro::{Input, InputSource};
+///
+/// fn ask_port(source: &mut dyn InputSource) -> u16 {
+///     Input::new().prompt("Port: ").default(8080).source(source).read().unwrap()
+/// }
+///
+/// // `ask_port(&mut std::io::stdin().lock())` in the real program
+/// assert_eq!(ask_port(&mut std::io::Cursor::new("9000\n")), 9000);
+/// ```
+pub trait InputSource: BufRead {
+    /// Returns whether a person is likely typing this input, rather than it
+    /// coming from a file, pipe, or script. Defaults to `false`.
+    fn is_interactive(&self) -> bool {
+        false
+    }
+}
+
+impl InputSource for &[u8] {}
+
+impl<T: AsRef<[u8]>> InputSource for io::Cursor<T> {}
+
+impl<R: io::Read> InputSource for io::BufReader<R> {}
+
+impl InputSource for io::Empty {}
+
+impl InputSource for io::StdinLock<'_> {
+    fn is_interactive(&self) -> bool {
+        io::stdin().is_terminal()
+    }
+}
+
+impl<S: InputSource + ?Sized> InputSource for Box<S> {
+    fn is_interactive(&self) -> bool {
+        (**self).is_interactive()
+    }
+}
+
+impl<S: InputSource + ?Sized> InputSource for &mut S {
+    fn is_interactive(&self) -> bool {
+        (**self).is_interactive()
+    }
+}
+
+impl<S: InputSource> InputSource for SkipBom<S> {
+    fn is_interactive(&self) -> bool {
+        self.get_ref().is_interactive()
+    }
+}
+
+/// Hides this thread's prompts, unless [`PromptVisibility::Always`] is set,
+/// until the returned guard is dropped.
+pub(crate) fn hide_prompts() -> HidePrompts {
+    HidePrompts(PROMPTS_HIDDEN.replace(true))
+}
+
+/// Restores whether prompts were hidden when dropped.
+pub(crate) struct HidePrompts(bool);
+
+impl Drop for HidePrompts {
+    fn drop(&mut self) {
+        PROMPTS_HIDDEN.set(self.0);
+    }
+}
+
+/// Makes this thread's stdin reads come from `source` instead of stdin.
+///
+/// A UTF-8 byte order mark at the start of `source` is skipped.
+///
+/// The `input!` family of macros and the crate's stdin wrappers (`read_input`,
+/// `confirm`, `Input::read`, `Form::run`, ...) consult the source first, which
+/// lets unit tests script code built on them. The override is per thread, so
+/// tests running in parallel do not see each other's input. The async,
+/// background, and raw-terminal readers still use the real stdin.
+///
+/// # Usage:
+/// ```
+/// use input_macro::{input, set_input_source};
+///
+/// set_input_source(Box::new(std::io::Cursor::new("Alice\n")));
+/// let name: Option<String> = input!("Name: ").unwrap();
+/// assert_eq!(name.as_deref(), Some("Alice"));
+/// ```
+pub fn set_input_source(source: Box<dyn BufRead>) {
+    SOURCE.with(|slot| *slot.borrow_mut() = Some(Box::new(SkipBom::new(source))));
+}
+
+/// Removes this thread's input source, returning it, so reads go back to stdin.
+pub fn reset_input_source() -> Option<Box<dyn BufRead>> {
+    SOURCE.with(|slot| slot.borrow_mut().take())
+}
+
+/// Returns whether this thread has an input source set.
+pub fn has_input_source() -> bool {
+    SOURCE.with(|slot| slot.borrow().is_some())
+}
+
+/// Runs `f` on this thread's input source, or on locked stdin if none is set.
+///
+/// A failure carries the caller's location, as a [`Located`] error.
+#[doc(hidden)]
+#[track_caller]
+pub fn __with_stdin<T, E>(
+    f: impl FnOnce(&mut dyn BufRead) -> Result<T, E>,
+) -> Result<T, Located<E>> {
+    let caller = Location::caller();
+    #[cfg(feature = "tracing")]
+    let read = crate::trace::Read::start();
+    let f = |reader: &mut dyn BufRead| crate::transcript::record_reads(reader, f);
+    // Taken out for the call so that `f` may itself read through `__with_stdin`
+    let result = match SOURCE.with(|slot| slot.borrow_mut().take()) {
+        Some(mut source) => {
+            let result = f(&mut *source);
+            SOURCE.with(|slot| {
+                let mut slot = slot.borrow_mut();
+                // Keep a source that `f` installed in the meantime
+                if slot.is_none() {
+                    *slot = Some(source);
+                }
+            });
+            result
+        }
+        #[cfg(feature = "rustyline")]
+        None if crate::editor::is_active() => crate::editor::with_editor(f),
+        None => {
+            let mut stdin = SESSION_LOCK
+                .with(|slot| slot.borrow_mut().take())
+                .unwrap_or_else(|| {
+                    // Checked lazily, so that `f` prints its prompt before stdin blocks
+                    let checked = STDIN_BOM_CHECKED.load(Ordering::Relaxed);
+                    SkipBom::resume(io::stdin().lock(), checked)
+                });
+            let result = f(&mut stdin);
+            if stdin.checked() {
+                STDIN_BOM_CHECKED.store(true, Ordering::Relaxed);
+            }
+            if SESSIONS.get() > 0 {
+                SESSION_LOCK.with(|slot| {
+                    slot.borrow_mut().get_or_insert(stdin);
+                });
+            }
+            result
+        }
+    };
+    #[cfg(feature = "tracing")]
+    read.finish(result.is_ok());
+    result.map_err(|error| Located {
+        error,
+        location: caller,
+    })
+}
+
+/// The [`Located`] error of a read without a reader that fails with
+/// `InputError<E>`, for functions whose full error type is unwieldy.
+pub type StdinError<E> = Located<InputError<E>>;
+
+/// Attaches the caller's location to a failure of a stdin read that does not
+/// go through [`__with_stdin`].
+#[track_caller]
+pub(crate) fn located<T, E>(result: Result<T, E>) -> Result<T, Located<E>> {
+    let location = Location::caller();
+    result.map_err(|error| Located { error, location })
+}
+
+/// An error from a stdin read, with the location of the call that started
+/// the read, so a failure can be traced to one of many prompts.
+///
+/// Every read that does not take a reader fails with this: the `input!`
+/// family of macros, stdin wrappers such as [`read_input`](crate::read_input),
+/// [`confirm`](crate::confirm()) and [`collect_all`](crate::collect_all), and
+/// the reads from the terminal, an editor, or async stdin. So does
+/// [`Input::read`](crate::Input::read), with or without a reader; the
+/// `*_from` functions that take a reader fail with the plain error.
+///
+/// It derefs to the error it wraps, so methods such as
+/// [`InputError::is_eof`](crate::InputError::is_eof) can be called on it,
+/// displays as that error, and converts into it with `?`.
+///
+/// # Usage:
+/// ```no_run
+/// # use input_macro::input;
+/// if let Err(err) = input!(u16, "Port: ") {
+///     eprintln!("{} (read at {})", err, err.location());
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Located<E> {
+    /// The underlying error.
+    pub error: E,
+    /// Where the failed read was called from.
+    pub location: &'static Location<'static>,
+}
+
+impl<E> Located<E> {
+    /// Returns where the failed read was called from.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Returns the underlying error, dropping its location.
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E> std::ops::Deref for Located<E> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        &self.error
+    }
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for Located<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl<E: std::error::Error> std::error::Error for Located<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
+/// Compares the underlying errors; locations are ignored.
+impl<E: PartialEq> PartialEq for Located<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.error == other.error
+    }
+}
+
+impl<E> From<Located<InputError<E>>> for InputError<E> {
+    fn from(err: Located<InputError<E>>) -> Self {
+        err.error
+    }
+}
+
+impl From<Located<io::Error>> for io::Error {
+    fn from(err: Located<io::Error>) -> Self {
+        err.error
+    }
+}
+
+impl<E> From<Located<InputError<E>>> for io::Error
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    fn from(err: Located<InputError<E>>) -> Self {
+        err.error.into()
+    }
+}
+
+/// Keeps stdin locked for this thread's stdin reads until the returned guard
+/// is dropped.
+///
+/// Without a session, each macro or wrapper call locks stdin anew, which
+/// costs time in tight loops. Within one, the lock is taken by the first read
+/// and reused by the rest, so other threads reading stdin wait for the
+/// session to end. Sessions nest; stdin is unlocked when the outermost one
+/// ends. An [input source](set_input_source) or the line editor still takes
+/// precedence over stdin.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::{input, stdin_session};
+///
+/// let _session = stdin_session();
+/// let mut total = 0u64;
+/// while let Some(n) = input!(u64).unwrap() {
+///     total += n;
+/// }
+/// println!("{}", total);
+/// ```
+pub fn stdin_session() -> StdinSession {
+    SESSIONS.set(SESSIONS.get() + 1);
+    StdinSession {
+        _not_send: PhantomData,
+    }
+}
+
+/// Ends a [`stdin_session`] when dropped.
+#[derive(Debug)]
+#[must_use = "the session ends when the guard is dropped"]
+pub struct StdinSession {
+    // The lock is held per thread
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for StdinSession {
+    fn drop(&mut self) {
+        let sessions = SESSIONS.get() - 1;
+        SESSIONS.set(sessions);
+        if sessions == 0 {
+            SESSION_LOCK.with(|slot| slot.borrow_mut().take());
+        }
+    }
+}
+
+/// Makes this thread's prompts go to `output` instead of stdout.
+///
+/// Like [`set_input_source`], this is per thread and covers every prompt the
+/// crate prints through its readers, blocking or async, which makes the
+/// prompts a flow writes observable in tests.
+pub fn set_prompt_output(output: Box<dyn Write>) {
+    OUTPUT.with(|slot| *slot.borrow_mut() = Some(output));
+}
+
+/// Removes this thread's prompt output, returning it, so prompts go back to stdout.
+pub fn reset_prompt_output() -> Option<Box<dyn Write>> {
+    OUTPUT.with(|slot| slot.borrow_mut().take())
+}
+
+/// Returns whether this thread has a prompt output set.
+#[cfg(feature = "color")]
+pub(crate) fn has_prompt_output() -> bool {
+    OUTPUT.with(|slot| slot.borrow().is_some())
+}
+
+/// Where prompts go when no [prompt output](set_prompt_output) is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PromptTarget {
+    /// Always stdout.
+    #[default]
+    Stdout,
+    /// Always stderr.
+    Stderr,
+    /// Stdout when it is a terminal, otherwise stderr, so that
+    /// `mytool > out.txt` still shows prompts while keeping the data clean.
+    Auto,
+}
+
+impl PromptTarget {
+    /// Decodes a target stored with `as u8`.
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => PromptTarget::Stderr,
+            2 => PromptTarget::Auto,
+            _ => PromptTarget::Stdout,
+        }
+    }
+
+    /// Returns whether this target currently resolves to stderr.
+    fn is_stderr(self) -> bool {
+        match self {
+            PromptTarget::Stdout => false,
+            PromptTarget::Stderr => true,
+            PromptTarget::Auto => !io::stdout().is_terminal(),
+        }
+    }
+}
+
+static TARGET: AtomicU8 = AtomicU8::new(PromptTarget::Stdout as u8);
+
+/// Sets where prompts go for the whole process; the default is stdout.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::{input, set_prompt_target, PromptTarget};
+///
+/// set_prompt_target(PromptTarget::Auto);
+/// let name: Option<String> = input!("Name: ").unwrap();
+/// println!("{}", name.unwrap_or_default());
+/// ```
+pub fn set_prompt_target(target: PromptTarget) {
+    TARGET.store(target as u8, Ordering::Relaxed);
+}
+
+/// Returns the target set with [`set_prompt_target`].
+pub fn prompt_target() -> PromptTarget {
+    PromptTarget::from_u8(TARGET.load(Ordering::Relaxed))
+}
+
+/// Returns whether the prompt target currently resolves to stderr.
+pub(crate) fn prompts_to_stderr() -> bool {
+    prompt_target().is_stderr()
+}
+
+/// Returns `true` if both stdin and stdout are terminals, i.e. a person is
+/// likely typing the answers and reading the output.
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+/// When prompts are printed, unless a [prompt output](set_prompt_output) is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PromptVisibility {
+    /// Only when stdin and the [`PromptTarget`] stream are both terminals, so
+    /// piped invocations do not write prompt text into pipelines.
+    #[default]
+    Interactive,
+    /// Always, even when nobody is there to see them.
+    Always,
+    /// Never.
+    Never,
+}
+
+impl PromptVisibility {
+    /// Decodes a visibility stored with `as u8`.
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => PromptVisibility::Always,
+            2 => PromptVisibility::Never,
+            _ => PromptVisibility::Interactive,
+        }
+    }
+
+    /// Returns whether a prompt written now to `target` should be shown.
+    fn allows(self, target: PromptTarget) -> bool {
+        match self {
+            PromptVisibility::Always => true,
+            PromptVisibility::Never => false,
+            PromptVisibility::Interactive => {
+                io::stdin().is_terminal()
+                    && if target.is_stderr() {
+                        io::stderr().is_terminal()
+                    } else {
+                        io::stdout().is_terminal()
+                    }
+            }
+        }
+    }
+}
+
+static VISIBILITY: AtomicU8 = AtomicU8::new(PromptVisibility::Interactive as u8);
+
+/// Sets when prompts are printed for the whole process; the default is
+/// [`PromptVisibility::Interactive`].
+pub fn set_prompt_visibility(visibility: PromptVisibility) {
+    VISIBILITY.store(visibility as u8, Ordering::Relaxed);
+}
+
+/// Returns the visibility set with [`set_prompt_visibility`].
+pub fn prompt_visibility() -> PromptVisibility {
+    PromptVisibility::from_u8(VISIBILITY.load(Ordering::Relaxed))
+}
+
+/// Returns whether a prompt written now should be shown.
+pub(crate) fn prompts_visible() -> bool {
+    prompt_visibility().allows(prompt_target())
+}
+
+/// Sets text printed before every prompt on this thread, such as an app
+/// name; the default is none.
+///
+/// Only the prompts passed to the crate get the prefix and
+/// [suffix](set_prompt_suffix), once each; the crate's own text, such as a
+/// default hint or a menu's `Choice [1-3]: `, does not. Like
+/// [`set_input_source`], this is per thread.
+///
+/// # Usage:
+/// ```
+/// use input_macro::{set_prompt_prefix, set_prompt_suffix, testing::ScriptedReader, Input};
+///
+/// set_prompt_prefix("myctl> ");
+/// set_prompt_suffix(": ");
+/// let port = ScriptedReader::new()
+///     .expect("myctl> Port: [default: 80] ", "")
+///     .run(|| Input::<u16>::new().prompt("Port").default(80).read())
+///     .unwrap();
+/// assert_eq!(port, 80);
+/// ```
+pub fn set_prompt_prefix(prefix: impl Into<String>) {
+    AFFIXES.with(|affixes| affixes.borrow_mut().0 = prefix.into());
+}
+
+/// Returns the prefix set with [`set_prompt_prefix`].
+pub fn prompt_prefix() -> String {
+    AFFIXES.with(|affixes| affixes.borrow().0.clone())
+}
+
+/// Sets text printed after every prompt on this thread, such as `": "`; the
+/// default is none. It goes before any default hint, and like the
+/// [prefix](set_prompt_prefix) only follows the prompts passed to the crate.
+pub fn set_prompt_suffix(suffix: impl Into<String>) {
+    AFFIXES.with(|affixes| affixes.borrow_mut().1 = suffix.into());
+}
+
+/// Returns the suffix set with [`set_prompt_suffix`].
+pub fn prompt_suffix() -> String {
+    AFFIXES.with(|affixes| affixes.borrow().1.clone())
+}
+
+/// Returns `prompt` with this thread's prefix and suffix around it.
+pub(crate) fn affixed(prompt: Arguments<'_>) -> String {
+    AFFIXES.with(|affixes| {
+        let (prefix, suffix) = &*affixes.borrow();
+        format!("{}{}{}", prefix, prompt, suffix)
+    })
+}
+
+/// Runs `f` on this thread's prompt output, or on the [`PromptTarget`] if none
+/// is set; prompts hidden by the [`PromptVisibility`] or [`hide_prompts`] go
+/// nowhere.
+pub(crate) fn with_prompt_output<T>(f: impl FnOnce(&mut dyn Write) -> T) -> T {
+    let f = |output: &mut dyn Write| crate::transcript::record_prompts(output, f);
+    if PROMPTS_HIDDEN.get() && prompt_visibility() != PromptVisibility::Always {
+        return f(&mut io::sink());
+    }
+    match OUTPUT.with(|slot| slot.borrow_mut().take()) {
+        Some(mut output) => {
+            let result = f(&mut *output);
+            OUTPUT.with(|slot| {
+                let mut slot = slot.borrow_mut();
+                if slot.is_none() {
+                    *slot = Some(output);
+                }
+            });
+            result
+        }
+        None if !prompts_visible() => f(&mut io::sink()),
+        #[cfg(feature = "rustyline")]
+        None if crate::editor::capturing() => f(&mut crate::editor::PromptCapture),
+        None if prompts_to_stderr() => f(&mut io::stderr()),
+        None => f(&mut io::stdout()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputError;
+    use std::io::Cursor;
+
+    /// The macros read from the source, line by line, then report EOF.
+    #[test]
+    fn test_macros_use_input_source() {
+        set_input_source(Box::new(Cursor::new("Alice\n42\n")));
+        let name: Option<String> = crate::input!("Name: ").unwrap();
+        let age = crate::input!(u8, "Age: ").unwrap();
+        let rest = crate::input_no_eof!(u8);
+        assert_eq!(name.as_deref(), Some("Alice"));
+        assert_eq!(age, Some(42));
+        assert!(matches!(
+            rest,
+            Err(Located {
+                error: InputError::Eof,
+                ..
+            })
+        ));
+        assert!(reset_input_source().is_some());
+    }
+
+    /// The prompt target round-trips through its atomic encoding. The
+    /// process-wide setting is left alone, since other tests print prompts.
+    #[test]
+    fn test_prompt_target() {
+        assert_eq!(prompt_target(), PromptTarget::Stdout);
+        for target in [
+            PromptTarget::Stdout,
+            PromptTarget::Stderr,
+            PromptTarget::Auto,
+        ] {
+            assert_eq!(PromptTarget::from_u8(target as u8), target);
+        }
+        assert!(!PromptTarget::Stdout.is_stderr());
+        assert!(PromptTarget::Stderr.is_stderr());
+        assert_eq!(PromptTarget::Auto.is_stderr(), !io::stdout().is_terminal());
+    }
+
+    /// Prompt visibility round-trips and can force prompts on or off.
+    #[test]
+    fn test_prompt_visibility() {
+        assert_eq!(prompt_visibility(), PromptVisibility::Interactive);
+        for visibility in [
+            PromptVisibility::Interactive,
+            PromptVisibility::Always,
+            PromptVisibility::Never,
+        ] {
+            assert_eq!(PromptVisibility::from_u8(visibility as u8), visibility);
+        }
+        assert!(PromptVisibility::Always.allows(PromptTarget::Stdout));
+        assert!(!PromptVisibility::Never.allows(PromptTarget::Stdout));
+        assert_eq!(
+            PromptVisibility::Interactive.allows(PromptTarget::Stdout),
+            is_interactive()
+        );
+    }
+
+    /// Failed stdin reads carry the location of the call that started them.
+    #[test]
+    fn test_failure_location() {
+        set_input_source(Box::new(Cursor::new("1\nx\n")));
+        let ok: Option<u8> = crate::input!().unwrap();
+        assert_eq!(ok, Some(1));
+        let line = line!() + 1;
+        let err = crate::input!(u8, "n: ").unwrap_err();
+        let location = err.location();
+        assert_eq!((location.file(), location.line()), (file!(), line));
+        reset_input_source();
+
+        // Builder reads locate their caller with any reader
+        let mut reader = Cursor::new("x\n");
+        let input = crate::Input::<u8>::new().reader(&mut reader);
+        let line = line!() + 1;
+        let err = input.read().unwrap_err();
+        assert_eq!(err.location().line(), line);
+        assert!(err.is_parse());
+    }
+
+    /// Sessions nest, and an input source still wins while one is open.
+    #[test]
+    fn test_stdin_session() {
+        let outer = stdin_session();
+        let inner = stdin_session();
+        assert_eq!(SESSIONS.get(), 2);
+        set_input_source(Box::new(Cursor::new("5\n")));
+        assert_eq!(crate::read_input::<u8>().unwrap(), 5);
+        reset_input_source();
+        drop(inner);
+        assert_eq!(SESSIONS.get(), 1);
+        drop(outer);
+        assert_eq!(SESSIONS.get(), 0);
+        assert!(SESSION_LOCK.with(|slot| slot.borrow().is_none()));
+    }
+
+    /// Function wrappers and the builder read from the source too.
+    #[test]
+    fn test_wrappers_use_input_source() {
+        set_input_source(Box::new(Cursor::new("y\n7\n\n")));
+        assert!(crate::confirm(None, None).unwrap());
+        assert_eq!(crate::read_input::<i32>().unwrap(), 7);
+        let port = crate::Input::<u16>::new().default(80).read().unwrap();
+        assert_eq!(port, 80);
+        reset_input_source();
+        assert!(!has_input_source());
+    }
+}
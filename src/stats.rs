@@ -0,0 +1,162 @@
+use std::io::{self, BufRead, Read};
+
+/// Counts of what an [`InputReader`](crate::InputReader) has read, from
+/// [`InputReader::stats`](crate::InputReader::stats).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReadStats {
+    /// Lines read, including ones that failed to parse and a final line with
+    /// no line ending.
+    pub lines: u64,
+    /// Bytes read, including line endings.
+    pub bytes: u64,
+    /// Lines that failed to parse.
+    pub parse_failures: u64,
+    /// Attempts repeated after a parse or validation failure, by
+    /// [`InputReader::read_input`](crate::InputReader::read_input).
+    pub retries: u64,
+    /// Reads that found the input already exhausted.
+    pub eofs: u64,
+}
+
+impl ReadStats {
+    fn record(&mut self, event: &ReadEvent) {
+        match *event {
+            ReadEvent::Line { bytes } => {
+                self.lines += 1;
+                self.bytes += bytes as u64;
+            }
+            ReadEvent::ParseFailure => self.parse_failures += 1,
+            ReadEvent::Retry => self.retries += 1,
+            ReadEvent::Eof => self.eofs += 1,
+        }
+    }
+}
+
+/// Something an [`InputReader`](crate::InputReader) counted, as passed to
+/// its [event hook](crate::InputReader::set_event_hook).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ReadEvent {
+    /// A line was read; `bytes` includes its line ending.
+    Line { bytes: usize },
+    /// A line failed to parse.
+    ParseFailure,
+    /// An attempt is being repeated after a failure.
+    Retry,
+    /// A read found the input already exhausted.
+    Eof,
+}
+
+/// A callback receiving every [`ReadEvent`].
+pub(crate) type EventHook = Box<dyn FnMut(&ReadEvent) + Send>;
+
+/// The stats of one reader and the hook to report them to.
+#[derive(Default)]
+pub(crate) struct Meter {
+    pub(crate) stats: ReadStats,
+    pub(crate) hook: Option<EventHook>,
+}
+
+impl Meter {
+    pub(crate) fn emit(&mut self, event: ReadEvent) {
+        self.stats.record(&event);
+        if let Some(hook) = &mut self.hook {
+            hook(&event);
+        }
+    }
+
+    /// Wraps `reader` to count the lines and bytes read through it.
+    pub(crate) fn wrap<'a, R: BufRead + ?Sized>(&'a mut self, reader: &'a mut R) -> Metered<'a, R> {
+        Metered {
+            inner: reader,
+            counter: Counter {
+                meter: self,
+                line: 0,
+                at_eof: false,
+            },
+        }
+    }
+}
+
+impl std::fmt::Debug for Meter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Meter")
+            .field("stats", &self.stats)
+            .field("hook", &self.hook.is_some())
+            .finish()
+    }
+}
+
+/// A reader counting what passes through it into a [`Meter`], for the
+/// duration of one read.
+pub(crate) struct Metered<'a, R: ?Sized> {
+    inner: &'a mut R,
+    counter: Counter<'a>,
+}
+
+struct Counter<'a> {
+    meter: &'a mut Meter,
+    /// Bytes of the line in progress.
+    line: usize,
+    /// Whether the end of input was counted since bytes were last read.
+    at_eof: bool,
+}
+
+impl Counter<'_> {
+    fn count(&mut self, bytes: &[u8]) {
+        self.at_eof &= bytes.is_empty();
+        for part in bytes.split_inclusive(|&b| b == b'\n') {
+            self.line += part.len();
+            if part.ends_with(b"\n") {
+                let bytes = std::mem::take(&mut self.line);
+                self.meter.emit(ReadEvent::Line { bytes });
+            }
+        }
+    }
+
+    /// Counts reaching the end of input with no line in progress as an EOF,
+    /// once however often it is seen.
+    fn end_of_input(&mut self) {
+        if self.line == 0 && !self.at_eof {
+            self.at_eof = true;
+            self.meter.emit(ReadEvent::Eof);
+        }
+    }
+}
+
+impl<'a, R: ?Sized> Metered<'a, R> {
+    /// Ends the read, counting a final line with no line ending.
+    pub(crate) fn finish(self) -> &'a mut Meter {
+        let Counter { meter, line, .. } = self.counter;
+        if line > 0 {
+            meter.emit(ReadEvent::Line { bytes: line });
+        }
+        meter
+    }
+}
+
+impl<R: BufRead + ?Sized> Read for Metered<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if n == 0 && !buf.is_empty() {
+            self.counter.end_of_input();
+        }
+        self.counter.count(&buf[..n]);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead + ?Sized> BufRead for Metered<'_, R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let buf = self.inner.fill_buf()?;
+        if buf.is_empty() {
+            self.counter.end_of_input();
+        }
+        Ok(buf)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let counter = &mut self.counter;
+        crate::buffered::consume_with(&mut self.inner, amt, |bytes| counter.count(bytes));
+    }
+}
@@ -0,0 +1,111 @@
+use std::io::{self, BufReader, ErrorKind, Read};
+use std::net::TcpStream;
+
+use crate::InputReader;
+
+/// A [`TcpStream`] read as line input, for [`InputReader::from_tcp`].
+///
+/// The peer resetting or aborting the connection reads as the end of input,
+/// like an orderly shutdown, so a client that disappears ends the session
+/// with `InputError::Eof` rather than an I/O error.
+#[derive(Debug)]
+pub struct TcpSource {
+    stream: TcpStream,
+}
+
+impl TcpSource {
+    /// Returns the underlying stream, e.g. to write replies.
+    pub fn get_ref(&self) -> &TcpStream {
+        &self.stream
+    }
+}
+
+impl Read for TcpSource {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.stream.read(buf) {
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    ErrorKind::ConnectionReset | ErrorKind::ConnectionAborted
+                ) =>
+            {
+                Ok(0)
+            }
+            result => result,
+        }
+    }
+}
+
+impl InputReader<BufReader<TcpSource>> {
+    /// Reads lines from `stream`, as a line protocol over TCP would.
+    ///
+    /// Reads block until a whole line has arrived, however the peer splits it
+    /// across packets. Once the peer shuts down its side, a final line with
+    /// no line ending is still returned, and the next read is
+    /// `Err(InputError::Eof)`.
+    ///
+    /// # Usage:
+    /// ```no_run
+    /// use std::io::Write;
+    /// use std::net::TcpListener;
+    /// use input_macro::{InputError, InputReader};
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:7878").unwrap();
+    /// for stream in listener.incoming() {
+    ///     let mut input = InputReader::from_tcp(stream.unwrap());
+    ///     loop {
+    ///         let reply = match input.read::<i64>() {
+    ///             Ok(n) => format!("{}\n", n * 2),
+    ///             Err(InputError::Eof) => break,
+    ///             Err(err) => format!("error: {}\n", err),
+    ///         };
+    ///         input.stream().write_all(reply.as_bytes()).unwrap();
+    ///     }
+    /// }
+    /// ```
+    pub fn from_tcp(stream: TcpStream) -> Self {
+        InputReader::new(BufReader::new(TcpSource { stream }))
+    }
+
+    /// Returns the stream being read, e.g. to write replies.
+    pub fn stream(&self) -> &TcpStream {
+        self.get_ref().get_ref().get_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputError;
+    use std::io::Write;
+    use std::net::{Shutdown, TcpListener};
+    use std::thread;
+    use std::time::Duration;
+
+    /// Lines split across writes arrive whole, and shutdown ends the input.
+    #[test]
+    fn test_from_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            stream.write_all(b"4").unwrap();
+            stream.flush().unwrap();
+            thread::sleep(Duration::from_millis(20));
+            stream.write_all(b"2\r\nx\n7").unwrap();
+            stream.shutdown(Shutdown::Write).unwrap();
+            let mut reply = String::new();
+            stream.read_to_string(&mut reply).unwrap();
+            reply
+        });
+
+        let mut input = InputReader::from_tcp(listener.accept().unwrap().0);
+        assert_eq!(input.read::<u8>().unwrap(), 42);
+        assert!(matches!(input.read::<u8>(), Err(InputError::Parse { .. })));
+        assert_eq!(input.read::<u8>().unwrap(), 7);
+        assert!(matches!(input.read::<u8>(), Err(InputError::Eof)));
+        input.stream().write_all(b"bye").unwrap();
+        drop(input);
+        assert_eq!(client.join().unwrap(), "bye");
+    }
+}
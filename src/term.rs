@@ -0,0 +1,466 @@
+//! Raw-terminal interactions, behind the `crossterm` feature.
+
+use std::convert::Infallible;
+use std::fmt::Arguments;
+use std::io::{self, Write};
+
+use crossterm::cursor::MoveToPreviousLine;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::queue;
+use crossterm::terminal::{self, Clear, ClearType};
+
+use crate::{read_trimmed_line, write_prompt, write_prompt_line, InputError, Located};
+
+/// Keeps the terminal in raw mode until dropped.
+#[derive(Debug)]
+pub(crate) struct RawMode;
+
+impl RawMode {
+    pub(crate) fn enable() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        Ok(RawMode)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Prints the optional prompt, then returns the next key pressed, without
+/// waiting for Enter.
+///
+/// Enter reads as `'\n'`, Tab as `'\t'`, and Backspace as `'\u{8}'`; other
+/// keys without a character, such as arrows, are skipped. Esc returns
+/// `Err(InputError::Cancelled)`, Ctrl-C `Err(InputError::Interrupted)`, and
+/// Ctrl-D `Err(InputError::Eof)`; other Ctrl and Alt chords return their
+/// character. Use [`KeyEvents`] to tell those apart.
+///
+/// When stdin or stdout is not a terminal, or an
+/// [input source](crate::set_input_source) is set, this falls back to reading
+/// a line and returning its first character (`'\n'` for an empty line); the
+/// rest of the line is discarded.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::read_key;
+///
+/// match read_key(Some(format_args!("(a)dd, (d)elete, (q)uit: "))).unwrap() {
+///     'a' => println!("Adding"),
+///     'd' => println!("Deleting"),
+///     _ => println!("Bye"),
+/// }
+/// ```
+#[track_caller]
+pub fn read_key(prompt: Option<Arguments<'_>>) -> Result<char, Located<InputError<Infallible>>> {
+    if !crate::is_interactive() || crate::has_input_source() {
+        return crate::__with_stdin(|reader| {
+            let mut input = String::new();
+            let line = read_trimmed_line(reader, prompt, &mut input)?;
+            Ok(line.chars().next().unwrap_or('\n'))
+        });
+    }
+    crate::source::located(read_key_raw(prompt))
+}
+
+/// Reads one key press from the terminal, for [`read_key`].
+fn read_key_raw(prompt: Option<Arguments<'_>>) -> Result<char, InputError<Infallible>> {
+    if let Some(prompt_args) = prompt {
+        write_prompt(prompt_args).map_err(InputError::Io)?;
+    }
+    let _raw = RawMode::enable().map_err(InputError::Io)?;
+    loop {
+        if let Event::Key(key) = event::read().map_err(InputError::Io)? {
+            if key.kind == KeyEventKind::Press {
+                if let Some(result) = key_char(&key) {
+                    return result;
+                }
+            }
+        }
+    }
+}
+
+/// Maps a key press to what [`read_key`] returns, or `None` to skip it.
+fn key_char(key: &KeyEvent) -> Option<Result<char, InputError<Infallible>>> {
+    match decode_key(key)? {
+        Key::Ctrl('c') => Some(Err(InputError::Interrupted)),
+        Key::Ctrl('d') => Some(Err(InputError::Eof)),
+        Key::Char(c) | Key::Alt(c) => Some(Ok(c)),
+        // Other Ctrl chords still read as their character, as typed
+        Key::Ctrl(c) => match key.code {
+            KeyCode::Char(typed) => Some(Ok(typed)),
+            _ => Some(Ok(c)),
+        },
+        Key::Enter => Some(Ok('\n')),
+        Key::Tab => Some(Ok('\t')),
+        Key::Backspace => Some(Ok('\u{8}')),
+        Key::Esc => Some(Err(InputError::Cancelled)),
+        _ => None,
+    }
+}
+
+/// A decoded key press, as yielded by [`KeyEvents`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Key {
+    /// A printable character, including Shift variants such as `'A'`.
+    Char(char),
+    /// A letter or other character typed with Ctrl held, in lower case.
+    Ctrl(char),
+    /// A character typed with Alt held.
+    Alt(char),
+    /// The Enter or Return key.
+    Enter,
+    /// The Tab key.
+    Tab,
+    /// Shift-Tab.
+    BackTab,
+    /// The Backspace key.
+    Backspace,
+    /// The Delete key, which deletes forward.
+    Delete,
+    /// The Insert key.
+    Insert,
+    /// The Escape key.
+    Esc,
+    /// The up arrow.
+    Up,
+    /// The down arrow.
+    Down,
+    /// The left arrow.
+    Left,
+    /// The right arrow.
+    Right,
+    /// The Home key.
+    Home,
+    /// The End key.
+    End,
+    /// The Page Up key.
+    PageUp,
+    /// The Page Down key.
+    PageDown,
+    /// A function key, `F(1)` through `F(12)` or beyond.
+    F(u8),
+}
+
+/// Decodes a crossterm key event, or returns `None` for keys with no [`Key`].
+fn decode_key(key: &KeyEvent) -> Option<Key> {
+    let key = match key.code {
+        KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Key::Ctrl(c.to_ascii_lowercase())
+        }
+        KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::ALT) => Key::Alt(c),
+        KeyCode::Char(c) => Key::Char(c),
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::BackTab => Key::BackTab,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Delete => Key::Delete,
+        KeyCode::Insert => Key::Insert,
+        KeyCode::Esc => Key::Esc,
+        KeyCode::Up => Key::Up,
+        KeyCode::Down => Key::Down,
+        KeyCode::Left => Key::Left,
+        KeyCode::Right => Key::Right,
+        KeyCode::Home => Key::Home,
+        KeyCode::End => Key::End,
+        KeyCode::PageUp => Key::PageUp,
+        KeyCode::PageDown => Key::PageDown,
+        KeyCode::F(n) => Key::F(n),
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// An endless iterator over key presses, which keeps the terminal in raw mode
+/// until dropped.
+///
+/// Raw mode turns off line editing and signal keys, so Ctrl-C arrives as
+/// `Key::Ctrl('c')` rather than stopping the program; stop iterating on
+/// whichever keys should end the interaction. Releases, repeats, and keys
+/// with no [`Key`] are skipped.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::{Key, KeyEvents};
+///
+/// let mut row = 0u32;
+/// for key in KeyEvents::new().unwrap() {
+///     match key.unwrap() {
+///         Key::Up => row = row.saturating_sub(1),
+///         Key::Down => row += 1,
+///         Key::Char('q') | Key::Esc | Key::Ctrl('c') => break,
+///         _ => {}
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct KeyEvents {
+    _raw: RawMode,
+}
+
+impl KeyEvents {
+    /// Puts the terminal in raw mode, failing if there is no terminal.
+    pub fn new() -> io::Result<Self> {
+        Ok(KeyEvents {
+            _raw: RawMode::enable()?,
+        })
+    }
+}
+
+impl Iterator for KeyEvents {
+    type Item = io::Result<Key>;
+
+    fn next(&mut self) -> Option<io::Result<Key>> {
+        loop {
+            match event::read() {
+                Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                    if let Some(key) = decode_key(&key) {
+                        return Some(Ok(key));
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+/// What a key press does to an open selector.
+#[derive(Debug, PartialEq)]
+enum SelectAction {
+    Move(usize),
+    Toggle,
+    Choose,
+    Cancel,
+    Interrupt,
+    Ignore,
+}
+
+fn select_action(key: &KeyEvent, selected: usize, len: usize) -> SelectAction {
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => SelectAction::Move((selected + len - 1) % len),
+        KeyCode::Down | KeyCode::Char('j') => SelectAction::Move((selected + 1) % len),
+        KeyCode::Home => SelectAction::Move(0),
+        KeyCode::End => SelectAction::Move(len - 1),
+        KeyCode::Char(' ') => SelectAction::Toggle,
+        KeyCode::Enter => SelectAction::Choose,
+        KeyCode::Esc => SelectAction::Cancel,
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            SelectAction::Interrupt
+        }
+        _ => SelectAction::Ignore,
+    }
+}
+
+/// Runs an arrow-key selector over `options` and returns the picked index.
+///
+/// `options` must not be empty.
+pub(crate) fn select_interactive<T: std::fmt::Display>(
+    prompt: Option<Arguments<'_>>,
+    options: &[T],
+) -> Result<usize, InputError<Infallible>> {
+    run_selector(prompt, options, None)
+}
+
+/// Runs an arrow-key selector where Space toggles options, and returns the
+/// toggled indices in ascending order.
+///
+/// `options` must not be empty.
+pub(crate) fn multi_select_interactive<T: std::fmt::Display>(
+    prompt: Option<Arguments<'_>>,
+    options: &[T],
+) -> Result<Vec<usize>, InputError<Infallible>> {
+    let mut checked = vec![false; options.len()];
+    run_selector(prompt, options, Some(&mut checked))?;
+    Ok((0..options.len()).filter(|&i| checked[i]).collect())
+}
+
+/// Drives a selector until Enter (returning the highlighted index), Esc, or Ctrl-C.
+///
+/// With `checked`, Space toggles the highlighted option's checkbox. The
+/// prompt and options are drawn by the [`Theme`](crate::Theme) in effect and
+/// go to the prompt output, like a numbered menu's.
+fn run_selector<T: std::fmt::Display>(
+    prompt: Option<Arguments<'_>>,
+    options: &[T],
+    mut checked: Option<&mut [bool]>,
+) -> Result<usize, InputError<Infallible>> {
+    if let Some(prompt_args) = prompt {
+        write_prompt_line(prompt_args).map_err(InputError::Io)?;
+    }
+
+    let _raw = RawMode::enable().map_err(InputError::Io)?;
+    crate::source::with_prompt_output(|mut out| {
+        // Sized, as crossterm's commands need
+        let out = &mut out;
+        let mut selected = 0;
+        draw_options(out, options, selected, checked.as_deref()).map_err(InputError::Io)?;
+        loop {
+            let key = match event::read().map_err(InputError::Io)? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => key,
+                _ => continue,
+            };
+            match (
+                select_action(&key, selected, options.len()),
+                checked.as_deref_mut(),
+            ) {
+                (SelectAction::Move(index), _) => selected = index,
+                (SelectAction::Toggle, Some(checked)) => checked[selected] = !checked[selected],
+                (SelectAction::Choose, _) => return Ok(selected),
+                (SelectAction::Cancel, _) => return Err(InputError::Cancelled),
+                (SelectAction::Interrupt, _) => return Err(InputError::Interrupted),
+                (SelectAction::Toggle, None) | (SelectAction::Ignore, _) => continue,
+            }
+            queue!(out, MoveToPreviousLine(options.len() as u16)).map_err(InputError::Io)?;
+            draw_options(out, options, selected, checked.as_deref()).map_err(InputError::Io)?;
+        }
+    })
+}
+
+/// Draws each option as a menu item of the theme in effect, after a `>`
+/// marking the highlighted one and, with `checked`, in front of a checkbox.
+fn draw_options<T: std::fmt::Display>(
+    out: &mut impl Write,
+    options: &[T],
+    selected: usize,
+    checked: Option<&[bool]>,
+) -> io::Result<()> {
+    for (i, option) in options.iter().enumerate() {
+        let marker = if i == selected { '>' } else { ' ' };
+        let item = match checked {
+            Some(checked) => {
+                let mark = if checked[i] { 'x' } else { ' ' };
+                crate::theme::menu_item(i + 1, &format_args!("[{}] {}", mark, option))
+            }
+            None => crate::theme::menu_item(i + 1, option),
+        };
+        queue!(out, Clear(ClearType::CurrentLine))?;
+        // Raw mode needs an explicit carriage return
+        write!(out, "{}{}\r\n", marker, item)?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    /// Up and Down wrap around the list; Enter and Esc finish.
+    #[test]
+    fn test_select_action() {
+        assert_eq!(
+            select_action(&key(KeyCode::Down), 2, 3),
+            SelectAction::Move(0)
+        );
+        assert_eq!(
+            select_action(&key(KeyCode::Up), 0, 3),
+            SelectAction::Move(2)
+        );
+        assert_eq!(
+            select_action(&key(KeyCode::End), 0, 3),
+            SelectAction::Move(2)
+        );
+        assert_eq!(
+            select_action(&key(KeyCode::Enter), 1, 3),
+            SelectAction::Choose
+        );
+        assert_eq!(
+            select_action(&key(KeyCode::Esc), 1, 3),
+            SelectAction::Cancel
+        );
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(select_action(&ctrl_c, 1, 3), SelectAction::Interrupt);
+        assert_eq!(
+            select_action(&key(KeyCode::Char('x')), 1, 3),
+            SelectAction::Ignore
+        );
+    }
+
+    /// Characters and editing keys map to chars; control keys end the read.
+    #[test]
+    fn test_key_char() {
+        assert_eq!(key_char(&key(KeyCode::Char('q'))), Some(Ok('q')));
+        assert_eq!(key_char(&key(KeyCode::Enter)), Some(Ok('\n')));
+        assert_eq!(key_char(&key(KeyCode::Left)), None);
+        assert_eq!(
+            key_char(&key(KeyCode::Esc)),
+            Some(Err(InputError::Cancelled))
+        );
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(key_char(&ctrl_c), Some(Err(InputError::Interrupted)));
+        let ctrl_d = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert_eq!(key_char(&ctrl_d), Some(Err(InputError::Eof)));
+        let ctrl_w = KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(key_char(&ctrl_w), Some(Ok('w')));
+    }
+
+    /// Modifiers and special keys decode to their own variants.
+    #[test]
+    fn test_decode_key() {
+        let with = |code, modifiers| decode_key(&KeyEvent::new(code, modifiers));
+        assert_eq!(
+            with(KeyCode::Char('A'), KeyModifiers::SHIFT),
+            Some(Key::Char('A'))
+        );
+        assert_eq!(
+            with(KeyCode::Char('W'), KeyModifiers::CONTROL),
+            Some(Key::Ctrl('w'))
+        );
+        assert_eq!(
+            with(KeyCode::Char('x'), KeyModifiers::ALT),
+            Some(Key::Alt('x'))
+        );
+        assert_eq!(decode_key(&key(KeyCode::F(5))), Some(Key::F(5)));
+        assert_eq!(decode_key(&key(KeyCode::Home)), Some(Key::Home));
+        assert_eq!(decode_key(&key(KeyCode::CapsLock)), None);
+    }
+
+    /// The selected option is marked when the menu is drawn.
+    #[test]
+    fn test_draw_options_marks_selection() {
+        let mut out = Vec::new();
+        draw_options(&mut out, &["a", "b"], 1, None).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("   1) a\r\n"));
+        assert!(text.contains(">  2) b\r\n"));
+    }
+
+    /// Options are drawn by the theme in effect.
+    #[test]
+    fn test_draw_options_themed() {
+        struct Bullets;
+        impl crate::Theme for Bullets {
+            fn format_menu_item(
+                &self,
+                f: &mut std::fmt::Formatter<'_>,
+                _: usize,
+                option: &dyn std::fmt::Display,
+            ) -> std::fmt::Result {
+                write!(f, " * {}", option)
+            }
+        }
+
+        let _theme = crate::theme::use_theme(Some(std::sync::Arc::new(Bullets)));
+        let mut out = Vec::new();
+        draw_options(&mut out, &["a", "b"], 0, Some(&[true, false])).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("> * [x] a\r\n"));
+        assert!(text.contains("  * [ ] b\r\n"));
+    }
+
+    /// Multi-select menus show a checkbox per option.
+    #[test]
+    fn test_draw_options_checkboxes() {
+        let mut out = Vec::new();
+        draw_options(&mut out, &["a", "b"], 0, Some(&[false, true])).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains(">  1) [ ] a\r\n"));
+        assert!(text.contains("   2) [x] b\r\n"));
+    }
+}
@@ -0,0 +1,373 @@
+//! Helpers for testing interactive flows built on this crate.
+//!
+//! [`ScriptedReader`] plays a fixed list of responses into the crate's stdin
+//! readers and checks the prompt written before each one, so prompt text,
+//! order, and retries can be asserted in plain unit tests.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Read, Write};
+use std::rc::Rc;
+
+use crate::source::{reset_input_source, reset_prompt_output, set_input_source, set_prompt_output};
+use crate::transcript::{self, Event};
+
+struct Response {
+    prompt: Option<String>,
+    line: String,
+}
+
+#[derive(Default)]
+struct Shared {
+    responses: VecDeque<Response>,
+    pending: String,
+    prompts: Vec<String>,
+    /// The prompt text expected after the last response, if it is checked.
+    last_prompt: Option<String>,
+}
+
+/// A scripted stand-in for the user.
+///
+/// Each response is one line of input. A response added with
+/// [`expect`](ScriptedReader::expect) also checks that the prompt text written
+/// since the previous response is exactly the given string, and panics with
+/// both prompts if not. Once the responses run out, reads see EOF.
+///
+/// [`run`](ScriptedReader::run) installs the script as this thread's input
+/// source and prompt output (see [`set_input_source`]),
+/// so code that calls `input!`, `read_input`, `Form::run`, and so on is
+/// scripted without changes.
+///
+/// # Usage:
+/// ```
+/// use input_macro::{input, testing::ScriptedReader};
+///
+/// fn ask_age() -> u8 {
+///     loop {
+///         if let Ok(Some(age)) = input!(u8, "Age: ") {
+///             return age;
+///         }
+///     }
+/// }
+///
+/// let age = ScriptedReader::new()
+///     .expect("Age: ", "old")
+///     .expect("Age: ", "42")
+///     .run(ask_age);
+/// assert_eq!(age, 42);
+/// ```
+pub struct ScriptedReader {
+    shared: Rc<RefCell<Shared>>,
+    line: io::Cursor<Vec<u8>>,
+}
+
+impl Default for ScriptedReader {
+    fn default() -> Self {
+        ScriptedReader {
+            shared: Rc::default(),
+            line: io::Cursor::new(Vec::new()),
+        }
+    }
+}
+
+impl ScriptedReader {
+    /// Creates a script with no responses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a response given without checking the prompt before it.
+    pub fn respond(self, line: impl Into<String>) -> Self {
+        self.push(None, line.into())
+    }
+
+    /// Adds a response, checking that `prompt` was written just before it.
+    pub fn expect(self, prompt: impl Into<String>, line: impl Into<String>) -> Self {
+        self.push(Some(prompt.into()), line.into())
+    }
+
+    /// Creates a script that replays a transcript saved by
+    /// [`Transcript`](crate::Transcript), for golden-file tests of interactive
+    /// flows.
+    ///
+    /// Each recorded answer becomes a response that expects the prompts
+    /// recorded since the previous answer, so the replayed flow must write
+    /// the same prompts, in the same order, as the recorded one. Prompts
+    /// recorded after the last answer are checked too, once the flow returns.
+    ///
+    /// Fails if reading fails, a line is not a transcript event, or an answer
+    /// was [redacted](crate::Transcript::redact), as it cannot be replayed.
+    ///
+    /// # Usage:
+    /// ```no_run
+    /// use input_macro::{testing::ScriptedReader, Form};
+    ///
+    /// let golden = std::fs::File::open("tests/setup.transcript").unwrap();
+    /// ScriptedReader::from_transcript(std::io::BufReader::new(golden))
+    ///     .unwrap()
+    ///     .run(|| Form::new().field::<String>("host", "Host: ").run())
+    ///     .unwrap();
+    /// ```
+    pub fn from_transcript(reader: impl BufRead) -> io::Result<Self> {
+        let mut script = ScriptedReader::new();
+        let mut prompt = String::new();
+        for event in transcript::parse(reader)? {
+            match event {
+                Event::Prompt(text) => prompt.push_str(&text),
+                Event::Answer(Some(line)) => {
+                    script = script.expect(std::mem::take(&mut prompt), line);
+                }
+                Event::Answer(None) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "a redacted answer cannot be replayed",
+                    ))
+                }
+            }
+        }
+        script.shared.borrow_mut().last_prompt = Some(prompt);
+        Ok(script)
+    }
+
+    fn push(self, prompt: Option<String>, line: String) -> Self {
+        self.shared
+            .borrow_mut()
+            .responses
+            .push_back(Response { prompt, line });
+        self
+    }
+
+    /// Runs `f` against the script and returns its result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a checked prompt does not match, or if `f` returns before
+    /// every response was read.
+    pub fn run<T>(self, f: impl FnOnce() -> T) -> T {
+        self.record(f).0
+    }
+
+    /// Like [`run`](ScriptedReader::run), but also returns every prompt written,
+    /// one entry per response read plus any prompt written after the last one.
+    pub fn record<T>(self, f: impl FnOnce() -> T) -> (T, Vec<String>) {
+        let shared = Rc::clone(&self.shared);
+        let guard = Installed::new(Box::new(self), Box::new(PromptRecorder(Rc::clone(&shared))));
+        let result = f();
+        drop(guard);
+
+        let mut shared = shared.borrow_mut();
+        let unread: Vec<&str> = shared.responses.iter().map(|r| r.line.as_str()).collect();
+        assert!(unread.is_empty(), "unread scripted responses: {:?}", unread);
+        if let Some(expected) = &shared.last_prompt {
+            assert_eq!(
+                &shared.pending, expected,
+                "unexpected prompt after the last response"
+            );
+        }
+        let mut prompts = std::mem::take(&mut shared.prompts);
+        if !shared.pending.is_empty() {
+            prompts.push(std::mem::take(&mut shared.pending));
+        }
+        (result, prompts)
+    }
+}
+
+impl Read for ScriptedReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        crate::buffered::read_from_buf(self, buf)
+    }
+}
+
+impl BufRead for ScriptedReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let exhausted = self.line.position() as usize >= self.line.get_ref().len();
+        if exhausted {
+            let mut shared = self.shared.borrow_mut();
+            if let Some(response) = shared.responses.pop_front() {
+                let written = std::mem::take(&mut shared.pending);
+                if let Some(expected) = &response.prompt {
+                    assert_eq!(
+                        &written, expected,
+                        "unexpected prompt before response {:?}",
+                        response.line
+                    );
+                }
+                shared.prompts.push(written);
+                self.line = io::Cursor::new(format!("{}\n", response.line).into_bytes());
+            }
+        }
+        self.line.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.line.consume(amt);
+    }
+}
+
+/// A script stands in for a person, so prompts are shown to it.
+impl crate::InputSource for ScriptedReader {
+    fn is_interactive(&self) -> bool {
+        true
+    }
+}
+
+/// Restores the thread's previous input source and prompt output, even if
+/// the scripted flow panics.
+pub(crate) struct Installed {
+    source: Option<Box<dyn BufRead>>,
+    output: Option<Box<dyn Write>>,
+}
+
+impl Installed {
+    /// Makes `source` and `output` this thread's input source and prompt
+    /// output until the guard is dropped.
+    pub(crate) fn new(source: Box<dyn BufRead>, output: Box<dyn Write>) -> Self {
+        let guard = Installed {
+            source: reset_input_source(),
+            output: reset_prompt_output(),
+        };
+        set_input_source(source);
+        set_prompt_output(output);
+        guard
+    }
+}
+
+impl Drop for Installed {
+    fn drop(&mut self) {
+        reset_input_source();
+        reset_prompt_output();
+        if let Some(source) = self.source.take() {
+            set_input_source(source);
+        }
+        if let Some(output) = self.output.take() {
+            set_prompt_output(output);
+        }
+    }
+}
+
+/// Collects prompt text for the [`ScriptedReader`] it belongs to.
+struct PromptRecorder(Rc<RefCell<Shared>>);
+
+impl Write for PromptRecorder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .borrow_mut()
+            .pending
+            .push_str(&String::from_utf8_lossy(buf));
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Form, Input, InputError, Located};
+
+    /// Prompts are recorded in order, including retry messages and repeated prompts.
+    #[test]
+    fn test_record_prompts_with_retries() {
+        let (port, prompts) = ScriptedReader::new()
+            .respond("80")
+            .respond("8080")
+            .record(|| {
+                Input::<u16>::new()
+                    .prompt("Port: ")
+                    .validate(|p| *p >= 1024)
+                    .max_attempts(2)
+                    .read()
+            });
+        assert_eq!(port.unwrap(), 8080);
+        assert_eq!(
+            prompts,
+            vec!["Port: ", "Invalid input: value rejected\nPort: "]
+        );
+    }
+
+    /// Expected prompts are checked against what the flow wrote.
+    #[test]
+    fn test_expect_prompts() {
+        let answers = ScriptedReader::new()
+            .expect("Host: ", "db")
+            .expect("Port: [default: 5432] ", "")
+            .run(|| {
+                Form::new()
+                    .field::<String>("host", "Host: ")
+                    .step("port", Input::<u16>::new().prompt("Port: ").default(5432))
+                    .run()
+            })
+            .unwrap();
+        assert_eq!(answers.get::<u16>("port"), Some(5432));
+    }
+
+    /// A mismatched prompt fails the test.
+    #[test]
+    #[should_panic(expected = "unexpected prompt")]
+    fn test_expect_wrong_prompt() {
+        let _: Option<String> = ScriptedReader::new()
+            .expect("Name: ", "Alice")
+            .run(|| crate::input!("Username: "))
+            .unwrap();
+    }
+
+    /// A recorded session replays against the same flow, and a changed
+    /// prompt fails the replay.
+    #[test]
+    fn test_from_transcript() {
+        let golden = concat!(
+            "2026-10-14T09:30:00.125Z prompt \"Name: \"\n",
+            "2026-10-14T09:30:02.840Z answer \"Alice\"\n",
+            "2026-10-14T09:30:02.841Z prompt \"Age: \"\n",
+            "2026-10-14T09:30:03.502Z answer \"x\"\n",
+            "2026-10-14T09:30:03.502Z prompt \"Parse error: invalid digit found in string (input: \\\"x\\\")\\n\"\n",
+            "2026-10-14T09:30:03.502Z prompt \"Age: \"\n",
+            "2026-10-14T09:30:04.010Z answer \"42\"\n",
+            "2026-10-14T09:30:04.011Z prompt \"More? \"\n",
+        );
+        let flow = |name_prompt: &'static str| {
+            move || {
+                let _: Option<String> = crate::input!("{}", name_prompt).unwrap();
+                let age = Input::<u8>::new().prompt("Age: ").max_attempts(2).read();
+                (age.unwrap(), crate::input_no_eof!(u8, "More? "))
+            }
+        };
+        let replay = || ScriptedReader::from_transcript(golden.as_bytes()).unwrap();
+
+        let (age, more) = replay().run(flow("Name: "));
+        assert_eq!(age, 42);
+        assert!(matches!(
+            more,
+            Err(Located {
+                error: InputError::Eof,
+                ..
+            })
+        ));
+        let changed = std::panic::catch_unwind(|| replay().run(flow("Your name: ")));
+        assert!(changed.is_err());
+
+        let redacted = "t prompt \"Token: \"\nt answer <redacted>\n";
+        assert!(ScriptedReader::from_transcript(redacted.as_bytes()).is_err());
+    }
+
+    /// Reads past the script see EOF, and `inputln!` prompts are captured.
+    #[test]
+    fn test_eof_after_script() {
+        let (res, prompts) = ScriptedReader::new().respond("hi").record(|| {
+            let first: Option<String> = crate::inputln!("Say something").unwrap();
+            let second = crate::input_no_eof!(String, "More? ");
+            (first, second)
+        });
+        assert_eq!(res.0.as_deref(), Some("hi"));
+        assert!(matches!(
+            res.1,
+            Err(Located {
+                error: InputError::Eof,
+                ..
+            })
+        ));
+        assert_eq!(prompts, vec!["Say something\n", "More? "]);
+    }
+}
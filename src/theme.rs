@@ -0,0 +1,221 @@
+use std::cell::RefCell;
+use std::fmt::{self, Display};
+use std::sync::{Arc, RwLock};
+
+/// How the crate draws the text around reads: prompts, default hints, the
+/// messages shown before a retry, and menu options.
+///
+/// Every method has a default that draws the crate's plain text, so a theme
+/// overrides only what it changes. Set one for the whole process with
+/// [`set_theme`], or for one read with [`Input::theme`](crate::Input::theme).
+///
+/// # Usage:
+/// ```
+/// use std::fmt::{self, Display};
+/// use input_macro::{testing::ScriptedReader, Input, Theme};
+///
+/// struct Arrows;
+///
+/// impl Theme for Arrows {
+///     fn format_prompt(&self, f: &mut fmt::Formatter<'_>, prompt: &dyn Display) -> fmt::Result {
+///         write!(f, "› {}", prompt)
+///     }
+///
+///     fn format_default_hint(&self, f: &mut fmt::Formatter<'_>, default: &dyn Display) -> fmt::Result {
+///         write!(f, "({}) ", default)
+///     }
+/// }
+///
+/// let port = ScriptedReader::new()
+///     .expect("› Port: (8080) ", "")
+///     .run(|| Input::<u16>::new().prompt("Port: ").default(8080).theme(Arrows).read())
+///     .unwrap();
+/// assert_eq!(port, 8080);
+/// ```
+pub trait Theme {
+    /// Draws `prompt` before a read, including any [prefix](crate::set_prompt_prefix),
+    /// [suffix](crate::set_prompt_suffix), and default hint.
+    fn format_prompt(&self, f: &mut fmt::Formatter<'_>, prompt: &dyn Display) -> fmt::Result {
+        write!(f, "{}", prompt)
+    }
+
+    /// Draws the hint for a read's default value, which follows its prompt.
+    fn format_default_hint(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        default: &dyn Display,
+    ) -> fmt::Result {
+        write!(f, "[default: {}] ", default)
+    }
+
+    /// Draws a message shown before the read is retried, such as a parse
+    /// error; it is followed by a newline.
+    fn format_error(&self, f: &mut fmt::Formatter<'_>, message: &dyn Display) -> fmt::Result {
+        write!(f, "{}", message)
+    }
+
+    /// Draws the option numbered `number`, from 1, in a menu; it is followed
+    /// by a newline. The arrow-key selectors draw their options with it too,
+    /// after a `>` marking the highlighted one, or a space.
+    fn format_menu_item(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        number: usize,
+        option: &dyn Display,
+    ) -> fmt::Result {
+        write!(f, "  {}) {}", number, option)
+    }
+}
+
+/// The theme used unless another is set: the crate's plain text.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlainTheme;
+
+impl Theme for PlainTheme {}
+
+static THEME: RwLock<Option<Arc<dyn Theme + Send + Sync>>> = RwLock::new(None);
+
+thread_local! {
+    static OVERRIDE: RefCell<Option<Arc<dyn Theme + Send + Sync>>> = const { RefCell::new(None) };
+}
+
+/// Sets the theme for the whole process; the default is [`PlainTheme`].
+pub fn set_theme(theme: impl Theme + Send + Sync + 'static) {
+    *THEME.write().unwrap_or_else(|err| err.into_inner()) = Some(Arc::new(theme));
+}
+
+/// Makes this thread use `theme`, if any, until the returned guard is dropped.
+pub(crate) fn use_theme(theme: Option<Arc<dyn Theme + Send + Sync>>) -> ThemeGuard {
+    ThemeGuard(theme.map(|theme| OVERRIDE.with(|slot| slot.replace(Some(theme)))))
+}
+
+/// Restores the theme this thread used before when dropped.
+pub(crate) struct ThemeGuard(Option<Option<Arc<dyn Theme + Send + Sync>>>);
+
+impl Drop for ThemeGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            OVERRIDE.with(|slot| *slot.borrow_mut() = previous);
+        }
+    }
+}
+
+/// Runs `f` on the theme in effect.
+fn with_theme<T>(f: impl FnOnce(&dyn Theme) -> T) -> T {
+    if let Some(theme) = OVERRIDE.with(|slot| slot.borrow().clone()) {
+        return f(&*theme);
+    }
+    let theme = THEME.read().unwrap_or_else(|err| err.into_inner()).clone();
+    match theme {
+        Some(theme) => f(&*theme),
+        None => f(&PlainTheme),
+    }
+}
+
+/// Displays through a formatting closure.
+struct Draw<F>(F);
+
+impl<F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result> Display for Draw<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.0)(f)
+    }
+}
+
+/// Draws `prompt` with the theme in effect.
+pub(crate) fn prompt(prompt: &dyn Display) -> String {
+    with_theme(|theme| {
+        Draw(|f: &mut fmt::Formatter<'_>| theme.format_prompt(f, prompt)).to_string()
+    })
+}
+
+/// Draws the hint for `default` with the theme in effect.
+pub(crate) fn default_hint(default: &dyn Display) -> String {
+    with_theme(|theme| {
+        Draw(|f: &mut fmt::Formatter<'_>| theme.format_default_hint(f, default)).to_string()
+    })
+}
+
+/// Draws a retry `message` with the theme in effect.
+pub(crate) fn error(message: &dyn Display) -> String {
+    with_theme(|theme| {
+        Draw(|f: &mut fmt::Formatter<'_>| theme.format_error(f, message)).to_string()
+    })
+}
+
+/// Draws menu option `number` with the theme in effect.
+pub(crate) fn menu_item(number: usize, option: &dyn Display) -> String {
+    with_theme(|theme| {
+        Draw(|f: &mut fmt::Formatter<'_>| theme.format_menu_item(f, number, option)).to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::ScriptedReader;
+    use crate::Input;
+
+    struct Boxed;
+
+    impl Theme for Boxed {
+        fn format_prompt(&self, f: &mut fmt::Formatter<'_>, prompt: &dyn Display) -> fmt::Result {
+            write!(f, "? {}", prompt)
+        }
+
+        fn format_default_hint(
+            &self,
+            f: &mut fmt::Formatter<'_>,
+            default: &dyn Display,
+        ) -> fmt::Result {
+            write!(f, "<{}> ", default)
+        }
+
+        fn format_error(&self, f: &mut fmt::Formatter<'_>, message: &dyn Display) -> fmt::Result {
+            write!(f, "! {}", message)
+        }
+
+        fn format_menu_item(
+            &self,
+            f: &mut fmt::Formatter<'_>,
+            number: usize,
+            option: &dyn Display,
+        ) -> fmt::Result {
+            write!(f, "[{}] {}", number, option)
+        }
+    }
+
+    /// A builder's theme draws its prompt, hint, and retry message.
+    #[test]
+    fn test_builder_theme() {
+        let (port, prompts) = ScriptedReader::new().respond("x").respond("").record(|| {
+            Input::<u16>::new()
+                .prompt("Port: ")
+                .default(80)
+                .max_attempts(2)
+                .theme(Boxed)
+                .read()
+        });
+        assert_eq!(port.unwrap(), 80);
+        assert_eq!(
+            prompts.concat(),
+            "? Port: <80> \
+             ! Parse error: invalid digit found in string (input: \"x\")\n\
+             ? Port: <80> "
+        );
+    }
+
+    /// Menus are drawn by the theme in effect, which ends with its guard.
+    #[test]
+    fn test_menu_theme() {
+        let (picked, prompts) = ScriptedReader::new().respond("2").record(|| {
+            let _theme = use_theme(Some(Arc::new(Boxed)));
+            crate::select(Some(format_args!("Color:")), &["red", "blue"])
+        });
+        assert_eq!(picked.unwrap(), 1);
+        assert_eq!(
+            prompts.concat(),
+            "? Color:\n[1] red\n[2] blue\n? Choice [1-2]: "
+        );
+        assert_eq!(prompt(&"x"), "x");
+    }
+}
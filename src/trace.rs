@@ -0,0 +1,157 @@
+use std::cell::{Cell, RefCell};
+use std::fmt::Arguments;
+use std::time::Instant;
+
+use tracing::field;
+
+use crate::InputError;
+
+thread_local! {
+    static ERROR_KIND: Cell<Option<&'static str>> = const { Cell::new(None) };
+    static SPAN: RefCell<Option<tracing::Span>> = const { RefCell::new(None) };
+}
+
+/// A stdin read being traced, from [`__with_stdin`](crate::__with_stdin).
+///
+/// Each read is a `read` span at debug level, with the prompt written
+/// during it as its `prompt` field, and ends with a debug event giving its
+/// `outcome` (`ok` or `error`), `duration`, and for failures the `error`
+/// kind: `io`, `parse`, `eof`, `attempts_exhausted`, `invalid`, `timeout`,
+/// `cancelled`, `interrupted`, or `too_long`.
+pub(crate) struct Read {
+    span: tracing::span::EnteredSpan,
+    /// The span of the read this one is nested in, if any.
+    outer: Option<tracing::Span>,
+    start: Instant,
+}
+
+impl Read {
+    pub(crate) fn start() -> Self {
+        ERROR_KIND.set(None);
+        let span = tracing::debug_span!("read", prompt = field::Empty);
+        Read {
+            outer: SPAN.replace(Some(span.clone())),
+            span: span.entered(),
+            start: Instant::now(),
+        }
+    }
+
+    pub(crate) fn finish(self, ok: bool) {
+        let duration = self.start.elapsed();
+        match ERROR_KIND.take() {
+            _ if ok => tracing::debug!(outcome = "ok", ?duration, "read finished"),
+            Some(kind) => {
+                tracing::debug!(outcome = "error", error = kind, ?duration, "read finished")
+            }
+            None => tracing::debug!(outcome = "error", ?duration, "read finished"),
+        }
+        SPAN.set(self.outer);
+        drop(self.span);
+    }
+}
+
+/// Records `prompt` on the current read's span.
+pub(crate) fn prompt(prompt: Arguments<'_>) {
+    SPAN.with_borrow(|span| {
+        if let Some(span) = span {
+            span.record("prompt", field::display(prompt));
+        }
+    });
+}
+
+/// Notes the kind of `error` for the current read's final event.
+pub(crate) fn error<E>(error: &InputError<E>) {
+    let kind = match error {
+        InputError::Io(_) => "io",
+        InputError::Parse { .. } => "parse",
+        InputError::Eof => "eof",
+        InputError::AttemptsExhausted(_) => "attempts_exhausted",
+        InputError::Invalid(_) => "invalid",
+        InputError::Timeout => "timeout",
+        InputError::Cancelled => "cancelled",
+        InputError::Interrupted => "interrupted",
+        InputError::TooLong(_) => "too_long",
+    };
+    ERROR_KIND.set(Some(kind));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Collects the fields of every span and event, as `name=value` lines.
+    #[derive(Clone, Default)]
+    struct Collector(Arc<Mutex<Vec<String>>>);
+
+    struct Fields<'a>(&'a mut String);
+
+    impl field::Visit for Fields<'_> {
+        fn record_debug(&mut self, field: &field::Field, value: &dyn std::fmt::Debug) {
+            if field.name() != "duration" {
+                self.0.push_str(&format!("{}={:?} ", field.name(), value));
+            }
+        }
+    }
+
+    impl Subscriber for Collector {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut line = format!("span {} ", span.metadata().name());
+            span.record(&mut Fields(&mut line));
+            self.0.lock().unwrap().push(line);
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, values: &Record<'_>) {
+            let mut line = String::from("record ");
+            values.record(&mut Fields(&mut line));
+            self.0.lock().unwrap().push(line);
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut line = String::from("event ");
+            event.record(&mut Fields(&mut line));
+            self.0.lock().unwrap().push(line);
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    /// A read's span records its prompt, and its event the outcome and error kind.
+    #[test]
+    fn test_traces_reads() {
+        let collector = Collector::default();
+        tracing::subscriber::with_default(collector.clone(), || {
+            crate::set_input_source(Box::new("7\nx\n".as_bytes()));
+            let _: Option<u8> = crate::input!(u8, "Age: ").unwrap();
+            let _ = crate::input!(u8, "Again: ");
+            let _ = crate::read_input::<u8>();
+            crate::reset_input_source();
+        });
+        let lines = collector.0.lock().unwrap().join("\n");
+        assert_eq!(
+            lines,
+            [
+                "span read ",
+                "record prompt=Age:  ",
+                r#"event message=read finished outcome="ok" "#,
+                "span read ",
+                "record prompt=Again:  ",
+                r#"event message=read finished outcome="error" error="parse" "#,
+                "span read ",
+                r#"event message=read finished outcome="error" error="eof" "#,
+            ]
+            .join("\n")
+        );
+    }
+}
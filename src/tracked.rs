@@ -0,0 +1,141 @@
+use std::io::{self, BufRead, Read};
+
+use crate::InputError;
+
+/// A reader wrapper that counts the lines and bytes consumed through it.
+///
+/// Any of the crate's `*_from` functions can read through it; afterwards
+/// [`TrackedReader::locate`] attaches the current position to an error, so
+/// failures deep in a large input can be reported precisely.
+///
+/// # Usage:
+/// ```
+/// use input_macro::{read_input_from, TrackedReader};
+///
+/// let mut reader = TrackedReader::new(std::io::Cursor::new("1\n2\nthree\n"));
+/// let err = loop {
+///     if let Err(err) = read_input_from::<_, i32>(&mut reader, None) {
+///         break reader.locate(err);
+///     }
+/// };
+/// assert_eq!(err.line, 3);
+/// assert_eq!(err.to_string(), "line 3: Parse error: invalid digit found in string (input: \"three\")");
+/// ```
+#[derive(Debug)]
+pub struct TrackedReader<R> {
+    inner: R,
+    newlines: usize,
+    offset: u64,
+    mid_line: bool,
+}
+
+impl<R> TrackedReader<R> {
+    /// Wraps `inner`, starting the count at line 1, byte 0.
+    pub fn new(inner: R) -> Self {
+        TrackedReader {
+            inner,
+            newlines: 0,
+            offset: 0,
+            mid_line: false,
+        }
+    }
+
+    /// Returns the one-based number of the line most recently read from, or 0
+    /// if nothing has been read yet.
+    pub fn line(&self) -> usize {
+        self.newlines + usize::from(self.mid_line)
+    }
+
+    /// Returns the number of bytes consumed so far.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Attaches the current line and byte offset to `error`.
+    pub fn locate<E>(&self, error: InputError<E>) -> LineError<E> {
+        LineError {
+            line: self.line(),
+            offset: self.offset,
+            error,
+        }
+    }
+
+    /// Returns the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: BufRead> Read for TrackedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        crate::buffered::read_from_buf(self, buf)
+    }
+}
+
+impl<R: BufRead> BufRead for TrackedReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let (newlines, mid_line) = (&mut self.newlines, &mut self.mid_line);
+        crate::buffered::consume_with(&mut self.inner, amt, |consumed| {
+            *newlines += consumed.iter().filter(|&&b| b == b'\n').count();
+            *mid_line = consumed.last() != Some(&b'\n');
+        });
+        self.offset += amt as u64;
+    }
+}
+
+/// An [`InputError`] together with where in the input it happened.
+#[derive(Debug)]
+pub struct LineError<E> {
+    /// One-based number of the line being read when the error occurred.
+    pub line: usize,
+    /// Bytes consumed before the error was located.
+    pub offset: u64,
+    /// The underlying error.
+    pub error: InputError<E>,
+}
+
+impl<E: std::fmt::Display + std::fmt::Debug> std::fmt::Display for LineError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+impl<E: std::fmt::Display + std::fmt::Debug> std::error::Error for LineError<E> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{read_input_from, Scanner};
+    use std::io::Cursor;
+
+    /// Lines and offsets advance with each line read.
+    #[test]
+    fn test_counts_lines_and_bytes() {
+        let mut reader = TrackedReader::new(Cursor::new("10\r\n20\nx"));
+        assert_eq!((reader.line(), reader.offset()), (0, 0));
+        let _: i32 = read_input_from(&mut reader, None).unwrap();
+        assert_eq!((reader.line(), reader.offset()), (1, 4));
+        let _: i32 = read_input_from(&mut reader, None).unwrap();
+        assert_eq!((reader.line(), reader.offset()), (2, 7));
+
+        let err = read_input_from::<_, i32>(&mut reader, None).unwrap_err();
+        let err = reader.locate(err);
+        assert_eq!((err.line, err.offset), (3, 8));
+        assert!(matches!(err.error, InputError::Parse { .. }));
+    }
+
+    /// Token reads report the line they stopped on.
+    #[test]
+    fn test_scanner_through_tracked_reader() {
+        let mut scanner = Scanner::new(TrackedReader::new(Cursor::new("1 2\n3 four\n")));
+        for _ in 0..3 {
+            scanner.next::<i32>().unwrap();
+        }
+        assert!(scanner.next::<i32>().is_err());
+        assert_eq!(scanner.into_inner().line(), 2);
+    }
+}
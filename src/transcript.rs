@@ -0,0 +1,461 @@
+use std::cell::RefCell;
+use std::io::{self, BufRead, Read, Write};
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::strip_line_ending;
+
+thread_local! {
+    static ACTIVE: RefCell<Option<Rc<RefCell<Recorder>>>> = const { RefCell::new(None) };
+}
+
+/// A prompt predicate deciding which answers are left out of a transcript.
+type Redact = Box<dyn Fn(&str) -> bool>;
+
+/// A record of an interactive session: every prompt written and every answer
+/// read on this thread, each with the time it happened, so a tool can attach
+/// what the operator actually entered to a bug report.
+///
+/// Recording starts with [`start`](Transcript::start) and covers the
+/// `input!` family of macros and the crate's stdin wrappers, whatever they
+/// read from: stdin, the line editor, or an [input source](crate::set_input_source).
+/// Prompts are recorded even when they are hidden, as for piped input.
+/// Password reads go to the terminal directly and are never recorded.
+///
+/// Each event is one line: a UTC timestamp, `prompt` or `answer`, and the
+/// text quoted and escaped as Rust's `{:?}` does, or `<redacted>` for an
+/// answer left out by [`redact`](Transcript::redact):
+///
+/// ```text
+/// 2026-10-14T09:30:00.125Z prompt "Name: "
+/// 2026-10-14T09:30:02.840Z answer "Alice"
+/// 2026-10-14T09:30:02.841Z prompt "API token: "
+/// 2026-10-14T09:30:05.002Z answer <redacted>
+/// ```
+///
+/// Errors writing to the sink are ignored, so a full disk cannot break the
+/// session being recorded. A saved transcript without redactions can be
+/// replayed with [`ScriptedReader::from_transcript`](crate::testing::ScriptedReader::from_transcript).
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::{input, Transcript};
+///
+/// let log = std::fs::File::create("session.log").unwrap();
+/// let _recording = Transcript::new(log)
+///     .redact(|prompt| prompt.contains("token"))
+///     .start();
+/// let name: Option<String> = input!("Name: ").unwrap();
+/// let token: Option<String> = input!("API token: ").unwrap();
+/// ```
+pub struct Transcript {
+    sink: Box<dyn Write>,
+    redact: Option<Redact>,
+}
+
+impl Transcript {
+    /// Creates a transcript written to `sink`, redacting nothing.
+    pub fn new(sink: impl Write + 'static) -> Self {
+        Transcript {
+            sink: Box::new(sink),
+            redact: None,
+        }
+    }
+
+    /// Leaves out each answer whose prompt, the text written just before it,
+    /// makes `redact` return `true`; use `|_| true` to leave out every answer.
+    pub fn redact(mut self, redact: impl Fn(&str) -> bool + 'static) -> Self {
+        self.redact = Some(Box::new(redact));
+        self
+    }
+
+    /// Records this thread's prompts and answers until the returned guard is
+    /// dropped, replacing any recording already in progress until then.
+    pub fn start(self) -> Recording {
+        let recorder = Rc::new(RefCell::new(Recorder {
+            transcript: self,
+            prompt: String::new(),
+            answer: Vec::new(),
+        }));
+        Recording {
+            previous: ACTIVE.with(|active| active.replace(Some(recorder))),
+        }
+    }
+}
+
+impl std::fmt::Debug for Transcript {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Transcript")
+            .field("redact", &self.redact.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Ends a [`Transcript`] recording when dropped, flushing its sink.
+#[must_use = "recording stops when the guard is dropped"]
+pub struct Recording {
+    previous: Option<Rc<RefCell<Recorder>>>,
+}
+
+impl std::fmt::Debug for Recording {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Recording").finish_non_exhaustive()
+    }
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        let recorder = ACTIVE.with(|active| active.replace(self.previous.take()));
+        if let Some(recorder) = recorder {
+            let mut recorder = recorder.borrow_mut();
+            recorder.end_answer();
+            let _ = recorder.transcript.sink.flush();
+        }
+    }
+}
+
+struct Recorder {
+    transcript: Transcript,
+    /// The most recent prompt, which decides whether the next answer is redacted.
+    prompt: String,
+    /// Bytes of the answer being read, up to its line ending.
+    answer: Vec<u8>,
+}
+
+impl Recorder {
+    fn event(&mut self, kind: &str, text: Option<&str>) {
+        let sink = &mut self.transcript.sink;
+        let _ = match text {
+            Some(text) => writeln!(sink, "{} {} {:?}", timestamp(SystemTime::now()), kind, text),
+            None => writeln!(sink, "{} {} <redacted>", timestamp(SystemTime::now()), kind),
+        };
+        let _ = sink.flush();
+    }
+
+    fn prompt(&mut self, prompt: String) {
+        self.end_answer();
+        self.event("prompt", Some(&prompt));
+        self.prompt = prompt;
+    }
+
+    fn read(&mut self, bytes: &[u8]) {
+        for line in bytes.split_inclusive(|&b| b == b'\n') {
+            self.answer.extend_from_slice(line);
+            if line.ends_with(b"\n") {
+                self.end_answer();
+            }
+        }
+    }
+
+    /// Records the answer read so far, if any.
+    fn end_answer(&mut self) {
+        if self.answer.is_empty() {
+            return;
+        }
+        let answer = String::from_utf8_lossy(&self.answer).into_owned();
+        self.answer.clear();
+        let redacted = self
+            .transcript
+            .redact
+            .as_ref()
+            .is_some_and(|redact| redact(&self.prompt));
+        let answer = strip_line_ending(&answer);
+        self.event("answer", (!redacted).then_some(answer));
+    }
+}
+
+fn active() -> Option<Rc<RefCell<Recorder>>> {
+    ACTIVE.with(|active| active.borrow().clone())
+}
+
+/// Runs `f` on `reader`, recording what it reads if a transcript is active.
+pub(crate) fn record_reads<T>(
+    reader: &mut dyn BufRead,
+    f: impl FnOnce(&mut dyn BufRead) -> T,
+) -> T {
+    match active() {
+        Some(recorder) => {
+            let result = f(&mut RecordingReader {
+                inner: reader,
+                recorder: &recorder,
+            });
+            // A read may stop short of a line ending, e.g. at EOF
+            recorder.borrow_mut().end_answer();
+            result
+        }
+        None => f(reader),
+    }
+}
+
+/// Runs `f` on `output`, recording what it writes as one prompt if a
+/// transcript is active.
+pub(crate) fn record_prompts<T>(output: &mut dyn Write, f: impl FnOnce(&mut dyn Write) -> T) -> T {
+    match active() {
+        Some(recorder) => {
+            let mut writer = RecordingWriter {
+                inner: output,
+                written: Vec::new(),
+            };
+            let result = f(&mut writer);
+            if !writer.written.is_empty() {
+                let prompt = String::from_utf8_lossy(&writer.written).into_owned();
+                recorder.borrow_mut().prompt(prompt);
+            }
+            result
+        }
+        None => f(output),
+    }
+}
+
+struct RecordingReader<'a> {
+    inner: &'a mut dyn BufRead,
+    recorder: &'a RefCell<Recorder>,
+}
+
+impl Read for RecordingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.recorder.borrow_mut().read(&buf[..n]);
+        Ok(n)
+    }
+}
+
+impl BufRead for RecordingReader<'_> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let recorder = self.recorder;
+        crate::buffered::consume_with(&mut self.inner, amt, |bytes| {
+            recorder.borrow_mut().read(bytes)
+        });
+    }
+}
+
+struct RecordingWriter<'a> {
+    inner: &'a mut dyn Write,
+    written: Vec<u8>,
+}
+
+impl Write for RecordingWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.written.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// One line of a transcript.
+pub(crate) enum Event {
+    Prompt(String),
+    /// `None` if the answer was redacted.
+    Answer(Option<String>),
+}
+
+/// Reads the events of a transcript written by [`Transcript`], ignoring
+/// the timestamps.
+///
+/// Fails if reading fails or a line is not an event.
+pub(crate) fn parse(reader: impl BufRead) -> io::Result<Vec<Event>> {
+    let mut events = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut parts = line.splitn(3, ' ').skip(1);
+        let event = match (parts.next(), parts.next()) {
+            (Some("prompt"), Some(text)) => unquote(text).map(Event::Prompt),
+            (Some("answer"), Some("<redacted>")) => Some(Event::Answer(None)),
+            (Some("answer"), Some(text)) => unquote(text).map(|text| Event::Answer(Some(text))),
+            _ => None,
+        };
+        events.push(event.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: not a transcript event", index + 1),
+            )
+        })?);
+    }
+    Ok(events)
+}
+
+/// Reverses the quoting and escaping of `{:?}` on a string.
+fn unquote(text: &str) -> Option<String> {
+    let mut chars = text.strip_prefix('"')?.strip_suffix('"')?.chars();
+    let mut out = String::new();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        out.push(match chars.next()? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            'u' => {
+                let rest = chars.as_str().strip_prefix('{')?;
+                let (hex, rest) = rest.split_once('}')?;
+                chars = rest.chars();
+                char::from_u32(u32::from_str_radix(hex, 16).ok()?)?
+            }
+            c @ ('\\' | '"' | '\'') => c,
+            _ => return None,
+        });
+    }
+    Some(out)
+}
+
+/// Formats `time` as an RFC 3339 UTC timestamp with milliseconds.
+fn timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+    // Days to a civil date, after Howard Hinnant's `civil_from_days`
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::ScriptedReader;
+    use std::time::Duration;
+
+    /// A sink that can be read after the transcript that owns it is gone.
+    #[derive(Clone, Default)]
+    struct Shared(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Events without their timestamps.
+    fn events(sink: &Shared) -> Vec<String> {
+        let text = String::from_utf8(sink.0.borrow().clone()).unwrap();
+        text.lines()
+            .map(|line| line.split_once(' ').unwrap().1.to_string())
+            .collect()
+    }
+
+    /// Prompts and answers are recorded in order, with retries and redaction.
+    #[test]
+    fn test_records_session() {
+        let sink = Shared::default();
+        ScriptedReader::new()
+            .respond("Alice")
+            .respond("x")
+            .respond("7")
+            .respond("hunter2")
+            .run(|| {
+                let _recording = Transcript::new(sink.clone())
+                    .redact(|prompt| prompt.starts_with("Password"))
+                    .start();
+                let _: Option<String> = crate::input!("Name: ").unwrap();
+                let _ = crate::Input::<u8>::new()
+                    .prompt("Age: ")
+                    .max_attempts(2)
+                    .read();
+                let _: Option<String> = crate::input!("Password: ").unwrap();
+            });
+        assert_eq!(
+            events(&sink),
+            [
+                r#"prompt "Name: ""#,
+                r#"answer "Alice""#,
+                r#"prompt "Age: ""#,
+                r#"answer "x""#,
+                r#"prompt "Parse error: invalid digit found in string (input: \"x\")\n""#,
+                r#"prompt "Age: ""#,
+                r#"answer "7""#,
+                r#"prompt "Password: ""#,
+                "answer <redacted>",
+            ]
+        );
+    }
+
+    /// Nothing is recorded once the guard is dropped.
+    #[test]
+    fn test_stops_when_dropped() {
+        let sink = Shared::default();
+        crate::set_input_source(Box::new("1\n2".as_bytes()));
+        let recording = Transcript::new(sink.clone()).start();
+        assert_eq!(crate::read_input::<u8>().unwrap(), 1);
+        drop(recording);
+        assert_eq!(crate::read_input::<u8>().unwrap(), 2);
+        crate::reset_input_source();
+        assert_eq!(events(&sink), [r#"answer "1""#]);
+    }
+
+    /// Prompts written to the caller's own writer get the prefix and are
+    /// recorded too.
+    #[test]
+    fn test_records_prompts_to_writer() {
+        let sink = Shared::default();
+        let recording = Transcript::new(sink.clone()).start();
+        crate::set_prompt_prefix("app> ");
+        let mut prompts = Vec::new();
+        let prompt = Some(format_args!("n: "));
+        let n: u8 = crate::read_input_to(&mut "7\n".as_bytes(), &mut prompts, prompt).unwrap();
+        crate::set_prompt_prefix("");
+        drop(recording);
+        assert_eq!((n, prompts.as_slice()), (7, &b"app> n: "[..]));
+        assert_eq!(events(&sink)[0], r#"prompt "app> n: ""#);
+    }
+
+    /// Recorded text reads back exactly, and malformed lines are rejected.
+    #[test]
+    fn test_parse() {
+        let text = "a\"b\\c\n\t\r\0\u{7f}é '";
+        let log = format!(
+            "t prompt {:?}\n\nt answer <redacted>\nt answer \"\"\n",
+            text
+        );
+        let events = parse(log.as_bytes()).unwrap();
+        assert!(matches!(&events[0], Event::Prompt(prompt) if prompt == text));
+        assert!(matches!(events[1], Event::Answer(None)));
+        assert!(matches!(&events[2], Event::Answer(Some(answer)) if answer.is_empty()));
+        let err = parse("t answer \"x\nt prompt \"y\"".as_bytes())
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "line 1: not a transcript event");
+    }
+
+    /// Timestamps are RFC 3339 in UTC.
+    #[test]
+    fn test_timestamp() {
+        assert_eq!(timestamp(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        let time = UNIX_EPOCH + Duration::from_millis(951_827_696_789);
+        assert_eq!(timestamp(time), "2000-02-29T12:34:56.789Z");
+    }
+}
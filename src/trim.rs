@@ -0,0 +1,108 @@
+use std::fmt::Arguments;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::{parse_line, read_raw_line, strip_line_ending, InputError, Located};
+
+/// How much of a line is trimmed before it is parsed.
+///
+/// Reads strip only the line ending by default; use
+/// [`read_input_trim_from`] or [`Input::trim`](crate::Input::trim) to choose
+/// otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Trim<'a> {
+    /// Keep the line exactly as read, line ending included.
+    None,
+    /// Strip the trailing `\r`/`\n`.
+    #[default]
+    LineEndings,
+    /// Strip leading and trailing whitespace, line ending included.
+    Whitespace,
+    /// Strip the line ending, then these characters from both ends.
+    Custom(&'a [char]),
+}
+
+impl Trim<'_> {
+    /// Trims `line` according to the policy.
+    ///
+    /// # Usage:
+    /// ```
+    /// use input_macro::Trim;
+    ///
+    /// assert_eq!(Trim::LineEndings.apply("  42 \r\n"), "  42 ");
+    /// assert_eq!(Trim::Whitespace.apply("  42 \r\n"), "42");
+    /// assert_eq!(Trim::Custom(&['"', ' ']).apply(" \"42\"\n"), "42");
+    /// ```
+    pub fn apply<'l>(&self, line: &'l str) -> &'l str {
+        match self {
+            Trim::None => line,
+            Trim::LineEndings => strip_line_ending(line),
+            Trim::Whitespace => line.trim(),
+            Trim::Custom(chars) => strip_line_ending(line).trim_matches(*chars),
+        }
+    }
+}
+
+/// Like [`read_input_from`](crate::read_input_from), but trims the line with
+/// `trim` instead of only stripping its line ending.
+pub fn read_input_trim_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    trim: Trim<'_>,
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let mut input = String::new();
+    let line = read_raw_line(reader, prompt, &mut input)?;
+    parse_line(trim.apply(line))
+}
+
+/// A convenience wrapper around [`read_input_trim_from`] that reads from stdin.
+#[track_caller]
+pub fn read_input_trim<T>(
+    prompt: Option<Arguments<'_>>,
+    trim: Trim<'_>,
+) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::__with_stdin(|reader| read_input_trim_from(reader, prompt, trim))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Each policy keeps or strips the surrounding characters it names.
+    #[test]
+    fn test_read_input_trim() {
+        let mut reader = Cursor::new(" a \n b \n b \r\n<c>\n");
+        let read = |reader: &mut Cursor<&str>, trim| {
+            read_input_trim_from::<_, String>(reader, None, trim).unwrap()
+        };
+        assert_eq!(read(&mut reader, Trim::None), " a \n");
+        assert_eq!(read(&mut reader, Trim::LineEndings), " b ");
+        assert_eq!(read(&mut reader, Trim::Whitespace), "b");
+        assert_eq!(read(&mut reader, Trim::Custom(&['<', '>'])), "c");
+        assert!(matches!(
+            read_input_trim_from::<_, String>(&mut reader, None, Trim::None),
+            Err(InputError::Eof)
+        ));
+    }
+
+    /// Trimming whitespace lets padded numbers parse.
+    #[test]
+    fn test_trim_before_parse() {
+        let mut reader = Cursor::new("  42\t\n  42\t\n");
+        assert!(read_input_trim_from::<_, u8>(&mut reader, None, Trim::LineEndings).is_err());
+        assert_eq!(
+            read_input_trim_from::<_, u8>(&mut reader, None, Trim::Whitespace).unwrap(),
+            42
+        );
+    }
+}
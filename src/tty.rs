@@ -0,0 +1,111 @@
+use std::fmt::Arguments;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read};
+use std::str::FromStr;
+
+use crate::{read_input_to, InputError, InputSource, Located};
+
+/// Opens the controlling terminal as a `(reader, writer)` pair.
+fn open_tty() -> io::Result<(File, File)> {
+    #[cfg(windows)]
+    {
+        let input = OpenOptions::new().read(true).write(true).open("CONIN$")?;
+        let output = OpenOptions::new().read(true).write(true).open("CONOUT$")?;
+        Ok((input, output))
+    }
+    #[cfg(not(windows))]
+    {
+        let tty = OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+        Ok((tty.try_clone()?, tty))
+    }
+}
+
+/// Reads one line from the controlling terminal (`/dev/tty`, or `CONIN$` on
+/// Windows), printing the prompt there too.
+///
+/// Stdin and stdout are left alone, so a tool whose stdin is consumed by
+/// piped data can still ask the operator for confirmation, the way `ssh` and
+/// `sudo` do. Fails with `InputError::Io` if the process has no terminal.
+///
+/// On Windows, enable the `windows-console` feature to read non-ASCII input
+/// correctly whatever the console's code page (see `WideConsoleReader`).
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::read_from_tty;
+///
+/// // e.g. `cat files.txt | mytool`
+/// let answer: String = read_from_tty(Some(format_args!("Delete them all? "))).unwrap();
+/// ```
+#[track_caller]
+pub fn read_from_tty<T>(prompt: Option<Arguments<'_>>) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::source::located(tty_read(prompt))
+}
+
+/// Reads one line from the controlling terminal; see [`read_from_tty`].
+fn tty_read<T>(prompt: Option<Arguments<'_>>) -> Result<T, InputError<T::Err>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let (input, mut output) = open_tty().map_err(InputError::Io)?;
+    #[cfg(all(windows, feature = "windows-console"))]
+    let mut input = crate::WideConsoleReader::new(input).map_err(InputError::Io)?;
+    #[cfg(not(all(windows, feature = "windows-console")))]
+    let mut input = BufReader::new(input);
+    read_input_to(&mut input, &mut output, prompt)
+}
+
+/// The controlling terminal as an [`InputSource`], which is always interactive.
+///
+/// Like [`read_from_tty`], this reads from the terminal even when stdin is
+/// redirected. Prompts written while reading from it still go to the usual
+/// prompt output.
+///
+/// # Usage:
+/// ```no_run
+/// use input_macro::{Input, TtySource};
+///
+/// let mut tty = TtySource::open().unwrap();
+/// let proceed: bool = Input::new().prompt("Proceed? ").source(&mut tty).read().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct TtySource {
+    input: BufReader<File>,
+}
+
+impl TtySource {
+    /// Opens the controlling terminal, failing if the process has none.
+    pub fn open() -> io::Result<Self> {
+        let (input, _) = open_tty()?;
+        Ok(TtySource {
+            input: BufReader::new(input),
+        })
+    }
+}
+
+impl Read for TtySource {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.input.read(buf)
+    }
+}
+
+impl BufRead for TtySource {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.input.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.input.consume(amt);
+    }
+}
+
+impl InputSource for TtySource {
+    fn is_interactive(&self) -> bool {
+        true
+    }
+}
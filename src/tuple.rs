@@ -0,0 +1,110 @@
+use std::fmt::Arguments;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::{
+    __scan_finish, __scan_token, read_with_from, BoxError, InputError, Located, TokenError,
+};
+
+/// A tuple whose fields parse, in order, from the whitespace-separated tokens
+/// of one line.
+///
+/// Implemented for tuples of up to 12 `FromStr` fields. The line must hold
+/// exactly one token per field.
+pub trait FromTokens: Sized {
+    /// Parses `line` field by field.
+    fn from_tokens(line: &str) -> Result<Self, TokenError<BoxError>>;
+}
+
+macro_rules! impl_from_tokens {
+    ($($t:ident),+) => {
+        impl<$($t),+> FromTokens for ($($t,)+)
+        where
+            $($t: FromStr, $t::Err: Into<BoxError>,)+
+        {
+            fn from_tokens(line: &str) -> Result<Self, TokenError<BoxError>> {
+                let mut tokens = line.split_whitespace();
+                let mut index = 0;
+                let values = ($(__scan_token::<$t, _>(&mut tokens, &mut index)?,)+);
+                __scan_finish(&mut tokens, index)?;
+                Ok(values)
+            }
+        }
+    };
+}
+
+impl_from_tokens!(A);
+impl_from_tokens!(A, B);
+impl_from_tokens!(A, B, C);
+impl_from_tokens!(A, B, C, D);
+impl_from_tokens!(A, B, C, D, E);
+impl_from_tokens!(A, B, C, D, E, F);
+impl_from_tokens!(A, B, C, D, E, F, G);
+impl_from_tokens!(A, B, C, D, E, F, G, H);
+impl_from_tokens!(A, B, C, D, E, F, G, H, I);
+impl_from_tokens!(A, B, C, D, E, F, G, H, I, J);
+impl_from_tokens!(A, B, C, D, E, F, G, H, I, J, K);
+impl_from_tokens!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// Prints the optional prompt, then reads one line into a tuple of differently
+/// typed fields, like [`scan!`](crate::scan) without naming each field.
+///
+/// A missing, extra, or unparsable token is reported as `InputError::Parse`
+/// holding a [`TokenError`].
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_tuple_from;
+///
+/// let mut reader = std::io::Cursor::new("Ada 36 97.5\n");
+/// let (name, age, score): (String, u8, f64) = read_tuple_from(&mut reader, None).unwrap();
+/// assert_eq!((name.as_str(), age, score), ("Ada", 36, 97.5));
+/// ```
+pub fn read_tuple_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<T, InputError<TokenError<BoxError>>>
+where
+    R: BufRead + ?Sized,
+    T: FromTokens,
+{
+    read_with_from(reader, prompt, T::from_tokens)
+}
+
+/// A convenience wrapper around [`read_tuple_from`] that reads from stdin.
+#[track_caller]
+pub fn read_tuple<T: FromTokens>(
+    prompt: Option<Arguments<'_>>,
+) -> Result<T, Located<InputError<TokenError<BoxError>>>> {
+    crate::__with_stdin(|reader| read_tuple_from(reader, prompt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Each field gets its own type, and the token count must match.
+    #[test]
+    fn test_read_tuple() {
+        let mut reader = Cursor::new("x 1 true\n1 2\n1 2 3\n");
+        let (c, n, b): (char, i64, bool) = read_tuple_from(&mut reader, None).unwrap();
+        assert_eq!((c, n, b), ('x', 1, true));
+        let res = read_tuple_from::<_, (u8, u8, u8)>(&mut reader, None);
+        assert!(matches!(
+            res,
+            Err(InputError::Parse {
+                source: TokenError::Missing { index: 2 },
+                ..
+            })
+        ));
+        let res = read_tuple_from::<_, (u8, u8)>(&mut reader, None);
+        assert!(matches!(
+            res,
+            Err(InputError::Parse {
+                source: TokenError::Unexpected { index: 2, .. },
+                ..
+            })
+        ));
+    }
+}
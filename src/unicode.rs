@@ -0,0 +1,102 @@
+use std::borrow::Cow;
+use std::fmt::Arguments;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use unicode_normalization::{is_nfc_quick, is_nfkc_quick, IsNormalized, UnicodeNormalization};
+
+use crate::{parse_line, read_trimmed_line, InputError, Located};
+
+/// A Unicode normalization form applied to input before it is parsed.
+///
+/// Normalizing lets text typed as composed characters (`"é"`) and as a base
+/// letter plus combining mark (`"e\u{301}"`) parse and validate the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Normalization {
+    /// Canonical composition (NFC).
+    Nfc,
+    /// Compatibility composition (NFKC), which also folds variants such as
+    /// full-width digits and ligatures into their plain forms.
+    Nfkc,
+}
+
+impl Normalization {
+    /// Returns `text` in this normalization form, borrowing it if it already is.
+    ///
+    /// # Usage:
+    /// ```
+    /// use input_macro::Normalization;
+    ///
+    /// assert_eq!(Normalization::Nfc.apply("e\u{301}"), "\u{e9}");
+    /// assert_eq!(Normalization::Nfkc.apply("\u{ff14}\u{ff12}"), "42");
+    /// ```
+    pub fn apply(self, text: &str) -> Cow<'_, str> {
+        let quick = match self {
+            Normalization::Nfc => is_nfc_quick(text.chars()),
+            Normalization::Nfkc => is_nfkc_quick(text.chars()),
+        };
+        if quick == IsNormalized::Yes {
+            return Cow::Borrowed(text);
+        }
+        match self {
+            Normalization::Nfc => Cow::Owned(text.nfc().collect()),
+            Normalization::Nfkc => Cow::Owned(text.nfkc().collect()),
+        }
+    }
+}
+
+/// Like [`read_input_from`](crate::read_input_from), but normalizes the line
+/// to `form` before parsing it.
+pub fn read_input_normalized_from<R, T>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+    form: Normalization,
+) -> Result<T, InputError<T::Err>>
+where
+    R: BufRead + ?Sized,
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    let mut input = String::new();
+    let line = read_trimmed_line(reader, prompt, &mut input)?;
+    parse_line(&form.apply(line))
+}
+
+/// A convenience wrapper around [`read_input_normalized_from`] that reads from stdin.
+#[track_caller]
+pub fn read_input_normalized<T>(
+    prompt: Option<Arguments<'_>>,
+    form: Normalization,
+) -> Result<T, Located<InputError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    crate::__with_stdin(|reader| read_input_normalized_from(reader, prompt, form))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Decomposed input reads back composed, and NFKC folds full-width digits.
+    #[test]
+    fn test_read_input_normalized() {
+        let mut reader = Cursor::new("Jose\u{301}\n\u{ff18}\u{ff10}\n");
+        let name: String =
+            read_input_normalized_from(&mut reader, None, Normalization::Nfc).unwrap();
+        assert_eq!(name, "Jos\u{e9}");
+        let port: u16 = read_input_normalized_from(&mut reader, None, Normalization::Nfkc).unwrap();
+        assert_eq!(port, 80);
+    }
+
+    /// Already-normalized text is borrowed rather than copied.
+    #[test]
+    fn test_apply_borrows_normalized_text() {
+        assert!(matches!(
+            Normalization::Nfc.apply("plain"),
+            Cow::Borrowed("plain")
+        ));
+    }
+}
@@ -0,0 +1,138 @@
+use std::fmt::Arguments;
+use std::io::BufRead;
+
+use crate::{read_trimmed_line, InputError, Located};
+
+/// The error returned when a line cannot be split into shell words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShellWordsError {
+    /// A quote of this kind was opened but not closed.
+    UnclosedQuote(char),
+    /// The line ended with a backslash that escapes nothing.
+    TrailingBackslash,
+}
+
+impl std::fmt::Display for ShellWordsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShellWordsError::UnclosedQuote(quote) => write!(f, "missing closing {}", quote),
+            ShellWordsError::TrailingBackslash => write!(f, "trailing backslash"),
+        }
+    }
+}
+
+impl std::error::Error for ShellWordsError {}
+
+/// Prints the optional prompt, then reads one line and splits it into words
+/// the way a POSIX shell would, without expanding anything.
+///
+/// Words are separated by whitespace. Single quotes keep everything up to
+/// the next single quote; double quotes do too, except that a backslash
+/// escapes `"`, `\`, `$`, and `` ` ``. Outside quotes a backslash escapes any
+/// character. An unbalanced quote or a trailing backslash is reported as
+/// `InputError::Parse` holding a [`ShellWordsError`].
+///
+/// # Usage:
+/// ```
+/// use input_macro::read_shell_words_from;
+///
+/// let mut reader = std::io::Cursor::new("add \"New York\" --force\n");
+/// let words = read_shell_words_from(&mut reader, Some(format_args!("> "))).unwrap();
+/// assert_eq!(words, ["add", "New York", "--force"]);
+/// ```
+pub fn read_shell_words_from<R>(
+    reader: &mut R,
+    prompt: Option<Arguments<'_>>,
+) -> Result<Vec<String>, InputError<ShellWordsError>>
+where
+    R: BufRead + ?Sized,
+{
+    let mut input = String::new();
+    let line = read_trimmed_line(reader, prompt, &mut input)?;
+    split_shell_words(line).map_err(|source| InputError::parse(source, line))
+}
+
+/// A convenience wrapper around [`read_shell_words_from`] that reads from stdin.
+#[track_caller]
+pub fn read_shell_words(
+    prompt: Option<Arguments<'_>>,
+) -> Result<Vec<String>, Located<InputError<ShellWordsError>>> {
+    crate::__with_stdin(|reader| read_shell_words_from(reader, prompt))
+}
+
+fn split_shell_words(line: &str) -> Result<Vec<String>, ShellWordsError> {
+    let mut words = Vec::new();
+    // `None` between words, so `""` still yields an empty word
+    let mut word: Option<String> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => words.extend(word.take()),
+            '\\' => {
+                let escaped = chars.next().ok_or(ShellWordsError::TrailingBackslash)?;
+                word.get_or_insert_with(String::new).push(escaped);
+            }
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next().ok_or(ShellWordsError::UnclosedQuote('\''))? {
+                        '\'' => break,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next().ok_or(ShellWordsError::UnclosedQuote('"'))? {
+                        '"' => break,
+                        '\\' => match chars.next().ok_or(ShellWordsError::UnclosedQuote('"'))? {
+                            c @ ('"' | '\\' | '$' | '`') => word.push(c),
+                            c => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Quotes group words and may join with surrounding text; escapes keep characters literal.
+    #[test]
+    fn test_split_shell_words() {
+        let split = |line| split_shell_words(line).unwrap();
+        assert_eq!(split("  a\tb  "), ["a", "b"]);
+        assert_eq!(split(r#"tag 'it''s' "x"y"#), ["tag", "its", "xy"]);
+        assert_eq!(split(r#"a\ b "\"q\" \n" '\'"#), ["a b", "\"q\" \\n", "\\"]);
+        assert_eq!(split(r#"'' """#), ["", ""]);
+        assert!(split("").is_empty());
+    }
+
+    /// Unbalanced quotes and dangling escapes are errors.
+    #[test]
+    fn test_split_shell_words_errors() {
+        assert_eq!(
+            split_shell_words("say \"hi"),
+            Err(ShellWordsError::UnclosedQuote('"'))
+        );
+        assert_eq!(
+            split_shell_words("it's"),
+            Err(ShellWordsError::UnclosedQuote('\''))
+        );
+        assert_eq!(
+            split_shell_words("a\\"),
+            Err(ShellWordsError::TrailingBackslash)
+        );
+    }
+}